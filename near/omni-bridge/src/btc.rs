@@ -11,8 +11,10 @@ use near_sdk::{
 };
 use omni_types::btc::{TokenReceiverMessage, TxOut, UTXOChainConfig};
 use omni_types::errors::BridgeError;
+use omni_types::utxo::{self, UtxoNetwork};
 use omni_types::{
-    get_native_token_address, ChainKind, DestinationChainMsg, Fee, TransferId, TransferMessage,
+    get_native_token_address, ChainKind, DestinationChainMsg, Fee, OmniAddress, TransferId,
+    TransferMessage,
 };
 use omni_utils::macros::trusted_relayer;
 use omni_utils::near_expect::NearExpect;
@@ -110,7 +112,7 @@ impl Contract {
     ) -> PromiseOrValue<()> {
         if matches!(call_result, Ok(result) if result.0 > 0) {
             let token_fee = transfer_msg.fee.fee.0;
-            self.send_fee_internal(&transfer_msg, fee_recipient, token_fee)
+            self.send_fee_internal(&transfer_msg, fee_recipient, transfer_owner, token_fee)
         } else {
             let required_storage_balance =
                 self.add_transfer_message(transfer_msg, transfer_owner.clone());
@@ -133,6 +135,7 @@ impl Contract {
         utxo_chain_connector_id: AccountId,
         utxo_chain_token_id: AccountId,
         decimals: u8,
+        network: UtxoNetwork,
     ) {
         let storage_usage = env::storage_usage();
         let token_address = get_native_token_address(chain_kind)
@@ -145,6 +148,7 @@ impl Contract {
             UTXOChainConfig {
                 connector: utxo_chain_connector_id,
                 token_id: utxo_chain_token_id.clone(),
+                network,
             },
         );
 
@@ -203,3 +207,28 @@ impl Contract {
             .clone()
     }
 }
+
+impl Contract {
+    /// Panics if `recipient` is not a valid address on the network of its UTXO chain connector.
+    pub(crate) fn validate_utxo_recipient(&self, recipient: &OmniAddress) {
+        self.try_validate_utxo_recipient(recipient)
+            .unwrap_or_else(|err| env::panic_str(err.to_string().as_str()));
+    }
+
+    pub(crate) fn try_validate_utxo_recipient(
+        &self,
+        recipient: &OmniAddress,
+    ) -> Result<(), BridgeError> {
+        let (OmniAddress::Btc(address) | OmniAddress::Zcash(address)) = recipient else {
+            return Ok(());
+        };
+
+        let chain_kind = recipient.get_chain();
+        let config = self
+            .utxo_chain_connectors
+            .get(&chain_kind)
+            .ok_or(BridgeError::InvalidRecipientChain)?;
+        utxo::validate_address(chain_kind, address, config.network)
+            .map_err(|_| BridgeError::InvalidRecipientAddress)
+    }
+}
@@ -22,29 +22,33 @@ use omni_types::locker_args::{
     StorageDepositAction,
 };
 use omni_types::mpc_types::SignatureResponse;
-use omni_types::near_events::OmniBridgeEvent;
+use omni_types::near_events::{
+    DestinationToken, EventFormat, LockAction, OmniBridgeEvent, TransferEventDetails,
+};
 use omni_types::prover_result::ProverResult;
 use omni_types::{
-    get_native_token_address, BasicMetadata, BridgeOnTransferMsg, ChainKind, DestinationChainMsg,
-    FastFinTransferMsg, FastTransfer, FastTransferId, FastTransferStatus, Fee, InitTransferMsg,
-    MetadataPayload, Nonce, OmniAddress, PayloadType, SignRequest, TransferId, TransferIdKind,
+    get_native_token_address, parse_token_icon, BasicMetadata, BridgeOnTransferMsg, ChainKind,
+    DestinationChainMsg, FastFinTransferMsg, FastTransfer, FastTransferId, FastTransferStatus, Fee,
+    GlobalTransferStatus, InitTransferMsg, MetadataPayload, Nonce, OmniAddress, PayloadType,
+    ResolvedTransfer, SignRequest, TokenName, TokenSymbol, TransferId, TransferIdKind,
     TransferMessage, TransferMessagePayload, UnifiedTransferId, UpdateFee, UtxoFinTransferMsg,
-    H160,
+    H160, H256,
 };
 use omni_utils::macros::trusted_relayer;
 use omni_utils::near_expect::NearExpect;
 use omni_utils::promise::PromiseOrPromiseIndexOrValue;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use storage::{
     Decimals, FastTransferStatusStorage, TransferMessageStorage, TransferMessageStorageValue,
     NEP141_DEPOSIT,
 };
-use token_lock::LockAction;
+use token_allowlist::TokenReviewRequest;
 
 mod btc;
 mod migrate;
 mod storage;
+mod token_allowlist;
 mod token_lock;
 
 #[cfg(test)]
@@ -67,11 +71,16 @@ const WNEAR_WITHDRAW_GAS: Gas = Gas::from_tgas(5);
 const NEAR_WITHDRAW_CALLBACK_GAS: Gas = Gas::from_tgas(5);
 const STORAGE_BALANCE_OF_GAS: Gas = Gas::from_tgas(3);
 const STORAGE_DEPOSIT_GAS: Gas = Gas::from_tgas(3);
-const DEPLOY_TOKEN_CALLBACK_GAS: Gas = Gas::from_tgas(75);
+const DEPLOY_TOKEN_CALLBACK_GAS: Gas = Gas::from_tgas(110);
+const DEPLOY_TOKEN_BY_DEPLOYER_CALLBACK_GAS: Gas = Gas::from_tgas(5);
 const DEPLOY_TOKEN_GAS: Gas = Gas::from_tgas(50);
 const BURN_TOKEN_GAS: Gas = Gas::from_tgas(3);
 const MINT_TOKEN_GAS: Gas = Gas::from_tgas(5);
+const REFUND_WITHDRAW_CALLBACK_GAS: Gas = Gas::from_tgas(15);
 const SET_METADATA_GAS: Gas = Gas::from_tgas(10);
+const SYNC_TOKEN_METADATA_CALLBACK_GAS: Gas = Gas::from_tgas(20);
+const METADATA_SYNC_INTERVAL_NS: u64 = 24 * 60 * 60 * 1_000_000_000;
+const FREEZE_TOKEN_ACCOUNT_GAS: Gas = Gas::from_tgas(10);
 const RESOLVE_FAST_TRANSFER_GAS: Gas = Gas::from_tgas(6);
 const UTXO_FIN_TRANSFER_CALLBACK_GAS: Gas = Gas::from_tgas(10);
 const RESOLVE_UTXO_FIN_TRANSFER_GAS: Gas = Gas::from_tgas(3);
@@ -85,6 +94,8 @@ const SIGN_PATH: &str = "bridge-1";
 
 const MAX_FT_TRANSFER_CALL_RESULT: usize = 50;
 const MAX_STORAGE_BALANCE_CHECK_RESULT: usize = 150;
+// Per payer, and per batch registered by the deployment callback or `register_token_storage`
+const MAX_TOKEN_STORAGE_REGISTRATIONS: usize = 10;
 
 const PROMISE_REGISTER_ID: u64 = 0;
 
@@ -108,6 +119,12 @@ enum StorageKey {
     LockedTokens,
     DeployedTokensV2,
     _Relayers,
+    GlobalTransferHashes,
+    MetadataSyncTimestamps,
+    TokenStorageRegistrations,
+    TokenAllowlist,
+    TokenReviewRequests,
+    MetadataSequences,
 }
 
 #[derive(AccessControlRole, Deserialize, Serialize, Copy, Clone)]
@@ -127,6 +144,7 @@ pub enum Role {
     TokenLockController,
     RelayerManager,
     UnpauseManager,
+    FreezeManager,
 }
 
 #[ext_contract(ext_token)]
@@ -169,6 +187,14 @@ pub trait ExtToken {
         decimals: Option<u8>,
         icon: Option<String>,
     );
+
+    fn set_freeze_enabled(&mut self, enabled: bool);
+
+    fn freeze_account(&mut self, account_id: AccountId);
+
+    fn unfreeze_account(&mut self, account_id: AccountId);
+
+    fn wipe_frozen(&mut self, account_id: AccountId) -> U128;
 }
 
 #[near(serializers = [json])]
@@ -177,6 +203,15 @@ pub struct BtcConfig {
     pub chain_signatures_root_public_key: Option<near_sdk::PublicKey>,
 }
 
+/// Storage registration on a token that isn't deployed yet, refundable to `payer` until
+/// the token is deployed.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenStorageRegistration {
+    pub account_id: AccountId,
+    pub payer: AccountId,
+}
+
 #[ext_contract(ext_bridge_token_facory)]
 pub trait ExtBridgeTokenFactory {
     fn set_controller_for_tokens(&self, tokens_account_id: Vec<AccountId>);
@@ -244,6 +279,14 @@ pub struct Contract {
     pub utxo_chain_connectors: HashMap<ChainKind, UTXOChainConfig>,
     pub migrated_tokens: LookupMap<AccountId, AccountId>,
     pub locked_tokens: LookupMap<(ChainKind, AccountId), u128>,
+    pub event_format: EventFormat,
+    pub global_transfer_hashes: LookupMap<H256, UnifiedTransferId>,
+    pub metadata_sync_timestamps: LookupMap<AccountId, u64>,
+    pub token_storage_registrations: LookupMap<AccountId, Vec<TokenStorageRegistration>>,
+    pub token_allowlist_enabled: bool,
+    pub token_allowlist: LookupSet<OmniAddress>,
+    pub token_review_requests: UnorderedMap<OmniAddress, TokenReviewRequest>,
+    pub metadata_sequences: LookupMap<AccountId, u64>,
 }
 
 #[trusted_relayer(
@@ -310,6 +353,14 @@ impl Contract {
             utxo_chain_connectors: HashMap::new(),
             migrated_tokens: LookupMap::new(StorageKey::MigratedTokens),
             locked_tokens: LookupMap::new(StorageKey::LockedTokens),
+            event_format: EventFormat::Nep297V2,
+            global_transfer_hashes: LookupMap::new(StorageKey::GlobalTransferHashes),
+            metadata_sync_timestamps: LookupMap::new(StorageKey::MetadataSyncTimestamps),
+            token_storage_registrations: LookupMap::new(StorageKey::TokenStorageRegistrations),
+            token_allowlist_enabled: false,
+            token_allowlist: LookupSet::new(StorageKey::TokenAllowlist),
+            token_review_requests: UnorderedMap::new(StorageKey::TokenReviewRequests),
+            metadata_sequences: LookupMap::new(StorageKey::MetadataSequences),
         };
 
         contract.acl_init_super_admin(near_sdk::env::predecessor_account_id());
@@ -317,8 +368,20 @@ impl Contract {
         contract
     }
 
+    /// Signs the token metadata for deployment on other chains. The icon and reference are
+    /// only included with `with_extended_metadata`, since not every destination chain
+    /// accepts the extended payload. In token allowlist mode the token must be allowlisted.
     #[pause(except(roles(Role::DAO)))]
-    pub fn log_metadata(&self, token_id: &AccountId) -> Promise {
+    pub fn log_metadata(
+        &self,
+        token_id: &AccountId,
+        with_extended_metadata: Option<bool>,
+    ) -> Promise {
+        require!(
+            self.is_token_allowed(&OmniAddress::Near(token_id.clone())),
+            BridgeError::TokenNotAllowlisted.as_ref()
+        );
+
         ext_token::ext(token_id.clone())
             .with_static_gas(LOG_METADATA_GAS)
             .ft_metadata()
@@ -326,7 +389,7 @@ impl Contract {
                 Self::ext(env::current_account_id())
                     .with_static_gas(LOG_METADATA_CALLBACK_GAS)
                     .with_attached_deposit(env::attached_deposit())
-                    .log_metadata_callback(token_id),
+                    .log_metadata_callback(token_id, with_extended_metadata),
             )
     }
 
@@ -336,22 +399,46 @@ impl Contract {
         &self,
         #[callback] metadata: FungibleTokenMetadata,
         token_id: &AccountId,
+        with_extended_metadata: Option<bool>,
     ) -> Promise {
+        // Tokens deployed before names and symbols were bounded may exceed the bounds, so
+        // their metadata is truncated instead of rejected.
+        let name = TokenName::new_truncated(&metadata.name);
+        let symbol = TokenSymbol::new_truncated(&metadata.symbol);
         require!(
-            !metadata.name.is_empty() && !metadata.symbol.is_empty(),
+            !name.as_str().is_empty() && !symbol.as_str().is_empty(),
             BridgeError::InvalidMetadata.as_ref()
         );
 
-        let metadata_payload = MetadataPayload {
+        let mut metadata_payload = MetadataPayload {
             prefix: PayloadType::Metadata,
             token: token_id.to_string(),
-            name: metadata.name,
-            symbol: metadata.symbol,
+            name,
+            symbol,
             decimals: metadata.decimals,
+            icon: None,
+            reference: None,
+            reference_hash: None,
         };
+        if with_extended_metadata.unwrap_or(false) {
+            metadata_payload.icon = metadata.icon.as_deref().and_then(parse_token_icon);
+            metadata_payload.reference = metadata
+                .reference
+                .as_deref()
+                .and_then(|reference| reference.parse().ok());
+            metadata_payload.reference_hash = metadata
+                .reference_hash
+                .and_then(|hash| hash.0.try_into().ok())
+                .map(H256);
+            if metadata_payload.has_extended_metadata() {
+                metadata_payload.prefix = PayloadType::MetadataV2;
+            }
+        }
 
         let payload = near_sdk::env::keccak256_array(
-            borsh::to_vec(&metadata_payload).near_expect(BridgeError::Borsh),
+            metadata_payload
+                .encode_hashable()
+                .near_expect(BridgeError::Borsh),
         );
 
         ext_signer::ext(self.mpc_signer.clone())
@@ -382,7 +469,7 @@ impl Contract {
                     signature,
                     metadata_payload,
                 }
-                .to_log_string(),
+                .to_log_string(self.event_format),
             );
         }
     }
@@ -430,7 +517,7 @@ impl Contract {
                     &OmniBridgeEvent::UpdateFeeEvent {
                         transfer_message: transfer.message,
                     }
-                    .to_log_string(),
+                    .to_log_string(self.event_format),
                 );
             }
             UpdateFee::Proof(_) => {
@@ -454,7 +541,10 @@ impl Contract {
         fee_recipient: Option<AccountId>,
         fee: &Option<Fee>,
     ) -> Promise {
-        let transfer_message = self.get_transfer_message(transfer_id);
+        let TransferMessageStorageValue {
+            message: transfer_message,
+            owner: storage_owner,
+        } = self.get_transfer_message_storage(transfer_id);
 
         if let Some(fee) = &fee {
             require!(
@@ -492,6 +582,19 @@ impl Contract {
             .and_then(|s| s.destination_msg())
             .unwrap_or_default();
 
+        let details = TransferEventDetails {
+            relayer: env::predecessor_account_id(),
+            storage_owner: Some(storage_owner),
+            destination_token: Some(DestinationToken {
+                token_address: token_address.clone(),
+                decimals: decimals.decimals,
+                origin_decimals: decimals.origin_decimals,
+                normalized_amount: U128(amount_to_transfer),
+                normalized_fee: U128(Self::normalize_amount(transfer_message.fee.fee.0, decimals)),
+            }),
+            lock_actions: Vec::new(),
+        };
+
         let transfer_payload = TransferMessagePayload {
             prefix: PayloadType::TransferMessage,
             destination_nonce: transfer_message.destination_nonce,
@@ -501,6 +604,7 @@ impl Contract {
             recipient: transfer_message.recipient,
             fee_recipient,
             message,
+            global_transfer_hash: transfer_id.global_hash(),
         };
 
         let payload = near_sdk::env::keccak256_array(
@@ -520,7 +624,7 @@ impl Contract {
             .then(
                 Self::ext(env::current_account_id())
                     .with_static_gas(SIGN_TRANSFER_CALLBACK_GAS)
-                    .sign_transfer_callback(transfer_payload, &transfer_message.fee),
+                    .sign_transfer_callback(transfer_payload, &transfer_message.fee, details),
             )
     }
 
@@ -532,33 +636,8 @@ impl Contract {
         amount: U128,
         init_transfer_msg: InitTransferMsg,
     ) -> PromiseOrPromiseIndexOrValue<U128> {
-        require!(
-            init_transfer_msg.recipient.get_chain() != ChainKind::Near,
-            BridgeError::InvalidRecipientChain.as_ref()
-        );
-
-        self.current_origin_nonce += 1;
-        let destination_nonce =
-            self.get_next_destination_nonce(init_transfer_msg.get_destination_chain());
-
-        let transfer_message = TransferMessage {
-            origin_nonce: self.current_origin_nonce,
-            token: OmniAddress::Near(token_id),
-            amount,
-            recipient: init_transfer_msg.recipient,
-            fee: Fee {
-                fee: init_transfer_msg.fee,
-                native_fee: init_transfer_msg.native_token_fee,
-            },
-            sender: OmniAddress::Near(sender_id),
-            msg: init_transfer_msg.msg.map(String::from).unwrap_or_default(),
-            destination_nonce,
-            origin_transfer_id: None,
-        };
-        require!(
-            transfer_message.fee.fee < transfer_message.amount,
-            BridgeError::InvalidFee.as_ref()
-        );
+        let transfer_message =
+            self.new_init_transfer_message(sender_id, token_id, amount, &init_transfer_msg);
 
         let required_storage_balance =
             self.required_balance_for_init_transfer_message(transfer_message.clone());
@@ -655,6 +734,7 @@ impl Contract {
         #[callback_result] call_result: Result<SignatureResponse, PromiseError>,
         #[serializer(borsh)] message_payload: TransferMessagePayload,
         #[serializer(borsh)] fee: &Fee,
+        #[serializer(borsh)] details: TransferEventDetails,
     ) {
         if let Ok(signature) = call_result {
             if fee.is_zero() {
@@ -665,8 +745,9 @@ impl Contract {
                 &OmniBridgeEvent::SignTransferEvent {
                     signature,
                     message_payload,
+                    details: Some(details),
                 }
-                .to_log_string(),
+                .to_log_string(self.event_format),
             );
         }
     }
@@ -870,7 +951,7 @@ impl Contract {
                 fast_transfer: fast_transfer.clone(),
                 new_transfer_id: None,
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
 
         let amount_without_fee = U128(
@@ -970,7 +1051,7 @@ impl Contract {
                 fast_transfer: fast_transfer.clone(),
                 new_transfer_id: Some(new_transfer_id),
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
 
         self.update_storage_balance(storage_payer, required_balance, NearToken::from_near(0));
@@ -1040,7 +1121,7 @@ impl Contract {
                     utxo_transfer_message: utxo_fin_transfer_msg,
                     new_transfer_id: None,
                 }
-                .to_log_string(),
+                .to_log_string(self.event_format),
             );
 
             U128(0)
@@ -1095,7 +1176,10 @@ impl Contract {
             BridgeError::UnknownFactory.as_ref()
         );
 
-        let transfer_message = self.remove_transfer_message(fin_transfer.transfer_id);
+        let TransferMessageStorageValue {
+            message: transfer_message,
+            owner: storage_owner,
+        } = self.remove_transfer_message(fin_transfer.transfer_id);
 
         if let Some(origin_transfer_id) = transfer_message.origin_transfer_id.clone() {
             let mut fast_transfer = FastTransfer::from_transfer(
@@ -1134,7 +1218,7 @@ impl Contract {
         // due to floor division, the difference naturally captures the normalization remainder.
         let fee = transfer_message.amount.0 - denormalized_amount;
 
-        self.send_fee_internal(&transfer_message, fee_recipient, fee)
+        self.send_fee_internal(&transfer_message, fee_recipient, storage_owner, fee)
     }
 
     #[payable]
@@ -1144,38 +1228,135 @@ impl Contract {
             Self::ext(env::current_account_id())
                 .with_attached_deposit(NO_DEPOSIT)
                 .with_static_gas(DEPLOY_TOKEN_CALLBACK_GAS)
-                .deploy_token_callback(near_sdk::env::attached_deposit()),
+                .deploy_token_callback(
+                    near_sdk::env::attached_deposit(),
+                    near_sdk::env::predecessor_account_id(),
+                ),
         )
     }
 
+    /// Deploys the token, or queues it for review if the token allowlist mode is enabled
+    /// and the token isn't allowlisted.
     #[private]
     pub fn deploy_token_callback(
         &mut self,
         attached_deposit: NearToken,
+        requester: AccountId,
         #[callback_result]
         #[serializer(borsh)]
         call_result: Result<ProverResult, PromiseError>,
-    ) -> Promise {
+    ) -> PromiseOrValue<()> {
         let Ok(ProverResult::LogMetadata(metadata)) = call_result else {
             env::panic_str(BridgeError::InvalidProofMessage.to_string().as_str());
         };
 
         let chain = metadata.emitter_address.get_chain();
         require!(
-            self.factories.get(&chain) == Some(metadata.emitter_address),
+            self.factories.get(&chain).as_ref() == Some(&metadata.emitter_address),
             BridgeError::UnknownFactory.as_ref()
         );
 
+        let token_address = metadata.token_address.clone();
+        if !self.is_token_allowed(&token_address) {
+            self.add_token_review_request(
+                token_address,
+                requester,
+                attached_deposit,
+                Some(metadata),
+            );
+            return PromiseOrValue::Value(());
+        }
+
+        let sequence = metadata.sequence;
         self.deploy_token_internal(
             chain,
-            &metadata.token_address,
-            BasicMetadata {
-                name: metadata.name,
-                symbol: metadata.symbol,
-                decimals: metadata.decimals,
-            },
+            &token_address,
+            BasicMetadata::from(metadata),
+            sequence,
             attached_deposit,
         )
+        .into()
+    }
+
+    /// Updates the name, symbol, icon and reference of a deployed token from a `LogMetadata`
+    /// proof emitted by the factory on the token's origin chain. Missing icon and reference
+    /// fields keep their current values. Decimals are never changed. The proof's origin
+    /// sequence must be newer than the one the token was deployed or last synced with, so
+    /// old proofs can't roll the metadata back. Each token can only be synced once per
+    /// `METADATA_SYNC_INTERVAL_NS`.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn sync_token_metadata(
+        &mut self,
+        #[serializer(borsh)] chain_kind: ChainKind,
+        #[serializer(borsh)] prover_args: Vec<u8>,
+    ) -> Promise {
+        self.verify_proof(chain_kind, prover_args).then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(SYNC_TOKEN_METADATA_CALLBACK_GAS)
+                .sync_token_metadata_callback(),
+        )
+    }
+
+    #[private]
+    pub fn sync_token_metadata_callback(
+        &mut self,
+        #[callback_result]
+        #[serializer(borsh)]
+        call_result: Result<ProverResult, PromiseError>,
+    ) -> Promise {
+        let Ok(ProverResult::LogMetadata(metadata)) = call_result else {
+            env::panic_str(BridgeError::InvalidProofMessage.to_string().as_str());
+        };
+
+        let chain = metadata.emitter_address.get_chain();
+        require!(
+            self.factories.get(&chain) == Some(metadata.emitter_address),
+            BridgeError::UnknownFactory.as_ref()
+        );
+
+        let token = self.get_token_id(&metadata.token_address);
+        self.assert_deployed_token(&token);
+        require!(
+            self.get_token_origin_chain(&token) == chain,
+            BridgeError::NotOriginChain.as_ref()
+        );
+
+        let now = env::block_timestamp();
+        let next_sync = self
+            .metadata_sync_timestamps
+            .get(&token)
+            .map_or(0, |last_sync| {
+                last_sync.saturating_add(METADATA_SYNC_INTERVAL_NS)
+            });
+        require!(
+            now >= next_sync,
+            BridgeError::MetadataSyncRateLimited.as_ref()
+        );
+        self.metadata_sync_timestamps.insert(&token, &now);
+
+        let sequence = metadata
+            .sequence
+            .near_expect(BridgeError::StaleMetadataProof);
+        require!(
+            self.metadata_sequences
+                .get(&token)
+                .is_none_or(|last_sequence| sequence > last_sequence),
+            BridgeError::StaleMetadataProof.as_ref()
+        );
+        self.metadata_sequences.insert(&token, &sequence);
+
+        ext_token::ext(token)
+            .with_static_gas(SET_METADATA_GAS)
+            .set_metadata(
+                Some(metadata.name.into()),
+                Some(metadata.symbol.into()),
+                metadata.reference.map(Into::into),
+                metadata
+                    .reference_hash
+                    .map(|hash| Base64VecU8(hash.0.to_vec())),
+                None,
+                metadata.icon.map(Into::into),
+            )
     }
 
     #[private]
@@ -1185,6 +1366,8 @@ impl Contract {
         token_id: AccountId,
     ) -> PromiseOrValue<()> {
         if env::promise_result_checked(0, usize::MAX).is_ok() {
+            self.register_token_storage_batch(&token_id);
+
             ext_token::ext(token_id)
                 .with_static_gas(STORAGE_DEPOSIT_GAS)
                 .with_attached_deposit(NEP141_DEPOSIT)
@@ -1206,8 +1389,8 @@ impl Contract {
     pub fn deploy_native_token(
         &mut self,
         chain_kind: ChainKind,
-        name: String,
-        symbol: String,
+        name: TokenName,
+        symbol: TokenSymbol,
         decimals: u8,
     ) -> Promise {
         let native_token_address = get_native_token_address(chain_kind)
@@ -1219,7 +1402,11 @@ impl Contract {
                 name,
                 symbol,
                 decimals,
+                icon: None,
+                reference: None,
+                reference_hash: None,
             },
+            None,
             env::attached_deposit(),
         )
     }
@@ -1298,7 +1485,7 @@ impl Contract {
                 decimals: deploy_token.decimals,
                 origin_decimals: deploy_token.origin_decimals,
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
 
         attached_deposit.saturating_sub(required_deposit)
@@ -1354,7 +1541,185 @@ impl Contract {
             NearToken::from_yoctonear(0),
         );
 
-        env::log_str(&OmniBridgeEvent::InitTransferEvent { transfer_message }.to_log_string());
+        env::log_str(
+            &OmniBridgeEvent::InitTransferEvent { transfer_message }
+                .to_log_string(self.event_format),
+        );
+    }
+
+    /// Called by a deployed or migrated legacy token after it burned `amount` from `sender_id`.
+    /// Unlike `finish_withdraw_v2`, the recipient may be on any supported chain and the transfer
+    /// storage and native fee are charged to the `sender_id` storage balance. Without a native
+    /// fee, storage the sender can't cover is booked to the bridge like in `finish_withdraw_v2`.
+    /// The tokens are already burned, so instead of panicking on an invalid transfer or unpaid
+    /// storage, `amount` of the bridge token is minted back to `sender_id` and a
+    /// `FailedWithdrawEvent` is logged with the reason.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn finish_withdraw_v3(
+        &mut self,
+        #[serializer(borsh)] sender_id: AccountId,
+        #[serializer(borsh)] amount: u128,
+        #[serializer(borsh)] recipient: OmniAddress,
+        #[serializer(borsh)] fee: Option<Fee>,
+    ) {
+        let predecessor_id = env::predecessor_account_id();
+        let is_migrated_token = !self.is_deployed_token(&predecessor_id);
+        let token_id = if is_migrated_token {
+            self.migrated_tokens
+                .get(&predecessor_id)
+                .near_expect(BridgeError::TokenNotDeployed)
+        } else {
+            predecessor_id
+        };
+
+        let fee = fee.unwrap_or_default();
+        if let Err(err) = self.validate_withdraw(&sender_id, amount, &recipient, &fee) {
+            self.refund_withdraw(
+                sender_id,
+                token_id,
+                amount,
+                recipient,
+                is_migrated_token,
+                &err,
+            );
+            return;
+        }
+
+        let transfer_message = self.new_init_transfer_message(
+            sender_id.clone(),
+            token_id.clone(),
+            U128(amount),
+            &InitTransferMsg {
+                recipient: recipient.clone(),
+                fee: fee.fee,
+                native_token_fee: fee.native_fee,
+                msg: None,
+                external_id: None,
+            },
+        );
+
+        let storage_balance =
+            self.add_transfer_message(transfer_message.clone(), sender_id.clone());
+        let native_fee = NearToken::from_yoctonear(transfer_message.fee.native_fee.0);
+        let is_charged = self
+            .try_update_storage_balance(
+                sender_id.clone(),
+                storage_balance.saturating_add(native_fee),
+                NearToken::from_yoctonear(0),
+            )
+            .is_ok()
+            || (native_fee.is_zero()
+                && self
+                    .try_update_storage_balance(
+                        env::current_account_id(),
+                        storage_balance,
+                        NearToken::from_yoctonear(0),
+                    )
+                    .is_ok());
+        if !is_charged {
+            self.remove_transfer_message_without_refund(transfer_message.get_transfer_id());
+            self.refund_withdraw(
+                sender_id,
+                token_id,
+                amount,
+                recipient,
+                is_migrated_token,
+                &BridgeError::InsufficientStorageDeposit,
+            );
+            return;
+        }
+
+        self.lock_tokens_if_needed(transfer_message.get_destination_chain(), &token_id, amount);
+
+        env::log_str(
+            &OmniBridgeEvent::InitTransferEvent { transfer_message }
+                .to_log_string(self.event_format),
+        );
+    }
+
+    /// Mints a refunded withdrawal of a migrated token back to `sender_id`. An unregistered
+    /// sender is registered on the token with `NEP141_DEPOSIT` taken from their storage
+    /// balance. The bridge only pays it if the sender's storage balance can't.
+    #[private]
+    pub fn refund_withdraw_callback(
+        &mut self,
+        sender_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+    ) {
+        let mint = ext_token::ext(token_id.clone())
+            .with_static_gas(MINT_TOKEN_GAS)
+            .mint(sender_id.clone(), amount, None);
+        if Self::check_storage_balance_result(0) {
+            mint.detach();
+            return;
+        }
+
+        if self
+            .try_update_storage_balance(sender_id.clone(), NEP141_DEPOSIT, NO_DEPOSIT)
+            .is_err()
+        {
+            // The refunded tokens must not be lost, so the bridge pays the registration,
+            // from its own storage balance when it has one.
+            let _ = self.try_update_storage_balance(
+                env::current_account_id(),
+                NEP141_DEPOSIT,
+                NO_DEPOSIT,
+            );
+        }
+        ext_token::ext(token_id)
+            .with_static_gas(STORAGE_DEPOSIT_GAS)
+            .with_attached_deposit(NEP141_DEPOSIT)
+            .storage_deposit(&sender_id, Some(true))
+            .then(mint)
+            .detach();
+    }
+
+    /// Called by a deployed token from `bridge_out` after it burned `amount` from `sender_id`.
+    /// The attached deposit pays the native fee and the transfer storage and any surplus is
+    /// transferred back to `sender_id`. If the deposit doesn't cover them, the shortfall is
+    /// taken from the storage balance of `sender_id`.
+    #[payable]
+    #[pause(except(roles(Role::DAO)))]
+    pub fn init_transfer_from_token(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        init_transfer_msg: &InitTransferMsg,
+    ) {
+        let token_id = env::predecessor_account_id();
+        require!(
+            self.is_deployed_token(&token_id),
+            BridgeError::TokenNotDeployed.as_ref()
+        );
+        require!(
+            init_transfer_msg.native_token_fee.0 == 0
+                || !self.acl_has_role(Role::NativeFeeRestricted.into(), sender_id.clone()),
+            BridgeError::InvalidFee.as_ref()
+        );
+
+        let transfer_message = self.new_init_transfer_message(
+            sender_id.clone(),
+            token_id.clone(),
+            amount,
+            init_transfer_msg,
+        );
+
+        let required_balance = self
+            .add_transfer_message(transfer_message.clone(), sender_id.clone())
+            .saturating_add(NearToken::from_yoctonear(transfer_message.fee.native_fee.0));
+        self.update_storage_balance(sender_id, required_balance, env::attached_deposit());
+
+        self.lock_tokens_if_needed(
+            transfer_message.get_destination_chain(),
+            &token_id,
+            amount.0,
+        );
+
+        env::log_str(
+            &OmniBridgeEvent::InitTransferEvent { transfer_message }
+                .to_log_string(self.event_format),
+        );
     }
 
     pub fn is_deployed_token(&self, token: &AccountId) -> bool {
@@ -1369,6 +1734,134 @@ impl Contract {
         self.token_id_to_address.get(&(chain_kind, token))
     }
 
+    /// Returns the NEAR token for `token_address`, or the account the token will be deployed
+    /// to if it isn't bridged yet. Returns `None` if no deployer is set for the chain.
+    pub fn predict_token_account(&self, token_address: OmniAddress) -> Option<AccountId> {
+        if let OmniAddress::Near(token_id) = token_address {
+            return Some(token_id);
+        }
+        if let Some(token_id) = self.token_address_to_id.get(&token_address) {
+            return Some(token_id);
+        }
+
+        let deployer = self
+            .token_deployer_accounts
+            .get(&token_address.get_chain())?;
+        token_address.get_token_account_id(&deployer).ok()
+    }
+
+    /// Pre-registers storage on a token that isn't deployed yet, so the first transfers to
+    /// `account_ids` can be finalised right after deployment. Each new account costs
+    /// `NEP141_DEPOSIT`, paid from the attached deposit or the caller's storage balance,
+    /// and is refunded by `unregister_token_storage` until the token is deployed. Each payer
+    /// can hold up to `MAX_TOKEN_STORAGE_REGISTRATIONS` registrations per token.
+    #[payable]
+    #[pause(except(roles(Role::DAO)))]
+    pub fn pre_register_token_storage(
+        &mut self,
+        token_address: OmniAddress,
+        account_ids: Vec<AccountId>,
+    ) {
+        let token_id = self.undeployed_token_id(token_address);
+        let payer = env::predecessor_account_id();
+
+        let storage_usage = env::storage_usage();
+        let mut registrations = self
+            .token_storage_registrations
+            .get(&token_id)
+            .unwrap_or_default();
+        let mut new_registrations: u128 = 0;
+        for account_id in account_ids {
+            if !registrations
+                .iter()
+                .any(|registration| registration.account_id == account_id)
+            {
+                registrations.push(TokenStorageRegistration {
+                    account_id,
+                    payer: payer.clone(),
+                });
+                new_registrations += 1;
+            }
+        }
+        require!(
+            registrations
+                .iter()
+                .filter(|registration| registration.payer == payer)
+                .count()
+                <= MAX_TOKEN_STORAGE_REGISTRATIONS,
+            BridgeError::TooManyStorageRegistrations.as_ref()
+        );
+        self.token_storage_registrations
+            .insert(&token_id, &registrations);
+
+        let required_balance = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
+            .saturating_add(NEP141_DEPOSIT.saturating_mul(new_registrations));
+        self.update_storage_balance(payer, required_balance, env::attached_deposit());
+    }
+
+    /// Removes storage registrations on a token that isn't deployed yet and refunds their
+    /// payers. Payers can only remove their own registrations, the DAO can remove any.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn unregister_token_storage(
+        &mut self,
+        token_address: OmniAddress,
+        account_ids: Vec<AccountId>,
+    ) {
+        let token_id = self.undeployed_token_id(token_address);
+        let predecessor_id = env::predecessor_account_id();
+        let is_dao = self.acl_has_role(Role::DAO.into(), predecessor_id.clone());
+
+        let registrations = self
+            .token_storage_registrations
+            .get(&token_id)
+            .unwrap_or_default();
+        let (removed, kept): (Vec<_>, Vec<_>) =
+            registrations.into_iter().partition(|registration| {
+                account_ids.contains(&registration.account_id)
+                    && (is_dao || registration.payer == predecessor_id)
+            });
+        require!(
+            !removed.is_empty(),
+            BridgeError::StorageRegistrationNotFound.as_ref()
+        );
+
+        if kept.is_empty() {
+            self.token_storage_registrations.remove(&token_id);
+        } else {
+            self.token_storage_registrations.insert(&token_id, &kept);
+        }
+
+        for registration in removed {
+            let storage_refund = Self::token_storage_registration_cost(&registration);
+            Self::refund(
+                registration.payer,
+                NEP141_DEPOSIT.saturating_add(storage_refund),
+            );
+        }
+    }
+
+    /// Registers storage for the next `MAX_TOKEN_STORAGE_REGISTRATIONS` pre-registrations of a
+    /// deployed token. The deployment registers the first batch, this method the rest.
+    #[pause(except(roles(Role::DAO)))]
+    pub fn register_token_storage(&mut self, token_id: AccountId) {
+        self.assert_deployed_token(&token_id);
+        require!(
+            self.token_storage_registrations.contains_key(&token_id),
+            BridgeError::StorageRegistrationNotFound.as_ref()
+        );
+        self.register_token_storage_batch(&token_id);
+    }
+
+    pub fn get_token_storage_registrations(
+        &self,
+        token_id: &AccountId,
+    ) -> Vec<TokenStorageRegistration> {
+        self.token_storage_registrations
+            .get(token_id)
+            .unwrap_or_default()
+    }
+
     pub fn get_token_id(&self, address: &OmniAddress) -> AccountId {
         if let OmniAddress::Near(token_account_id) = address {
             token_account_id.clone()
@@ -1445,6 +1938,7 @@ impl Contract {
             s if s.starts_with("fogo") => ChainKind::Fogo,
             s if s.starts_with("strk") || s.starts_with("starknet") => ChainKind::Strk,
             s if s.starts_with("aptos") => ChainKind::Aptos,
+            s if s.starts_with("sui") => ChainKind::Sui,
             _ => env::panic_str(&BridgeError::CannotDetermineOriginChain.as_ref()),
         };
 
@@ -1496,6 +1990,37 @@ impl Contract {
             .map(storage::FastTransferStatusStorage::into_main)
     }
 
+    /// Returns `None` once an outgoing transfer leaves the NEAR storage (signed without a fee,
+    /// settled with the fee claim or refunded), as its hash is dropped together with the message.
+    pub fn resolve_global_transfer_hash(
+        &self,
+        global_transfer_hash: H256,
+    ) -> Option<ResolvedTransfer> {
+        let transfer_id = self.global_transfer_hashes.get(&global_transfer_hash)?;
+        let transfer_message = match transfer_id.kind {
+            TransferIdKind::Nonce(origin_nonce) => self
+                .pending_transfers
+                .get(&TransferId {
+                    origin_chain: transfer_id.origin_chain,
+                    origin_nonce,
+                })
+                .map(|storage| storage.into_main().message),
+            TransferIdKind::Utxo(_) => None,
+        };
+
+        let status = if transfer_message.is_some() {
+            GlobalTransferStatus::Pending
+        } else {
+            GlobalTransferStatus::Finalised
+        };
+
+        Some(ResolvedTransfer {
+            transfer_id,
+            status,
+            transfer_message,
+        })
+    }
+
     pub fn is_fast_transfer_finalised(&self, fast_transfer_id: &FastTransferId) -> bool {
         self.fast_transfers
             .get(fast_transfer_id)
@@ -1565,12 +2090,15 @@ impl Contract {
                     token_id: token_info.token_id,
                     token_address: token_info.token_address,
                     metadata: BasicMetadata {
-                        name: String::new(),
-                        symbol: String::new(),
+                        name: TokenName::default(),
+                        symbol: TokenSymbol::default(),
                         decimals: token_info.decimals,
+                        icon: None,
+                        reference: None,
+                        reference_hash: None,
                     },
                 }
-                .to_log_string(),
+                .to_log_string(self.event_format),
             );
         }
     }
@@ -1579,8 +2107,8 @@ impl Contract {
     pub fn set_token_metadata(
         &mut self,
         address: OmniAddress,
-        name: Option<String>,
-        symbol: Option<String>,
+        name: Option<TokenName>,
+        symbol: Option<TokenSymbol>,
         icon: Option<String>,
         reference: Option<String>,
         reference_hash: Option<Base64VecU8>,
@@ -1597,8 +2125,8 @@ impl Contract {
         ext_token::ext(token)
             .with_static_gas(SET_METADATA_GAS)
             .set_metadata(
-                name,
-                symbol,
+                name.map(Into::into),
+                symbol.map(Into::into),
                 reference,
                 reference_hash,
                 Some(decimals),
@@ -1606,6 +2134,48 @@ impl Contract {
             )
     }
 
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_token_freeze_enabled(&mut self, token: AccountId, enabled: bool) -> Promise {
+        self.assert_deployed_token(&token);
+
+        ext_token::ext(token)
+            .with_static_gas(FREEZE_TOKEN_ACCOUNT_GAS)
+            .set_freeze_enabled(enabled)
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::FreezeManager))]
+    pub fn freeze_token_account(&mut self, token: AccountId, account_id: AccountId) -> Promise {
+        self.assert_deployed_token(&token);
+
+        ext_token::ext(token)
+            .with_static_gas(FREEZE_TOKEN_ACCOUNT_GAS)
+            .freeze_account(account_id)
+    }
+
+    #[access_control_any(roles(Role::DAO, Role::FreezeManager))]
+    pub fn unfreeze_token_account(&mut self, token: AccountId, account_id: AccountId) -> Promise {
+        self.assert_deployed_token(&token);
+
+        ext_token::ext(token)
+            .with_static_gas(FREEZE_TOKEN_ACCOUNT_GAS)
+            .unfreeze_account(account_id)
+    }
+
+    /// Burns the balance of an account frozen on `token`. The promise resolves to the
+    /// burned amount.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn wipe_frozen_token_account(
+        &mut self,
+        token: AccountId,
+        account_id: AccountId,
+    ) -> Promise {
+        self.assert_deployed_token(&token);
+
+        ext_token::ext(token)
+            .with_static_gas(FREEZE_TOKEN_ACCOUNT_GAS)
+            .wipe_frozen(account_id)
+    }
+
     #[access_control_any(roles(Role::DAO))]
     #[payable]
     pub fn migrate_deployed_token(
@@ -1664,7 +2234,7 @@ impl Contract {
                 old_token_id: old_token,
                 new_token_id: new_token,
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
     }
 
@@ -1703,23 +2273,31 @@ impl Contract {
         #[serializer(borsh)] lock_actions: Vec<LockAction>,
     ) {
         let token = self.get_token_id(&transfer_message.token);
-
-        if Self::is_refund_required(is_ft_transfer_call) {
-            self.burn_tokens_if_needed(
-                token.clone(),
-                U128(
-                    transfer_message
-                        .amount_without_fee()
-                        .near_expect(BridgeError::InvalidFee),
-                ),
-            );
+        // A failed mint (e.g. over the token's mint limits or to a frozen account) leaves nothing
+        // to burn. With a `msg` the mint only resolves after `ft_resolve_transfer`, which doesn't
+        // panic, so an error still means that the mint itself was reverted.
+        let is_mint_failed = self.is_deployed_token(&token)
+            && env::promise_result_checked(0, MAX_FT_TRANSFER_CALL_RESULT).is_err();
+
+        if is_mint_failed || Self::is_refund_required(is_ft_transfer_call) {
+            if !is_mint_failed {
+                self.burn_tokens_if_needed(
+                    token.clone(),
+                    U128(
+                        transfer_message
+                            .amount_without_fee()
+                            .near_expect(BridgeError::InvalidFee),
+                    ),
+                );
+            }
 
             self.revert_lock_actions(&lock_actions);
 
             self.remove_fin_transfer(&transfer_message.get_transfer_id(), storage_owner);
 
             env::log_str(
-                &OmniBridgeEvent::FailedFinTransferEvent { transfer_message }.to_log_string(),
+                &OmniBridgeEvent::FailedFinTransferEvent { transfer_message }
+                    .to_log_string(self.event_format),
             );
         } else {
             // Send fee to the fee recipient
@@ -1747,7 +2325,18 @@ impl Contract {
                     .detach();
             }
 
-            env::log_str(&OmniBridgeEvent::FinTransferEvent { transfer_message }.to_log_string());
+            env::log_str(
+                &OmniBridgeEvent::FinTransferEvent {
+                    transfer_message,
+                    details: Some(TransferEventDetails {
+                        relayer: storage_owner.clone(),
+                        storage_owner: Some(storage_owner.clone()),
+                        destination_token: None,
+                        lock_actions,
+                    }),
+                }
+                .to_log_string(self.event_format),
+            );
         }
     }
 
@@ -1761,6 +2350,15 @@ impl Contract {
         self.provers.remove(&chain);
     }
 
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_event_format(&mut self, event_format: EventFormat) {
+        self.event_format = event_format;
+    }
+
+    pub fn get_event_format(&self) -> EventFormat {
+        self.event_format
+    }
+
     #[must_use]
     pub fn get_provers(&self) -> Vec<(ChainKind, AccountId)> {
         self.provers.iter().collect()
@@ -1808,6 +2406,13 @@ impl Contract {
         }
     }
 
+    fn assert_deployed_token(&self, token: &AccountId) {
+        require!(
+            self.is_deployed_token(token),
+            BridgeError::TokenNotDeployed.as_ref()
+        );
+    }
+
     fn burn_tokens_if_needed(&self, token: AccountId, amount: U128) {
         if self.is_deployed_token(&token) {
             ext_token::ext(token)
@@ -1831,6 +2436,112 @@ impl Contract {
         payload_nonce
     }
 
+    /// Checks everything `new_init_transfer_message` and the native fee role would panic on.
+    fn validate_withdraw(
+        &self,
+        sender_id: &AccountId,
+        amount: u128,
+        recipient: &OmniAddress,
+        fee: &Fee,
+    ) -> Result<(), BridgeError> {
+        if recipient.get_chain() == ChainKind::Near {
+            return Err(BridgeError::InvalidRecipientChain);
+        }
+        self.try_validate_utxo_recipient(recipient)?;
+        if fee.fee.0 >= amount
+            || (fee.native_fee.0 != 0
+                && self.acl_has_role(Role::NativeFeeRestricted.into(), sender_id.clone()))
+        {
+            return Err(BridgeError::InvalidFee);
+        }
+
+        Ok(())
+    }
+
+    /// Mints burned tokens back to the sender of a withdrawal that can't be initiated. A
+    /// migrated token's sender may not be registered on the new token yet, which is checked
+    /// by `refund_withdraw_callback`.
+    fn refund_withdraw(
+        &self,
+        sender_id: AccountId,
+        token_id: AccountId,
+        amount: u128,
+        recipient: OmniAddress,
+        is_migrated_token: bool,
+        reason: &BridgeError,
+    ) {
+        env::log_str(
+            &OmniBridgeEvent::FailedWithdrawEvent {
+                sender_id: sender_id.clone(),
+                token_id: token_id.clone(),
+                amount: U128(amount),
+                recipient,
+                reason: reason.as_ref().to_owned(),
+            }
+            .to_log_string(self.event_format),
+        );
+
+        if is_migrated_token {
+            ext_token::ext(token_id.clone())
+                .with_static_gas(STORAGE_BALANCE_OF_GAS)
+                .storage_balance_of(&sender_id)
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(REFUND_WITHDRAW_CALLBACK_GAS)
+                        .refund_withdraw_callback(sender_id, token_id, U128(amount)),
+                )
+                .detach();
+        } else {
+            ext_token::ext(token_id)
+                .with_static_gas(MINT_TOKEN_GAS)
+                .mint(sender_id, U128(amount), None)
+                .detach();
+        }
+    }
+
+    fn new_init_transfer_message(
+        &mut self,
+        sender_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+        init_transfer_msg: &InitTransferMsg,
+    ) -> TransferMessage {
+        require!(
+            init_transfer_msg.recipient.get_chain() != ChainKind::Near,
+            BridgeError::InvalidRecipientChain.as_ref()
+        );
+        self.validate_utxo_recipient(&init_transfer_msg.recipient);
+
+        self.current_origin_nonce += 1;
+        let destination_nonce =
+            self.get_next_destination_nonce(init_transfer_msg.get_destination_chain());
+
+        let transfer_message = TransferMessage {
+            origin_nonce: self.current_origin_nonce,
+            token: OmniAddress::Near(token_id),
+            amount,
+            recipient: init_transfer_msg.recipient.clone(),
+            fee: Fee {
+                fee: init_transfer_msg.fee,
+                native_fee: init_transfer_msg.native_token_fee,
+            },
+            sender: OmniAddress::Near(sender_id),
+            msg: init_transfer_msg
+                .msg
+                .clone()
+                .map(String::from)
+                .unwrap_or_default(),
+            destination_nonce,
+            origin_transfer_id: None,
+        };
+        require!(
+            transfer_message.fee.fee < transfer_message.amount,
+            BridgeError::InvalidFee.as_ref()
+        );
+
+        transfer_message
+    }
+
     fn init_transfer_internal(
         &mut self,
         transfer_message: TransferMessage,
@@ -1865,7 +2576,10 @@ impl Contract {
             return transfer_message.amount;
         }
 
-        env::log_str(&OmniBridgeEvent::InitTransferEvent { transfer_message }.to_log_string());
+        env::log_str(
+            &OmniBridgeEvent::InitTransferEvent { transfer_message }
+                .to_log_string(self.event_format),
+        );
         U128(0)
     }
 
@@ -1999,16 +2713,18 @@ impl Contract {
             );
         }
 
-        self.unlock_tokens_if_needed(
-            transfer_message.get_origin_chain(),
-            &token,
-            transfer_message.amount.0,
-        );
-        self.lock_tokens_if_needed(
-            transfer_message.get_destination_chain(),
-            &token,
-            transfer_message.fee.fee.into(),
-        );
+        let mut lock_actions = vec![
+            self.unlock_tokens_if_needed(
+                transfer_message.get_origin_chain(),
+                &token,
+                transfer_message.amount.0,
+            ),
+            self.lock_tokens_if_needed(
+                transfer_message.get_destination_chain(),
+                &token,
+                transfer_message.fee.fee.into(),
+            ),
+        ];
 
         let fast_transfer = FastTransfer::from_transfer(transfer_message.clone(), token.clone());
         let recipient = if let Some(status) = self.get_fast_transfer_status(&fast_transfer.id()) {
@@ -2018,16 +2734,19 @@ impl Contract {
             );
             Some(status.relayer)
         } else {
-            self.lock_tokens_if_needed(
-                transfer_message.get_destination_chain(),
-                &token,
-                transfer_message
-                    .amount_without_fee()
-                    .near_expect(BridgeError::InvalidFee),
+            lock_actions.push(
+                self.lock_tokens_if_needed(
+                    transfer_message.get_destination_chain(),
+                    &token,
+                    transfer_message
+                        .amount_without_fee()
+                        .near_expect(BridgeError::InvalidFee),
+                ),
             );
 
             None
         };
+        let destination_token = self.get_destination_token(&transfer_message, &token);
 
         // If fast transfer happened, send tokens to the relayer that executed fast transfer
         if let Some(relayer) = recipient {
@@ -2050,12 +2769,49 @@ impl Contract {
         }
 
         self.update_storage_balance(
-            predecessor_account_id,
+            predecessor_account_id.clone(),
             required_balance,
             env::attached_deposit(),
         );
 
-        env::log_str(&OmniBridgeEvent::FinTransferEvent { transfer_message }.to_log_string());
+        env::log_str(
+            &OmniBridgeEvent::FinTransferEvent {
+                transfer_message,
+                details: Some(TransferEventDetails {
+                    relayer: predecessor_account_id.clone(),
+                    storage_owner: Some(predecessor_account_id),
+                    destination_token,
+                    lock_actions,
+                }),
+            }
+            .to_log_string(self.event_format),
+        );
+    }
+
+    /// Token paid out on the destination chain, or `None` for transfers to NEAR and
+    /// tokens that are not bound on the destination chain.
+    fn get_destination_token(
+        &self,
+        transfer_message: &TransferMessage,
+        token: &AccountId,
+    ) -> Option<DestinationToken> {
+        let destination_chain = transfer_message.get_destination_chain();
+        if destination_chain == ChainKind::Near {
+            return None;
+        }
+
+        let token_address = self.get_token_address(destination_chain, token.clone())?;
+        let decimals = self.token_decimals.get(&token_address)?;
+        Some(DestinationToken {
+            token_address,
+            decimals: decimals.decimals,
+            origin_decimals: decimals.origin_decimals,
+            normalized_amount: U128(Self::normalize_amount(
+                transfer_message.amount_without_fee()?,
+                decimals,
+            )),
+            normalized_fee: U128(Self::normalize_amount(transfer_message.fee.fee.0, decimals)),
+        })
     }
 
     fn send_tokens(
@@ -2188,21 +2944,26 @@ impl Contract {
         message_owner: AccountId,
     ) -> NearToken {
         let storage_usage = env::storage_usage();
+        let transfer_id = transfer_message.get_transfer_id();
         require!(
             self.insert_raw_transfer(transfer_message, message_owner,)
                 .is_none(),
             BridgeError::KeyExists.as_ref()
         );
+        self.global_transfer_hashes
+            .insert(&transfer_id.global_hash(), &transfer_id.into());
         env::storage_byte_cost().saturating_mul((env::storage_usage() - storage_usage).into())
     }
 
-    fn remove_transfer_message(&mut self, transfer_id: TransferId) -> TransferMessage {
+    fn remove_transfer_message(&mut self, transfer_id: TransferId) -> TransferMessageStorageValue {
         let storage_usage = env::storage_usage();
         let transfer = self
             .pending_transfers
             .remove(&transfer_id)
             .map(storage::TransferMessageStorage::into_main)
             .near_expect(BridgeError::TransferNotExist);
+        self.global_transfer_hashes
+            .remove(&transfer_id.global_hash());
 
         let refund =
             env::storage_byte_cost().saturating_mul((storage_usage - env::storage_usage()).into());
@@ -2212,7 +2973,7 @@ impl Contract {
             self.accounts_balances.insert(&transfer.owner, &storage);
         }
 
-        transfer.message
+        transfer
     }
 
     fn remove_transfer_message_without_refund(
@@ -2224,6 +2985,8 @@ impl Contract {
             .remove(&transfer_id)
             .map(storage::TransferMessageStorage::into_main)
             .near_expect(BridgeError::TransferNotExist);
+        self.global_transfer_hashes
+            .remove(&transfer_id.global_hash());
 
         transfer.message
     }
@@ -2234,6 +2997,8 @@ impl Contract {
             self.finalised_transfers.insert(transfer_id),
             BridgeError::TransferAlreadyFinalised.as_ref()
         );
+        self.global_transfer_hashes
+            .insert(&transfer_id.global_hash(), &(*transfer_id).into());
         env::storage_byte_cost()
             .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
     }
@@ -2244,6 +3009,8 @@ impl Contract {
             self.finalised_utxo_transfers.insert(transfer_id),
             BridgeError::UtxoTransferAlreadyFinalised.as_ref()
         );
+        self.global_transfer_hashes
+            .insert(&transfer_id.global_hash(), transfer_id);
         env::storage_byte_cost()
             .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
     }
@@ -2327,6 +3094,8 @@ impl Contract {
     fn remove_fin_transfer(&mut self, transfer_id: &TransferId, storage_owner: &AccountId) {
         let storage_usage = env::storage_usage();
         self.finalised_transfers.remove(transfer_id);
+        self.global_transfer_hashes
+            .remove(&transfer_id.global_hash());
 
         let refund =
             env::storage_byte_cost().saturating_mul((storage_usage - env::storage_usage()).into());
@@ -2345,6 +3114,8 @@ impl Contract {
         let storage_usage = env::storage_usage();
 
         self.finalised_utxo_transfers.remove(transfer_id);
+        self.global_transfer_hashes
+            .remove(&transfer_id.global_hash());
 
         let refund =
             env::storage_byte_cost().saturating_mul((storage_usage - env::storage_usage()).into());
@@ -2399,20 +3170,35 @@ impl Contract {
         }
     }
 
+    /// Token account of `token_address` for storage registrations, which are only accepted
+    /// before the token is deployed or bound.
+    fn undeployed_token_id(&self, token_address: OmniAddress) -> AccountId {
+        require!(
+            token_address.get_chain() != ChainKind::Near,
+            BridgeError::NearTokenNotDeployable.as_ref()
+        );
+        require!(
+            self.token_address_to_id.get(&token_address).is_none(),
+            BridgeError::TokenExists.as_ref()
+        );
+        self.predict_token_account(token_address)
+            .near_expect(BridgeError::DeployerNotSet)
+    }
+
     fn deploy_token_internal(
         &mut self,
         chain_kind: ChainKind,
         token_address: &OmniAddress,
         metadata: BasicMetadata,
+        metadata_sequence: Option<u64>,
         attached_deposit: NearToken,
     ) -> Promise {
         let deployer = self
             .token_deployer_accounts
             .get(&chain_kind)
             .unwrap_or_else(|| env::panic_str(BridgeError::DeployerNotSet.to_string().as_str()));
-        let prefix = token_address.get_token_prefix();
-        let token_id: AccountId = format!("{prefix}.{deployer}")
-            .parse()
+        let token_id = token_address
+            .get_token_account_id(&deployer)
             .unwrap_or_else(|_| env::panic_str(BridgeError::ParseAccountId.to_string().as_str()));
 
         let storage_usage = env::storage_usage();
@@ -2429,6 +3215,9 @@ impl Contract {
         );
         self.deployed_tokens_v2
             .insert(&token_id, &token_address.get_chain());
+        if let Some(sequence) = metadata_sequence {
+            self.metadata_sequences.insert(&token_id, &sequence);
+        }
 
         let required_deposit = env::storage_byte_cost()
             .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into())
@@ -2445,15 +3234,25 @@ impl Contract {
                 token_address: token_address.clone(),
                 metadata: metadata.clone(),
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
 
+        let registrations =
+            self.token_storage_registrations
+                .get(&token_id)
+                .map_or(0, |registrations| {
+                    u64::try_from(registrations.len().min(MAX_TOKEN_STORAGE_REGISTRATIONS))
+                        .near_expect(BridgeError::Cast)
+                });
         ext_deployer::ext(deployer)
             .with_static_gas(DEPLOY_TOKEN_GAS)
             .with_attached_deposit(attached_deposit.saturating_sub(required_deposit))
             .deploy_token(token_id.clone(), metadata)
             .then(
                 Self::ext(env::current_account_id())
+                    .with_static_gas(DEPLOY_TOKEN_BY_DEPLOYER_CALLBACK_GAS.saturating_add(
+                        STORAGE_DEPOSIT_GAS.saturating_mul(registrations.saturating_add(1)),
+                    ))
                     .deploy_token_by_deployer_callback(token_address, token_id),
             )
     }
@@ -2559,7 +3358,7 @@ impl Contract {
                 utxo_transfer_message: utxo_fin_transfer_msg,
                 new_transfer_id: None,
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
 
         PromiseOrPromiseIndexOrValue::Value(U128(0))
@@ -2646,7 +3445,7 @@ impl Contract {
                 utxo_transfer_message: utxo_fin_transfer_msg,
                 new_transfer_id: Some(transfer_message.get_transfer_id()),
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
 
         PromiseOrPromiseIndexOrValue::Value(U128(0))
@@ -2656,6 +3455,7 @@ impl Contract {
         &mut self,
         transfer_message: &TransferMessage,
         fee_recipient: AccountId,
+        storage_owner: AccountId,
         token_fee: u128,
     ) -> PromiseOrValue<()> {
         if transfer_message.fee.native_fee.0 != 0 {
@@ -2679,15 +3479,25 @@ impl Contract {
         }
 
         let token = self.get_token_id(&transfer_message.token);
+        let lock_action = self.unlock_tokens_if_needed(
+            transfer_message.get_destination_chain(),
+            &token,
+            token_fee,
+        );
+
         env::log_str(
             &OmniBridgeEvent::ClaimFeeEvent {
                 transfer_message: transfer_message.clone(),
+                details: Some(TransferEventDetails {
+                    relayer: fee_recipient.clone(),
+                    storage_owner: Some(storage_owner),
+                    destination_token: self.get_destination_token(transfer_message, &token),
+                    lock_actions: vec![lock_action],
+                }),
             }
-            .to_log_string(),
+            .to_log_string(self.event_format),
         );
 
-        self.unlock_tokens_if_needed(transfer_message.get_destination_chain(), &token, token_fee);
-
         if token_fee > 0 {
             if self.is_deployed_token(&token) {
                 ext_token::ext(token)
@@ -2772,6 +3582,46 @@ impl Contract {
             .verify_proof(prover_args)
     }
 
+    fn token_storage_registration_cost(registration: &TokenStorageRegistration) -> NearToken {
+        let registration_len = borsh::to_vec(registration)
+            .near_expect(BridgeError::Borsh)
+            .len();
+        env::storage_byte_cost()
+            .saturating_mul(registration_len.try_into().near_expect(BridgeError::Cast))
+    }
+
+    fn register_token_storage_batch(&mut self, token_id: &AccountId) {
+        let mut registrations = self
+            .token_storage_registrations
+            .remove(token_id)
+            .unwrap_or_default();
+        let remaining =
+            registrations.split_off(registrations.len().min(MAX_TOKEN_STORAGE_REGISTRATIONS));
+        if !remaining.is_empty() {
+            self.token_storage_registrations
+                .insert(token_id, &remaining);
+        }
+
+        let mut storage_refunds: BTreeMap<AccountId, NearToken> = BTreeMap::new();
+        for registration in registrations {
+            ext_token::ext(token_id.clone())
+                .with_static_gas(STORAGE_DEPOSIT_GAS)
+                .with_attached_deposit(NEP141_DEPOSIT)
+                .storage_deposit(&registration.account_id, Some(true))
+                .detach();
+
+            let storage_refund = Self::token_storage_registration_cost(&registration);
+            let payer_refund = storage_refunds
+                .entry(registration.payer)
+                .or_insert(NearToken::from_yoctonear(0));
+            *payer_refund = payer_refund.saturating_add(storage_refund);
+        }
+
+        for (payer, storage_refund) in storage_refunds {
+            Self::refund(payer, storage_refund);
+        }
+    }
+
     fn refund(account_id: AccountId, amount: NearToken) {
         if !amount.is_zero() {
             Promise::new(account_id).transfer(amount).detach();
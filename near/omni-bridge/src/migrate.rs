@@ -11,11 +11,17 @@ use near_sdk::{
     env, near, AccountId, CryptoHash, PanicOnDefault,
 };
 use omni_types::{
-    btc::UTXOChainConfig, ChainKind, FastTransferId, Nonce, OmniAddress, TransferId,
-    UnifiedTransferId,
+    btc::UTXOChainConfig, near_events::EventFormat, utxo::UtxoNetwork, ChainKind, FastTransferId,
+    Nonce, OmniAddress, TransferId, UnifiedTransferId,
 };
 use omni_utils::trusted_relayer::{RelayerConfig, RelayerState};
 
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldUTXOChainConfig {
+    pub connector: AccountId,
+    pub token_id: AccountId,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct OldState {
     pub factories: LookupMap<ChainKind, OmniAddress>,
@@ -36,7 +42,7 @@ pub struct OldState {
     pub wnear_account_id: AccountId,
     pub provers: UnorderedMap<ChainKind, AccountId>,
     pub init_transfer_promises: LookupMap<AccountId, CryptoHash>,
-    pub utxo_chain_connectors: HashMap<ChainKind, UTXOChainConfig>,
+    pub utxo_chain_connectors: HashMap<ChainKind, OldUTXOChainConfig>,
     pub migrated_tokens: LookupMap<AccountId, AccountId>,
     pub locked_tokens: LookupMap<(ChainKind, AccountId), u128>,
     pub relayers: LookupMap<AccountId, RelayerState>,
@@ -45,10 +51,26 @@ pub struct OldState {
 
 #[near]
 impl Contract {
+    /// `utxo_network` is the network of the already configured UTXO chain connectors.
     #[private]
     #[init(ignore_state)]
-    pub fn migrate() -> Self {
+    pub fn migrate(utxo_network: UtxoNetwork) -> Self {
         if let Some(old_state) = env::state_read::<OldState>() {
+            let utxo_chain_connectors = old_state
+                .utxo_chain_connectors
+                .into_iter()
+                .map(|(chain_kind, config)| {
+                    (
+                        chain_kind,
+                        UTXOChainConfig {
+                            connector: config.connector,
+                            token_id: config.token_id,
+                            network: utxo_network,
+                        },
+                    )
+                })
+                .collect();
+
             Self {
                 factories: old_state.factories,
                 pending_transfers: old_state.pending_transfers,
@@ -68,9 +90,18 @@ impl Contract {
                 wnear_account_id: old_state.wnear_account_id,
                 provers: old_state.provers,
                 init_transfer_promises: old_state.init_transfer_promises,
-                utxo_chain_connectors: old_state.utxo_chain_connectors,
+                utxo_chain_connectors,
                 migrated_tokens: old_state.migrated_tokens,
                 locked_tokens: old_state.locked_tokens,
+                // Existing indexers parse the bare JSON logs, switch to NEP-297 once they are updated
+                event_format: EventFormat::Legacy,
+                global_transfer_hashes: LookupMap::new(StorageKey::GlobalTransferHashes),
+                metadata_sync_timestamps: LookupMap::new(StorageKey::MetadataSyncTimestamps),
+                token_storage_registrations: LookupMap::new(StorageKey::TokenStorageRegistrations),
+                token_allowlist_enabled: false,
+                token_allowlist: LookupSet::new(StorageKey::TokenAllowlist),
+                token_review_requests: UnorderedMap::new(StorageKey::TokenReviewRequests),
+                metadata_sequences: LookupMap::new(StorageKey::MetadataSequences),
             }
         } else {
             env::panic_str("Old state not found. Migration is not needed.")
@@ -1,8 +1,14 @@
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_contract_standards::storage_management::{StorageBalance, StorageBalanceBounds};
+use near_sdk::json_types::Base64VecU8;
 use near_sdk::{assert_one_yocto, borsh, near, PromiseOrValue};
 use near_sdk::{env, near_bindgen, AccountId, NearToken};
 use omni_types::errors::{BridgeError, StorageError};
-use omni_types::{FastTransferStatus, Nonce, TransferId, TransferIdKind, UnifiedTransferId};
+use omni_types::{
+    BoundedString, FastTransferStatus, Nonce, TransferId, TransferIdKind, UnifiedTransferId,
+    UtxoId, H256, MAX_ACCOUNT_ID_LEN, MAX_INIT_TRANSFER_MSG_LEN, MAX_TOKEN_ICON_LEN,
+    MAX_TOKEN_NAME_LEN, MAX_TOKEN_REFERENCE_LEN, MAX_TOKEN_SYMBOL_LEN, UTXO_TX_HASH_LEN,
+};
 use omni_utils::near_expect::NearExpect;
 
 use crate::{
@@ -304,11 +310,11 @@ impl Contract {
             .saturating_mul((Self::get_basic_storage() + key_len + value_len).into())
     }
 
-    pub fn required_balance_for_init_transfer(&self, msg: Option<String>) -> NearToken {
-        let max_account_id: AccountId = "a"
-            .repeat(64)
-            .parse()
-            .near_expect(BridgeError::ParseAccountId);
+    pub fn required_balance_for_init_transfer(
+        &self,
+        msg: Option<BoundedString<MAX_INIT_TRANSFER_MSG_LEN>>,
+    ) -> NearToken {
+        let max_account_id = Self::max_account_id();
 
         self.required_balance_for_init_transfer_message(TransferMessage {
             origin_nonce: 0,
@@ -317,16 +323,11 @@ impl Contract {
             recipient: OmniAddress::Near(max_account_id.clone()),
             fee: Fee::default(),
             sender: OmniAddress::Near(max_account_id.clone()),
-            msg: msg.unwrap_or_default(),
+            msg: msg.map(String::from).unwrap_or_default(),
             destination_nonce: 0,
             origin_transfer_id: Some(UnifiedTransferId {
                 origin_chain: ChainKind::Eth,
-                kind: TransferIdKind::Utxo({
-                    omni_types::UtxoId {
-                        tx_hash: "a".repeat(64),
-                        vout: 0,
-                    }
-                }),
+                kind: TransferIdKind::Utxo(Self::max_utxo_id()),
             }),
         })
     }
@@ -335,11 +336,6 @@ impl Contract {
         &self,
         transfer_message: TransferMessage,
     ) -> NearToken {
-        let max_account_id: AccountId = "a"
-            .repeat(64)
-            .parse()
-            .near_expect(BridgeError::ParseAccountId);
-
         let key_len: u64 = borsh::to_vec(&transfer_message.get_transfer_id())
             .near_expect(BridgeError::Borsh)
             .len()
@@ -349,7 +345,7 @@ impl Contract {
         let value_len: u64 =
             borsh::to_vec(&TransferMessageStorage::V2(TransferMessageStorageValue {
                 message: transfer_message,
-                owner: max_account_id,
+                owner: Self::max_account_id(),
             }))
             .near_expect(BridgeError::Borsh)
             .len()
@@ -358,23 +354,19 @@ impl Contract {
 
         env::storage_byte_cost()
             .saturating_mul((Self::get_basic_storage() + key_len + value_len).into())
+            .saturating_add(Self::required_balance_for_global_transfer_hash())
     }
 
     pub fn required_balance_for_fin_transfer(&self) -> NearToken {
-        let key_len: u64 = borsh::to_vec(&(
-            ChainKind::Eth,
-            omni_types::UtxoId {
-                tx_hash: "a".repeat(64),
-                vout: 0,
-            },
-        ))
-        .near_expect(BridgeError::Borsh)
-        .len()
-        .try_into()
-        .near_expect(BridgeError::Cast);
+        let key_len: u64 = borsh::to_vec(&(ChainKind::Eth, Self::max_utxo_id()))
+            .near_expect(BridgeError::Borsh)
+            .len()
+            .try_into()
+            .near_expect(BridgeError::Cast);
 
-        let storage_cost =
-            env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + key_len).into());
+        let storage_cost = env::storage_byte_cost()
+            .saturating_mul((Self::get_basic_storage() + key_len).into())
+            .saturating_add(Self::required_balance_for_global_transfer_hash());
         let ft_transfers_cost = NearToken::from_yoctonear(2);
 
         storage_cost.saturating_add(ft_transfers_cost)
@@ -387,10 +379,7 @@ impl Contract {
             .try_into()
             .near_expect(BridgeError::Cast);
 
-        let max_account_id: AccountId = "a"
-            .repeat(64)
-            .parse()
-            .near_expect(BridgeError::ParseAccountId);
+        let max_account_id = Self::max_account_id();
         let value_len: u64 = borsh::to_vec(&FastTransferStatusStorage::V0(FastTransferStatus {
             relayer: max_account_id.clone(),
             finalised: false,
@@ -409,10 +398,7 @@ impl Contract {
     }
 
     pub fn required_balance_for_bind_token(&self) -> NearToken {
-        let max_token_id: AccountId = "a"
-            .repeat(64)
-            .parse()
-            .near_expect(BridgeError::ParseAccountId);
+        let max_token_id = Self::max_account_id();
 
         let key_len: u64 = borsh::to_vec(&(ChainKind::Near, &max_token_id))
             .near_expect(BridgeError::Borsh)
@@ -432,28 +418,115 @@ impl Contract {
 
     pub fn required_balance_for_deploy_token(&self) -> NearToken {
         let key_len = Self::max_key_len_of_account_id();
-        let deployed_tokens_required_balance =
-            env::storage_byte_cost().saturating_mul((Self::get_basic_storage() + key_len).into());
+        // `deployed_tokens_v2` stores the origin chain and `metadata_sequences` the
+        // origin sequence next to the `deployed_tokens` entry
+        let value_len: u64 = borsh::to_vec(&(ChainKind::Near, u64::MAX))
+            .near_expect(BridgeError::Borsh)
+            .len()
+            .try_into()
+            .near_expect(BridgeError::Cast);
+        let deployed_tokens_required_balance = env::storage_byte_cost()
+            .saturating_mul((3 * (Self::get_basic_storage() + key_len) + value_len).into());
         let bind_token_required_balance = self.required_balance_for_bind_token();
 
         bind_token_required_balance
             .saturating_add(deployed_tokens_required_balance)
+            .saturating_add(Self::required_balance_for_token_account())
             .saturating_add(NEP141_DEPOSIT)
     }
 
+    /// Balance forwarded to the token deployer. It covers the new token account with the
+    /// longest metadata and the deployer's records of the token.
+    fn required_balance_for_token_account() -> NearToken {
+        // Account record with the hash of the global token contract
+        const ACCOUNT_RECORD_LEN: u64 = 100 + 32;
+
+        let metadata_len: u64 = borsh::to_vec(&FungibleTokenMetadata {
+            spec: FT_METADATA_SPEC.to_string(),
+            name: "N".repeat(MAX_TOKEN_NAME_LEN),
+            symbol: "S".repeat(MAX_TOKEN_SYMBOL_LEN),
+            icon: Some("I".repeat(MAX_TOKEN_ICON_LEN)),
+            reference: Some("R".repeat(MAX_TOKEN_REFERENCE_LEN)),
+            reference_hash: Some(Base64VecU8(vec![0; 32])),
+            decimals: u8::MAX,
+        })
+        .near_expect(BridgeError::Borsh)
+        .len()
+        .try_into()
+        .near_expect(BridgeError::Cast);
+
+        // `OmniToken` state: controller, token prefix, total supply, account storage
+        // usage and metadata prefix
+        let state_len: u64 = borsh::to_vec(&(
+            Self::max_account_id(),
+            b"t".to_vec(),
+            u128::MAX,
+            u64::MAX,
+            b"m".to_vec(),
+        ))
+        .near_expect(BridgeError::Borsh)
+        .len()
+        .try_into()
+        .near_expect(BridgeError::Cast);
+
+        // The deployer keeps the token in `deployed_tokens` and `token_code_versions`
+        let key_len = Self::max_key_len_of_account_id();
+        let deployer_records_len = 3 * (Self::get_basic_storage() + key_len + 8);
+
+        env::storage_byte_cost().saturating_mul(
+            (ACCOUNT_RECORD_LEN
+                + 2 * Self::get_basic_storage()
+                + state_len
+                + metadata_len
+                + deployer_records_len)
+                .into(),
+        )
+    }
+
+    /// Balance for the `global_transfer_hashes` entry added next to every pending and
+    /// finalised transfer.
+    fn required_balance_for_global_transfer_hash() -> NearToken {
+        let key_len: u64 = borsh::to_vec(&H256::ZERO)
+            .near_expect(BridgeError::Borsh)
+            .len()
+            .try_into()
+            .near_expect(BridgeError::Cast);
+
+        let value_len: u64 = borsh::to_vec(&UnifiedTransferId {
+            origin_chain: ChainKind::Eth,
+            kind: TransferIdKind::Utxo(Self::max_utxo_id()),
+        })
+        .near_expect(BridgeError::Borsh)
+        .len()
+        .try_into()
+        .near_expect(BridgeError::Cast);
+
+        env::storage_byte_cost()
+            .saturating_mul((Self::get_basic_storage() + key_len + value_len).into())
+    }
+
     const fn get_basic_storage() -> u64 {
         const EXTRA_BYTES_RECORD: u64 = 40;
         const EXTRA_KEY_PREFIX_LEN: u64 = 1;
         EXTRA_BYTES_RECORD + EXTRA_KEY_PREFIX_LEN
     }
 
-    fn max_key_len_of_account_id() -> u64 {
-        let max_account_id: AccountId = "a"
-            .repeat(64)
+    /// Longest valid account ID, the upper bound of every account ID stored by the bridge.
+    fn max_account_id() -> AccountId {
+        "a".repeat(MAX_ACCOUNT_ID_LEN)
             .parse()
-            .near_expect(BridgeError::ParseAccountId);
+            .near_expect(BridgeError::ParseAccountId)
+    }
 
-        borsh::to_vec(&max_account_id)
+    fn max_utxo_id() -> UtxoId {
+        UtxoId {
+            tx_hash: "0".repeat(UTXO_TX_HASH_LEN),
+            vout: u32::MAX,
+        }
+    }
+
+    fn max_key_len_of_account_id() -> u64 {
+        borsh::to_vec(&Self::max_account_id())
             .near_expect(BridgeError::Borsh)
             .len()
             .try_into()
@@ -1,24 +1,30 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
+use near_contract_standards::fungible_token::metadata::{FungibleTokenMetadata, FT_METADATA_SPEC};
 use near_contract_standards::storage_management::StorageBalance;
 use near_sdk::{
-    borsh, json_types::U128, serde_json, test_utils::VMContextBuilder, test_vm_config, testing_env,
-    AccountId, NearToken, PromiseOrValue, PromiseResult, RuntimeFeesConfig,
+    borsh,
+    json_types::U128,
+    serde_json,
+    test_utils::{get_logs, VMContextBuilder},
+    test_vm_config, testing_env, AccountId, NearToken, PromiseOrValue, PromiseResult,
+    RuntimeFeesConfig,
 };
 use omni_types::{
+    btc::UTXOChainConfig,
     locker_args::StorageDepositAction,
-    prover_result::{FinTransferMessage, InitTransferMessage, ProverResult},
+    near_events::LockAction,
+    prover_result::{FinTransferMessage, InitTransferMessage, LogMetadataMessage, ProverResult},
     sol_address::SolAddress,
-    BridgeOnTransferMsg, ChainKind, EvmAddress, Fee, InitTransferMsg, Nonce, OmniAddress,
-    TransferId, TransferIdKind, TransferMessage, UnifiedTransferId, UpdateFee,
+    utxo::UtxoNetwork,
+    BridgeOnTransferMsg, ChainKind, EvmAddress, Fee, GlobalTransferStatus, InitTransferMsg, Nonce,
+    OmniAddress, TransferId, TransferIdKind, TransferMessage, UnifiedTransferId, UpdateFee,
+    MAX_TOKEN_SYMBOL_LEN,
 };
 
-use crate::Contract;
-use crate::{
-    storage::{Decimals, TransferMessageStorage, TransferMessageStorageValue},
-    token_lock::LockAction,
-};
+use crate::storage::{Decimals, TransferMessageStorage, TransferMessageStorageValue};
+use crate::{Contract, TokenStorageRegistration, MAX_TOKEN_STORAGE_REGISTRATIONS, NEP141_DEPOSIT};
 
 const DEFAULT_NONCE: Nonce = 0;
 const DEFAULT_TRANSFER_ID: TransferId = TransferId {
@@ -238,6 +244,64 @@ fn test_init_transfer_invalid_fee() {
     );
 }
 
+fn get_btc_contract(network: UtxoNetwork) -> Contract {
+    let mut contract = get_default_contract();
+    contract.utxo_chain_connectors.insert(
+        ChainKind::Btc,
+        UTXOChainConfig {
+            connector: "btc_connector.testnet".parse().unwrap(),
+            token_id: "nbtc.testnet".parse().unwrap(),
+            network,
+        },
+    );
+    contract
+}
+
+fn run_btc_init_transfer(contract: &mut Contract, recipient: &str) {
+    run_ft_on_transfer(
+        contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(InitTransferMsg {
+            recipient: OmniAddress::Btc(recipient.to_string()),
+            fee: U128(0),
+            native_token_fee: U128(0),
+            msg: None,
+            external_id: None,
+        }),
+    );
+}
+
+#[test]
+fn test_init_transfer_valid_btc_recipient() {
+    let mut contract = get_btc_contract(UtxoNetwork::Mainnet);
+    run_btc_init_transfer(&mut contract, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE + 1);
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_RECIPIENT_ADDRESS")]
+fn test_init_transfer_invalid_btc_recipient() {
+    let mut contract = get_btc_contract(UtxoNetwork::Mainnet);
+    run_btc_init_transfer(&mut contract, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5");
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_RECIPIENT_ADDRESS")]
+fn test_init_transfer_btc_recipient_wrong_network() {
+    let mut contract = get_btc_contract(UtxoNetwork::Testnet);
+    run_btc_init_transfer(&mut contract, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+}
+
+#[test]
+#[should_panic(expected = "ERR_INVALID_RECIPIENT_CHAIN")]
+fn test_init_transfer_btc_recipient_without_connector() {
+    let mut contract = get_default_contract();
+    run_btc_init_transfer(&mut contract, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4");
+}
+
 #[test]
 fn test_init_transfer_balance_updated() {
     let mut contract = get_default_contract();
@@ -1056,7 +1120,7 @@ fn test_fin_transfer_callback_refund_restores_locked_tokens() {
     let lock_actions = vec![LockAction::Unlocked {
         chain_kind: ChainKind::Eth,
         token_id: token_id.clone(),
-        amount: DEFAULT_TRANSFER_AMOUNT,
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
     }];
     contract.fin_transfer_send_tokens_callback(
         transfer_message,
@@ -1072,6 +1136,110 @@ fn test_fin_transfer_callback_refund_restores_locked_tokens() {
     );
 }
 
+#[test]
+fn test_fin_transfer_callback_failed_mint_takes_refund_path() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = "eth-token.testnet".parse().unwrap();
+    contract
+        .deployed_tokens_v2
+        .insert(&token_id, &ChainKind::Eth);
+    contract
+        .locked_tokens
+        .insert(&(ChainKind::Sol, token_id.clone()), &0);
+    let recipient: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+
+    let transfer_message = TransferMessage {
+        origin_nonce: DEFAULT_NONCE,
+        token: OmniAddress::Near(token_id.clone()),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+        recipient: OmniAddress::Near(recipient.clone()),
+        fee: Fee::default(),
+        sender: OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        msg: String::new(),
+        destination_nonce: 1,
+        origin_transfer_id: None,
+    };
+
+    setup_test_env(
+        recipient.clone(),
+        NearToken::from_near(0),
+        Some(vec![PromiseResult::Failed]),
+    );
+
+    let lock_actions = vec![LockAction::Unlocked {
+        chain_kind: ChainKind::Sol,
+        token_id: token_id.clone(),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+    }];
+    contract.fin_transfer_send_tokens_callback(
+        transfer_message,
+        &recipient,
+        false,
+        &recipient,
+        lock_actions,
+    );
+
+    assert_eq!(
+        contract.get_locked_tokens(ChainKind::Sol, token_id),
+        Some(U128(DEFAULT_TRANSFER_AMOUNT))
+    );
+}
+
+#[test]
+fn test_fin_transfer_callback_failed_mint_with_msg_takes_refund_path() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = "eth-token.testnet".parse().unwrap();
+    contract
+        .deployed_tokens_v2
+        .insert(&token_id, &ChainKind::Eth);
+    contract
+        .locked_tokens
+        .insert(&(ChainKind::Sol, token_id.clone()), &0);
+    let recipient: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Eth,
+        origin_nonce: DEFAULT_NONCE,
+    };
+    contract.finalised_transfers.insert(&transfer_id);
+
+    let transfer_message = TransferMessage {
+        origin_nonce: DEFAULT_NONCE,
+        token: OmniAddress::Near(token_id.clone()),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+        recipient: OmniAddress::Near(recipient.clone()),
+        fee: Fee::default(),
+        sender: OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        msg: "msg".to_string(),
+        destination_nonce: 1,
+        origin_transfer_id: None,
+    };
+
+    setup_test_env(
+        recipient.clone(),
+        NearToken::from_near(0),
+        Some(vec![PromiseResult::Failed]),
+    );
+
+    let lock_actions = vec![LockAction::Unlocked {
+        chain_kind: ChainKind::Sol,
+        token_id: token_id.clone(),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+    }];
+    contract.fin_transfer_send_tokens_callback(
+        transfer_message,
+        &recipient,
+        true,
+        &recipient,
+        lock_actions,
+    );
+
+    assert!(!contract.is_transfer_finalised(transfer_id));
+    assert_eq!(
+        contract.get_locked_tokens(ChainKind::Sol, token_id),
+        Some(U128(DEFAULT_TRANSFER_AMOUNT))
+    );
+}
+
 #[test]
 fn test_is_transfer_finalised() {
     let mut contract = get_default_contract();
@@ -1088,6 +1256,54 @@ fn test_is_transfer_finalised() {
     assert!(contract.is_transfer_finalised(transfer_id));
 }
 
+#[test]
+fn test_resolve_global_transfer_hash() {
+    let mut contract = get_default_contract();
+    run_ft_on_transfer(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT.to_string(),
+        DEFAULT_FT_CONTRACT_ACCOUNT.to_string(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+        None,
+        &BridgeOnTransferMsg::InitTransfer(get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0)),
+    );
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: contract.current_origin_nonce,
+    };
+
+    let resolved = contract
+        .resolve_global_transfer_hash(transfer_id.global_hash())
+        .unwrap();
+    assert_eq!(resolved.transfer_id, transfer_id.into());
+    assert_eq!(resolved.status, GlobalTransferStatus::Pending);
+    assert_eq!(
+        resolved.transfer_message.unwrap().amount,
+        U128(DEFAULT_TRANSFER_AMOUNT)
+    );
+
+    contract.remove_transfer_message_without_refund(transfer_id);
+    assert!(contract
+        .resolve_global_transfer_hash(transfer_id.global_hash())
+        .is_none());
+
+    let fin_transfer_id = TransferId {
+        origin_chain: ChainKind::Eth,
+        origin_nonce: 1,
+    };
+    assert!(contract
+        .resolve_global_transfer_hash(fin_transfer_id.global_hash())
+        .is_none());
+    contract.add_fin_transfer(&fin_transfer_id);
+    assert_eq!(
+        contract
+            .resolve_global_transfer_hash(fin_transfer_id.global_hash())
+            .unwrap()
+            .status,
+        GlobalTransferStatus::Finalised
+    );
+}
+
 #[test]
 fn test_normalize_amount() {
     assert_eq!(
@@ -1264,3 +1480,828 @@ fn test_legacy_ft_on_transfer() {
         &get_init_transfer_msg(DEFAULT_ETH_USER_ADDRESS, 0, 0),
     );
 }
+
+#[test]
+fn test_finish_withdraw_v3_from_migrated_token() {
+    let mut contract = get_default_contract();
+    let sender_id: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let legacy_token_id: AccountId = "legacy-token.testnet".parse().unwrap();
+    let token_id: AccountId = "eth-token.testnet".parse().unwrap();
+
+    contract
+        .deployed_tokens_v2
+        .insert(&token_id, &ChainKind::Eth);
+    contract.migrated_tokens.insert(&legacy_token_id, &token_id);
+
+    let min_storage_balance = contract.required_balance_for_account();
+    let init_transfer_balance = contract.required_balance_for_init_transfer(None);
+    let total_balance = min_storage_balance.saturating_add(init_transfer_balance);
+    run_storage_deposit(&mut contract, sender_id.clone(), total_balance);
+
+    let recipient = OmniAddress::Base(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    setup_test_env(legacy_token_id, NearToken::from_yoctonear(0), None);
+    contract.finish_withdraw_v3(
+        sender_id.clone(),
+        DEFAULT_TRANSFER_AMOUNT,
+        recipient.clone(),
+        Some(Fee {
+            fee: U128(DEFAULT_TRANSFER_FEE),
+            native_fee: U128(0),
+        }),
+    );
+
+    let transfer = contract.get_transfer_message(TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: 1,
+    });
+    assert_eq!(transfer.token, OmniAddress::Near(token_id));
+    assert_eq!(transfer.recipient, recipient);
+    assert_eq!(transfer.fee.fee, U128(DEFAULT_TRANSFER_FEE));
+    assert_eq!(transfer.destination_nonce, 1);
+
+    let storage_balance = contract.accounts_balances.get(&sender_id).unwrap();
+    assert!(
+        storage_balance.available < total_balance,
+        "Expected storage balance must be deducted"
+    );
+}
+
+#[test]
+fn test_finish_withdraw_v3_unregistered_sender_books_storage_to_bridge() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = "eth-token.testnet".parse().unwrap();
+    contract
+        .deployed_tokens_v2
+        .insert(&token_id, &ChainKind::Eth);
+
+    let bridge_id = near_sdk::env::current_account_id();
+    let bridge_balance = contract
+        .required_balance_for_account()
+        .saturating_add(contract.required_balance_for_init_transfer(None));
+    run_storage_deposit(&mut contract, bridge_id.clone(), bridge_balance);
+
+    setup_test_env(token_id, NearToken::from_yoctonear(0), None);
+    contract.finish_withdraw_v3(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        DEFAULT_TRANSFER_AMOUNT,
+        OmniAddress::Base(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        None,
+    );
+
+    assert!(contract
+        .pending_transfers
+        .get(&TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: 1,
+        })
+        .is_some());
+    assert!(
+        contract
+            .accounts_balances
+            .get(&bridge_id)
+            .unwrap()
+            .available
+            < bridge_balance
+    );
+}
+
+#[test]
+fn test_finish_withdraw_v3_unregistered_sender_with_native_fee() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = "eth-token.testnet".parse().unwrap();
+    contract
+        .deployed_tokens_v2
+        .insert(&token_id, &ChainKind::Eth);
+
+    setup_test_env(token_id, NearToken::from_yoctonear(0), None);
+    contract.finish_withdraw_v3(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        DEFAULT_TRANSFER_AMOUNT,
+        OmniAddress::Base(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        Some(Fee {
+            fee: U128(0),
+            native_fee: U128(1),
+        }),
+    );
+
+    assert!(contract
+        .pending_transfers
+        .get(&TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: 1,
+        })
+        .is_none());
+}
+
+#[test]
+fn test_finish_withdraw_v3_invalid_recipient() {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = "eth-token.testnet".parse().unwrap();
+    contract
+        .deployed_tokens_v2
+        .insert(&token_id, &ChainKind::Eth);
+
+    setup_test_env(token_id, NearToken::from_yoctonear(0), None);
+    contract.finish_withdraw_v3(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        DEFAULT_TRANSFER_AMOUNT,
+        OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        None,
+    );
+
+    assert_eq!(contract.current_origin_nonce, DEFAULT_NONCE);
+    assert!(get_logs()
+        .iter()
+        .any(|log| log.contains("failed_withdraw_event")
+            && log.contains("ERR_INVALID_RECIPIENT_CHAIN")));
+}
+
+#[test]
+fn test_refund_withdraw_callback_registered_sender() {
+    let mut contract = get_default_contract();
+    let sender_id: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let storage_balance = contract
+        .required_balance_for_account()
+        .saturating_add(NEP141_DEPOSIT);
+    run_storage_deposit(&mut contract, sender_id.clone(), storage_balance);
+
+    setup_test_env(
+        sender_id.clone(),
+        NearToken::from_yoctonear(0),
+        Some(vec![PromiseResult::Successful(
+            serde_json::to_vec(&Some(StorageBalance {
+                total: NEP141_DEPOSIT,
+                available: NearToken::from_yoctonear(0),
+            }))
+            .unwrap(),
+        )]),
+    );
+    contract.refund_withdraw_callback(
+        sender_id.clone(),
+        "eth-token.testnet".parse().unwrap(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+    );
+
+    assert_eq!(
+        contract
+            .accounts_balances
+            .get(&sender_id)
+            .unwrap()
+            .available,
+        NEP141_DEPOSIT
+    );
+}
+
+#[test]
+fn test_refund_withdraw_callback_charges_sender_registration() {
+    let mut contract = get_default_contract();
+    let sender_id: AccountId = DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap();
+    let storage_balance = contract
+        .required_balance_for_account()
+        .saturating_add(NEP141_DEPOSIT);
+    run_storage_deposit(&mut contract, sender_id.clone(), storage_balance);
+
+    setup_test_env(
+        sender_id.clone(),
+        NearToken::from_yoctonear(0),
+        Some(vec![PromiseResult::Successful(
+            serde_json::to_vec(&None::<StorageBalance>).unwrap(),
+        )]),
+    );
+    contract.refund_withdraw_callback(
+        sender_id.clone(),
+        "eth-token.testnet".parse().unwrap(),
+        U128(DEFAULT_TRANSFER_AMOUNT),
+    );
+
+    assert_eq!(
+        contract
+            .accounts_balances
+            .get(&sender_id)
+            .unwrap()
+            .available,
+        NearToken::from_yoctonear(0)
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOKEN_NOT_DEPLOYED")]
+fn test_finish_withdraw_v3_unknown_token() {
+    let mut contract = get_default_contract();
+
+    setup_test_env(
+        DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.finish_withdraw_v3(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        DEFAULT_TRANSFER_AMOUNT,
+        OmniAddress::Arb(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        None,
+    );
+}
+
+fn run_sync_token_metadata(
+    contract: &mut Contract,
+    token_address: &OmniAddress,
+    timestamp: u64,
+    sequence: Option<u64>,
+) {
+    let context = VMContextBuilder::new()
+        .current_account_id(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap())
+        .predecessor_account_id(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap())
+        .block_timestamp(timestamp)
+        .build();
+    testing_env!(context);
+
+    contract
+        .sync_token_metadata_callback(Ok(ProverResult::LogMetadata(LogMetadataMessage {
+            token_address: token_address.clone(),
+            name: "Renamed Token".parse().unwrap(),
+            symbol: "RNM".parse().unwrap(),
+            decimals: 6,
+            emitter_address: OmniAddress::Eth(
+                EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap(),
+            ),
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            sequence,
+        })))
+        .detach();
+}
+
+fn setup_metadata_sync_contract() -> (Contract, OmniAddress) {
+    let mut contract = get_default_contract();
+    let token_id: AccountId = "eth-token.testnet".parse().unwrap();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+
+    contract.factories.insert(
+        &ChainKind::Eth,
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    );
+    contract
+        .deployed_tokens_v2
+        .insert(&token_id, &ChainKind::Eth);
+    contract
+        .token_address_to_id
+        .insert(&token_address, &token_id);
+
+    (contract, token_address)
+}
+
+#[test]
+fn test_sync_token_metadata() {
+    let (mut contract, token_address) = setup_metadata_sync_contract();
+    let token_id = contract.get_token_id(&token_address);
+    let next_sync_timestamp = 24 * 60 * 60 * 1_000_000_000 + 1;
+
+    run_sync_token_metadata(&mut contract, &token_address, 1, Some(10));
+    assert_eq!(contract.metadata_sync_timestamps.get(&token_id), Some(1));
+    assert_eq!(contract.metadata_sequences.get(&token_id), Some(10));
+
+    run_sync_token_metadata(&mut contract, &token_address, next_sync_timestamp, Some(11));
+    assert_eq!(
+        contract.metadata_sync_timestamps.get(&token_id),
+        Some(next_sync_timestamp)
+    );
+    assert_eq!(contract.metadata_sequences.get(&token_id), Some(11));
+}
+
+#[test]
+#[should_panic(expected = "ERR_METADATA_SYNC_RATE_LIMITED")]
+fn test_sync_token_metadata_rate_limited() {
+    let (mut contract, token_address) = setup_metadata_sync_contract();
+
+    run_sync_token_metadata(&mut contract, &token_address, 1, Some(10));
+    run_sync_token_metadata(&mut contract, &token_address, 2, Some(11));
+}
+
+#[test]
+#[should_panic(expected = "ERR_STALE_METADATA_PROOF")]
+fn test_sync_token_metadata_stale_proof() {
+    let (mut contract, token_address) = setup_metadata_sync_contract();
+
+    run_sync_token_metadata(&mut contract, &token_address, 1, Some(10));
+    run_sync_token_metadata(
+        &mut contract,
+        &token_address,
+        24 * 60 * 60 * 1_000_000_000 + 1,
+        Some(10),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_STALE_METADATA_PROOF")]
+fn test_sync_token_metadata_without_sequence() {
+    let (mut contract, token_address) = setup_metadata_sync_contract();
+
+    run_sync_token_metadata(&mut contract, &token_address, 1, None);
+}
+
+fn setup_token_deployer_contract() -> (Contract, AccountId) {
+    let mut contract = get_default_contract();
+    let deployer: AccountId = "omnidep.testnet".parse().unwrap();
+    contract
+        .token_deployer_accounts
+        .insert(&ChainKind::Eth, &deployer);
+
+    (contract, deployer)
+}
+
+#[test]
+fn test_predict_token_account() {
+    let (contract, deployer) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+
+    assert_eq!(
+        contract.predict_token_account(token_address.clone()),
+        Some(token_address.get_token_account_id(&deployer).unwrap())
+    );
+    assert_eq!(
+        contract.predict_token_account(OmniAddress::new_zero(ChainKind::Sol).unwrap()),
+        None
+    );
+}
+
+#[test]
+fn test_predict_token_account_for_bound_token() {
+    let (mut contract, _) = setup_token_deployer_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    contract
+        .token_address_to_id
+        .insert(&token_address, &token_id);
+
+    assert_eq!(
+        contract.predict_token_account(token_address),
+        Some(token_id)
+    );
+}
+
+fn run_pre_register_token_storage(
+    contract: &mut Contract,
+    payer: &str,
+    token_address: &OmniAddress,
+    account_ids: Vec<AccountId>,
+) {
+    setup_test_env(payer.parse().unwrap(), NearToken::from_near(1), None);
+    contract.pre_register_token_storage(token_address.clone(), account_ids);
+}
+
+#[test]
+fn test_pre_register_token_storage() {
+    let (mut contract, deployer) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let accounts: Vec<AccountId> = vec![
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        "relayer.testnet".parse().unwrap(),
+    ];
+
+    run_pre_register_token_storage(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT,
+        &token_address,
+        vec![
+            accounts[0].clone(),
+            accounts[1].clone(),
+            accounts[0].clone(),
+        ],
+    );
+
+    let token_id = token_address.get_token_account_id(&deployer).unwrap();
+    assert_eq!(
+        contract.get_token_storage_registrations(&token_id),
+        accounts
+            .into_iter()
+            .map(|account_id| TokenStorageRegistration {
+                account_id,
+                payer: DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+            })
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOKEN_EXISTS")]
+fn test_pre_register_token_storage_for_bound_token() {
+    let (mut contract, _) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    contract.token_address_to_id.insert(
+        &token_address,
+        &DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(),
+    );
+
+    run_pre_register_token_storage(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT,
+        &token_address,
+        vec![DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()],
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_NEAR_TOKEN_NOT_DEPLOYABLE")]
+fn test_pre_register_token_storage_for_near_token() {
+    let (mut contract, _) = setup_token_deployer_contract();
+
+    run_pre_register_token_storage(
+        &mut contract,
+        DEFAULT_NEAR_USER_ACCOUNT,
+        &OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+        vec![DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()],
+    );
+}
+
+fn token_storage_accounts(prefix: &str, count: usize) -> Vec<AccountId> {
+    (0..count)
+        .map(|i| format!("{prefix}-{i}.testnet").parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_pre_register_token_storage_capped_per_payer() {
+    let (mut contract, deployer) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+
+    run_pre_register_token_storage(
+        &mut contract,
+        "payer.testnet",
+        &token_address,
+        token_storage_accounts("payer", MAX_TOKEN_STORAGE_REGISTRATIONS),
+    );
+    run_pre_register_token_storage(
+        &mut contract,
+        "alice.testnet",
+        &token_address,
+        token_storage_accounts("alice", 1),
+    );
+
+    let token_id = token_address.get_token_account_id(&deployer).unwrap();
+    assert_eq!(
+        contract.get_token_storage_registrations(&token_id).len(),
+        MAX_TOKEN_STORAGE_REGISTRATIONS + 1
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOO_MANY_STORAGE_REGISTRATIONS")]
+fn test_pre_register_token_storage_over_payer_cap() {
+    let (mut contract, _) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+
+    run_pre_register_token_storage(
+        &mut contract,
+        "payer.testnet",
+        &token_address,
+        token_storage_accounts("payer", MAX_TOKEN_STORAGE_REGISTRATIONS + 1),
+    );
+}
+
+#[test]
+fn test_deploy_token_by_deployer_callback_registers_storage_in_batches() {
+    let (mut contract, deployer) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let token_id = token_address.get_token_account_id(&deployer).unwrap();
+    let alice_accounts = token_storage_accounts("alice", 1);
+
+    run_pre_register_token_storage(
+        &mut contract,
+        "payer.testnet",
+        &token_address,
+        token_storage_accounts("payer", MAX_TOKEN_STORAGE_REGISTRATIONS),
+    );
+    run_pre_register_token_storage(
+        &mut contract,
+        "alice.testnet",
+        &token_address,
+        alice_accounts.clone(),
+    );
+
+    contract.deployed_tokens.insert(&token_id);
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        Some(vec![PromiseResult::Successful(vec![])]),
+    );
+    contract.deploy_token_by_deployer_callback(&token_address, token_id.clone());
+
+    assert_eq!(
+        contract.get_token_storage_registrations(&token_id),
+        vec![TokenStorageRegistration {
+            account_id: alice_accounts[0].clone(),
+            payer: "alice.testnet".parse().unwrap(),
+        }]
+    );
+
+    setup_test_env(
+        "alice.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.register_token_storage(token_id.clone());
+
+    assert!(contract
+        .get_token_storage_registrations(&token_id)
+        .is_empty());
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOKEN_NOT_DEPLOYED")]
+fn test_register_token_storage_for_undeployed_token() {
+    let (mut contract, deployer) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+
+    run_pre_register_token_storage(
+        &mut contract,
+        "payer.testnet",
+        &token_address,
+        token_storage_accounts("payer", 1),
+    );
+
+    contract.register_token_storage(token_address.get_token_account_id(&deployer).unwrap());
+}
+
+#[test]
+fn test_unregister_token_storage() {
+    let (mut contract, deployer) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let token_id = token_address.get_token_account_id(&deployer).unwrap();
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    let payer = "alice.testnet";
+    let other_payer = "payer.testnet";
+
+    run_pre_register_token_storage(
+        &mut contract,
+        payer,
+        &token_address,
+        vec![payer.parse().unwrap()],
+    );
+    run_pre_register_token_storage(
+        &mut contract,
+        other_payer,
+        &token_address,
+        vec![relayer.clone()],
+    );
+
+    setup_test_env(payer.parse().unwrap(), NearToken::from_yoctonear(0), None);
+    contract.unregister_token_storage(
+        token_address.clone(),
+        vec![payer.parse().unwrap(), relayer.clone()],
+    );
+
+    assert_eq!(
+        contract.get_token_storage_registrations(&token_id),
+        vec![TokenStorageRegistration {
+            account_id: relayer,
+            payer: other_payer.parse().unwrap(),
+        }]
+    );
+}
+
+#[test]
+#[should_panic(expected = "ERR_STORAGE_REGISTRATION_NOT_FOUND")]
+fn test_unregister_token_storage_of_other_payer() {
+    let (mut contract, _) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+
+    run_pre_register_token_storage(
+        &mut contract,
+        "payer.testnet",
+        &token_address,
+        vec![relayer.clone()],
+    );
+
+    setup_test_env(
+        "attacker.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.unregister_token_storage(token_address, vec![relayer]);
+}
+
+#[test]
+fn test_unregister_token_storage_by_dao() {
+    let (mut contract, deployer) = setup_token_deployer_contract();
+    let token_address = OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap());
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+
+    run_pre_register_token_storage(
+        &mut contract,
+        "payer.testnet",
+        &token_address,
+        vec![relayer.clone()],
+    );
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.unregister_token_storage(token_address.clone(), vec![relayer]);
+
+    let token_id = token_address.get_token_account_id(&deployer).unwrap();
+    assert!(contract
+        .get_token_storage_registrations(&token_id)
+        .is_empty());
+}
+
+fn run_queued_deploy_token(contract: &mut Contract) -> OmniAddress {
+    let token_address = OmniAddress::Eth(
+        EvmAddress::from_str("0x0987654321098765432109876543210987654321").unwrap(),
+    );
+    contract.factories.insert(
+        &ChainKind::Eth,
+        &OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+    );
+    contract.token_allowlist_enabled = true;
+
+    setup_test_env(
+        "omni_bridge.testnet".parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    let result = contract.deploy_token_callback(
+        NearToken::from_near(1),
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        Ok(ProverResult::LogMetadata(LogMetadataMessage {
+            token_address: token_address.clone(),
+            name: "Token".parse().unwrap(),
+            symbol: "TKN".parse().unwrap(),
+            decimals: 18,
+            emitter_address: OmniAddress::Eth(
+                EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap(),
+            ),
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            sequence: Some(1),
+        })),
+    );
+    assert!(matches!(result, PromiseOrValue::Value(())));
+
+    token_address
+}
+
+#[test]
+fn test_deploy_token_queued_in_allowlist_mode() {
+    let (mut contract, _) = setup_token_deployer_contract();
+    let token_address = run_queued_deploy_token(&mut contract);
+
+    let requests = contract.get_token_review_requests(None, None);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, token_address);
+    assert_eq!(requests[0].1.deposit, NearToken::from_near(1));
+    assert!(requests[0].1.metadata.is_some());
+    assert!(contract.token_address_to_id.get(&token_address).is_none());
+}
+
+#[test]
+fn test_approve_token_review() {
+    let (mut contract, deployer) = setup_token_deployer_contract();
+    let token_address = run_queued_deploy_token(&mut contract);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    let result = contract.approve_token_review(token_address.clone());
+
+    assert!(matches!(result, PromiseOrValue::Promise(_)));
+    assert_eq!(
+        contract
+            .metadata_sequences
+            .get(&token_address.get_token_account_id(&deployer).unwrap()),
+        Some(1)
+    );
+    assert!(contract.is_token_allowlisted(&token_address));
+    assert!(contract.get_token_review_requests(None, None).is_empty());
+    assert_eq!(
+        contract.token_address_to_id.get(&token_address),
+        Some(token_address.get_token_account_id(&deployer).unwrap())
+    );
+}
+
+#[test]
+fn test_reject_token_review() {
+    let (mut contract, _) = setup_token_deployer_contract();
+    let token_address = run_queued_deploy_token(&mut contract);
+
+    setup_test_env(
+        DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap(),
+        NearToken::from_yoctonear(0),
+        None,
+    );
+    contract.reject_token_review(token_address.clone());
+
+    assert!(!contract.is_token_allowlisted(&token_address));
+    assert!(contract.get_token_review_requests(None, None).is_empty());
+    assert!(contract.token_address_to_id.get(&token_address).is_none());
+}
+
+#[test]
+fn test_request_token_review_for_near_token() {
+    let mut contract = get_default_contract();
+    let token_address = OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap());
+
+    setup_test_env(
+        "alice.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    contract.request_token_review(token_address.clone());
+
+    let requests = contract.get_token_review_requests(None, None);
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, token_address);
+    assert!(requests[0].1.metadata.is_none());
+}
+
+// A foreign token review is only opened by `deploy_token`, so nobody can front-run it with
+// a request that makes the deployment callback fail.
+#[test]
+#[should_panic(expected = "ERR_NOT_NEAR_TOKEN")]
+fn test_request_token_review_for_foreign_token() {
+    let (mut contract, _) = setup_token_deployer_contract();
+
+    setup_test_env(
+        "alice.testnet".parse().unwrap(),
+        NearToken::from_near(1),
+        None,
+    );
+    contract.request_token_review(OmniAddress::Eth(
+        EvmAddress::from_str("0x0987654321098765432109876543210987654321").unwrap(),
+    ));
+}
+
+#[test]
+#[should_panic(expected = "ERR_TOKEN_NOT_ALLOWLISTED")]
+fn test_log_metadata_in_allowlist_mode() {
+    let mut contract = get_default_contract();
+    contract.token_allowlist_enabled = true;
+
+    contract
+        .log_metadata(&DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap(), None)
+        .detach();
+}
+
+#[test]
+fn test_log_metadata_callback_truncates_long_symbol() {
+    let contract = get_default_contract();
+    let token_id: AccountId = DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap();
+    let metadata = FungibleTokenMetadata {
+        spec: FT_METADATA_SPEC.to_owned(),
+        name: "Token".to_owned(),
+        symbol: "S".repeat(MAX_TOKEN_SYMBOL_LEN + 1),
+        icon: None,
+        reference: None,
+        reference_hash: None,
+        decimals: 18,
+    };
+
+    setup_test_env(token_id.clone(), NearToken::from_yoctonear(0), None);
+    contract
+        .log_metadata_callback(metadata, &token_id, None)
+        .detach();
+}
+
+#[test]
+fn test_required_balance_for_init_transfer_message_covers_storage() {
+    let mut contract = get_default_contract();
+    let owner: AccountId = "relayer.testnet".parse().unwrap();
+    let transfer_message = TransferMessage {
+        origin_nonce: 1,
+        token: OmniAddress::Near(DEFAULT_FT_CONTRACT_ACCOUNT.parse().unwrap()),
+        amount: U128(DEFAULT_TRANSFER_AMOUNT),
+        recipient: OmniAddress::Eth(EvmAddress::from_str(DEFAULT_ETH_USER_ADDRESS).unwrap()),
+        fee: Fee::default(),
+        sender: OmniAddress::Near(DEFAULT_NEAR_USER_ACCOUNT.parse().unwrap()),
+        msg: String::default(),
+        destination_nonce: 1,
+        origin_transfer_id: None,
+    };
+    let required_balance =
+        contract.required_balance_for_init_transfer_message(transfer_message.clone());
+
+    let charged = contract.add_transfer_message(transfer_message, owner.clone());
+    contract.update_storage_balance(owner, charged, required_balance);
+}
+
+#[test]
+fn test_required_balance_for_fin_transfer_covers_storage() {
+    let mut contract = get_default_contract();
+    let relayer: AccountId = "relayer.testnet".parse().unwrap();
+    let required_balance = contract.required_balance_for_fin_transfer();
+
+    let charged = contract.add_fin_utxo_transfer(&UnifiedTransferId {
+        origin_chain: ChainKind::Btc,
+        kind: TransferIdKind::Utxo(omni_types::UtxoId {
+            tx_hash: "0".repeat(omni_types::UTXO_TX_HASH_LEN),
+            vout: u32::MAX,
+        }),
+    });
+    contract.update_storage_balance(relayer, charged, required_balance);
+}
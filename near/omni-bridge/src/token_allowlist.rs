@@ -0,0 +1,177 @@
+use near_plugins::{access_control_any, pause, AccessControllable, Pausable};
+use near_sdk::{env, near, require, AccountId, NearToken, PromiseOrValue};
+use omni_types::errors::BridgeError;
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::prover_result::LogMetadataMessage;
+use omni_types::{BasicMetadata, OmniAddress};
+use omni_utils::near_expect::NearExpect;
+
+use crate::{Contract, ContractExt, Role};
+
+/// Token waiting for the DAO to allowlist it while the token allowlist mode is enabled.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct TokenReviewRequest {
+    pub requester: AccountId,
+    /// Refunded to the requester on rejection, or used for the deployment on approval.
+    pub deposit: NearToken,
+    /// Verified metadata of a queued `deploy_token` call. The token is deployed with it
+    /// once the request is approved.
+    pub metadata: Option<LogMetadataMessage>,
+}
+
+#[near]
+impl Contract {
+    /// While enabled, `log_metadata` and `deploy_token` only accept allowlisted tokens.
+    /// Deployments of other tokens are queued for review instead.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_token_allowlist_enabled(&mut self, enabled: bool) {
+        self.token_allowlist_enabled = enabled;
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_tokens_to_allowlist(&mut self, token_addresses: Vec<OmniAddress>) {
+        for token_address in token_addresses {
+            self.token_allowlist.insert(&token_address);
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_tokens_from_allowlist(&mut self, token_addresses: Vec<OmniAddress>) {
+        for token_address in token_addresses {
+            self.token_allowlist.remove(&token_address);
+        }
+    }
+
+    /// Asks the DAO to allowlist a NEAR token before calling `log_metadata`. Foreign tokens are
+    /// queued by `deploy_token` together with their verified metadata. The attached deposit
+    /// must cover the storage of the request and is refunded once the request is resolved.
+    #[payable]
+    #[pause(except(roles(Role::DAO)))]
+    pub fn request_token_review(&mut self, token_address: OmniAddress) {
+        require!(
+            matches!(token_address, OmniAddress::Near(_)),
+            BridgeError::NotNearToken.as_ref()
+        );
+        require!(
+            !self.token_allowlist.contains(&token_address),
+            BridgeError::TokenAlreadyAllowlisted.as_ref()
+        );
+
+        self.add_token_review_request(
+            token_address,
+            env::predecessor_account_id(),
+            env::attached_deposit(),
+            None,
+        );
+    }
+
+    /// Allowlists the token and deploys it if the request came from `deploy_token`.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn approve_token_review(&mut self, token_address: OmniAddress) -> PromiseOrValue<()> {
+        let request = self
+            .token_review_requests
+            .remove(&token_address)
+            .near_expect(BridgeError::TokenReviewNotFound);
+        self.token_allowlist.insert(&token_address);
+
+        env::log_str(
+            &OmniBridgeEvent::TokenReviewApprovedEvent {
+                token_address: token_address.clone(),
+            }
+            .to_log_string(self.event_format),
+        );
+
+        if let Some(metadata) = request.metadata {
+            let chain = metadata.emitter_address.get_chain();
+            let sequence = metadata.sequence;
+            self.deploy_token_internal(
+                chain,
+                &token_address,
+                BasicMetadata::from(metadata),
+                sequence,
+                request.deposit,
+            )
+            .into()
+        } else {
+            Self::refund(request.requester, request.deposit);
+            PromiseOrValue::Value(())
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn reject_token_review(&mut self, token_address: OmniAddress) {
+        let request = self
+            .token_review_requests
+            .remove(&token_address)
+            .near_expect(BridgeError::TokenReviewNotFound);
+        Self::refund(request.requester, request.deposit);
+
+        env::log_str(
+            &OmniBridgeEvent::TokenReviewRejectedEvent { token_address }
+                .to_log_string(self.event_format),
+        );
+    }
+
+    pub fn is_token_allowlist_enabled(&self) -> bool {
+        self.token_allowlist_enabled
+    }
+
+    pub fn is_token_allowlisted(&self, token_address: &OmniAddress) -> bool {
+        self.token_allowlist.contains(token_address)
+    }
+
+    pub fn get_token_review_requests(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<(OmniAddress, TokenReviewRequest)> {
+        self.token_review_requests
+            .iter()
+            .skip(usize::try_from(from_index.unwrap_or_default()).unwrap_or(usize::MAX))
+            .take(usize::try_from(limit.unwrap_or(u64::MAX)).unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+impl Contract {
+    pub(crate) fn is_token_allowed(&self, token_address: &OmniAddress) -> bool {
+        !self.token_allowlist_enabled || self.token_allowlist.contains(token_address)
+    }
+
+    pub(crate) fn add_token_review_request(
+        &mut self,
+        token_address: OmniAddress,
+        requester: AccountId,
+        deposit: NearToken,
+        metadata: Option<LogMetadataMessage>,
+    ) {
+        let storage_usage = env::storage_usage();
+        let request = TokenReviewRequest {
+            requester: requester.clone(),
+            deposit,
+            metadata,
+        };
+        require!(
+            self.token_review_requests
+                .insert(&token_address, &request)
+                .is_none(),
+            BridgeError::TokenReviewAlreadyRequested.as_ref()
+        );
+
+        let required_deposit = env::storage_byte_cost()
+            .saturating_mul((env::storage_usage().saturating_sub(storage_usage)).into());
+        require!(
+            deposit >= required_deposit,
+            BridgeError::InsufficientStorageDeposit.as_ref()
+        );
+
+        env::log_str(
+            &OmniBridgeEvent::TokenReviewRequestedEvent {
+                token_address,
+                requester,
+            }
+            .to_log_string(self.event_format),
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use near_plugins::{access_control_any, AccessControllable};
 use near_sdk::{json_types::U128, near, require, AccountId};
-use omni_types::{errors::TokenLockError, ChainKind};
+use omni_types::{errors::TokenLockError, near_events::LockAction, ChainKind};
 use omni_utils::near_expect::NearExpect;
 
 use crate::{Contract, ContractExt, Role};
@@ -12,22 +12,6 @@ pub struct SetLockedTokenArgs {
     amount: U128,
 }
 
-#[near(serializers=[json, borsh])]
-#[derive(Debug, Clone)]
-pub enum LockAction {
-    Locked {
-        chain_kind: ChainKind,
-        token_id: AccountId,
-        amount: u128,
-    },
-    Unlocked {
-        chain_kind: ChainKind,
-        token_id: AccountId,
-        amount: u128,
-    },
-    Unchanged,
-}
-
 #[near]
 impl Contract {
     #[must_use]
@@ -64,7 +48,7 @@ impl Contract {
         LockAction::Locked {
             chain_kind,
             token_id: token_id.clone(),
-            amount,
+            amount: U128(amount),
         }
     }
 
@@ -89,7 +73,7 @@ impl Contract {
         LockAction::Unlocked {
             chain_kind,
             token_id: token_id.clone(),
-            amount,
+            amount: U128(amount),
         }
     }
 
@@ -127,14 +111,14 @@ impl Contract {
                     token_id,
                     amount,
                 } => {
-                    self.unlock_tokens(*chain_kind, token_id, *amount);
+                    self.unlock_tokens(*chain_kind, token_id, amount.0);
                 }
                 LockAction::Unlocked {
                     chain_kind,
                     token_id,
                     amount,
                 } => {
-                    self.lock_tokens(*chain_kind, token_id, *amount);
+                    self.lock_tokens(*chain_kind, token_id, amount.0);
                 }
                 LockAction::Unchanged => {}
             }
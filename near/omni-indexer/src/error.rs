@@ -0,0 +1,17 @@
+use omni_utils::ErrorDisplay;
+use strum_macros::AsRefStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, AsRefStr, ErrorDisplay)]
+#[strum(serialize_all = "shouty_snake_case", prefix = "ERR_")]
+#[non_exhaustive]
+pub enum IndexerError {
+    Borsh,
+    Database(String),
+    InvalidInput(String),
+}
+
+impl From<sled::Error> for IndexerError {
+    fn from(err: sled::Error) -> Self {
+        Self::Database(err.to_string())
+    }
+}
@@ -0,0 +1,378 @@
+//! Embedded store of transfer lifecycles built from bridge events.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+use near_sdk::AccountId;
+use omni_types::near_events::OmniBridgeEvent;
+use omni_types::{ChainKind, FastTransferId, TransferMessage, UnifiedTransferId};
+
+use crate::error::IndexerError;
+use crate::lake::StreamerMessage;
+use crate::record::{EventLocation, TransferRecord, TransferStatus};
+
+const TRANSFER_PREFIX: &[u8] = b"t/";
+const FAST_TRANSFER_PREFIX: &[u8] = b"f/";
+const LAST_BLOCK_HEIGHT_KEY: &[u8] = b"m/last_block_height";
+/// Upper bound on the legs followed by `transfer_legs`, guards against link cycles.
+const MAX_TRANSFER_LEGS: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct IndexerConfig {
+    pub bridge_account_id: AccountId,
+    /// Bridged token of every UTXO chain. UTXO transfer events only carry the token, so
+    /// events of unlisted tokens are not indexed.
+    pub utxo_chain_tokens: HashMap<AccountId, ChainKind>,
+}
+
+pub struct Indexer {
+    db: sled::Db,
+    config: IndexerConfig,
+}
+
+impl Indexer {
+    pub fn open(path: impl AsRef<Path>, config: IndexerConfig) -> Result<Self, IndexerError> {
+        Ok(Self {
+            db: sled::open(path)?,
+            config,
+        })
+    }
+
+    /// In-memory database removed on drop, for tests and one-off runs.
+    pub fn temporary(config: IndexerConfig) -> Result<Self, IndexerError> {
+        Ok(Self {
+            db: sled::Config::new().temporary(true).open()?,
+            config,
+        })
+    }
+
+    /// Processes a stream of whitespace separated `StreamerMessage`s, e.g. a NEAR Lake
+    /// JSON fixture or newline delimited JSON, and flushes the database at the end.
+    ///
+    /// Returns the number of indexed events.
+    pub fn process_reader(&self, reader: impl Read) -> Result<usize, IndexerError> {
+        let mut indexed = 0;
+        for message in serde_json::Deserializer::from_reader(reader).into_iter() {
+            let message: StreamerMessage =
+                message.map_err(|err| IndexerError::InvalidInput(err.to_string()))?;
+            indexed += self.process_streamer_message(&message)?;
+        }
+        self.db.flush()?;
+
+        Ok(indexed)
+    }
+
+    /// Applies the bridge events of one block atomically and returns the number of indexed
+    /// events. Blocks at or below the last processed height are skipped, so inputs can be
+    /// replayed safely.
+    pub fn process_streamer_message(
+        &self,
+        message: &StreamerMessage,
+    ) -> Result<usize, IndexerError> {
+        let block_height = message.block.header.height;
+        if self
+            .last_block_height()?
+            .is_some_and(|last| block_height <= last)
+        {
+            return Ok(0);
+        }
+
+        let mut update = BlockUpdate::new(self);
+        let mut indexed = 0;
+        for log in message.bridge_logs(&self.config.bridge_account_id) {
+            // The bridge also logs plain strings, e.g. when yielding an init transfer
+            let Ok(event) = OmniBridgeEvent::from_log_string(log.log) else {
+                continue;
+            };
+            let location = EventLocation {
+                block_height: log.block_height,
+                block_timestamp: log.block_timestamp,
+                receipt_id: log.receipt_id.to_string(),
+                log_index: log.log_index,
+            };
+            if update.apply(&event, &location)? {
+                indexed += 1;
+            }
+        }
+        update.commit(block_height)?;
+
+        Ok(indexed)
+    }
+
+    pub fn last_block_height(&self) -> Result<Option<u64>, IndexerError> {
+        self.db
+            .get(LAST_BLOCK_HEIGHT_KEY)?
+            .map(|height| {
+                <[u8; 8]>::try_from(height.as_ref())
+                    .map(u64::from_be_bytes)
+                    .map_err(|_| IndexerError::Borsh)
+            })
+            .transpose()
+    }
+
+    pub fn transfer(
+        &self,
+        transfer_id: &UnifiedTransferId,
+    ) -> Result<Option<TransferRecord>, IndexerError> {
+        self.load(&transfer_key(transfer_id)?)
+    }
+
+    pub fn transfer_by_fast_transfer_id(
+        &self,
+        fast_transfer_id: &FastTransferId,
+    ) -> Result<Option<TransferRecord>, IndexerError> {
+        let Some(transfer_id) = self.db.get(fast_transfer_key(fast_transfer_id))? else {
+            return Ok(None);
+        };
+        let transfer_id: UnifiedTransferId =
+            borsh::from_slice(&transfer_id).map_err(|_| IndexerError::Borsh)?;
+
+        self.transfer(&transfer_id)
+    }
+
+    /// All legs of the transfer `transfer_id` belongs to, from the origin leg to the leg
+    /// forwarded last.
+    pub fn transfer_legs(
+        &self,
+        transfer_id: &UnifiedTransferId,
+    ) -> Result<Vec<TransferRecord>, IndexerError> {
+        let Some(mut origin) = self.transfer(transfer_id)? else {
+            return Ok(Vec::new());
+        };
+        for _ in 0..MAX_TRANSFER_LEGS {
+            let Some(parent) = origin.parent.as_ref() else {
+                break;
+            };
+            let Some(parent) = self.transfer(parent)? else {
+                break;
+            };
+            origin = parent;
+        }
+
+        let mut legs = vec![origin];
+        while legs.len() < MAX_TRANSFER_LEGS {
+            let Some(child) = legs.last().and_then(|leg| leg.child.as_ref()) else {
+                break;
+            };
+            let Some(child) = self.transfer(child)? else {
+                break;
+            };
+            legs.push(child);
+        }
+
+        Ok(legs)
+    }
+
+    /// Scans all indexed transfers.
+    pub fn transfers_with_status(
+        &self,
+        status: TransferStatus,
+    ) -> Result<Vec<TransferRecord>, IndexerError> {
+        let mut transfers = Vec::new();
+        for entry in self.db.scan_prefix(TRANSFER_PREFIX) {
+            let (_, record) = entry?;
+            let record: TransferRecord =
+                borsh::from_slice(&record).map_err(|_| IndexerError::Borsh)?;
+            if record.status == status {
+                transfers.push(record);
+            }
+        }
+
+        Ok(transfers)
+    }
+
+    fn load(&self, key: &[u8]) -> Result<Option<TransferRecord>, IndexerError> {
+        self.db
+            .get(key)?
+            .map(|record| borsh::from_slice(&record).map_err(|_| IndexerError::Borsh))
+            .transpose()
+    }
+}
+
+/// Records touched by one block, written in a single batch.
+struct BlockUpdate<'a> {
+    indexer: &'a Indexer,
+    records: HashMap<Vec<u8>, TransferRecord>,
+    fast_transfers: Vec<(FastTransferId, UnifiedTransferId)>,
+}
+
+impl<'a> BlockUpdate<'a> {
+    fn new(indexer: &'a Indexer) -> Self {
+        Self {
+            indexer,
+            records: HashMap::new(),
+            fast_transfers: Vec::new(),
+        }
+    }
+
+    fn record(
+        &mut self,
+        transfer_id: &UnifiedTransferId,
+    ) -> Result<&mut TransferRecord, IndexerError> {
+        match self.records.entry(transfer_key(transfer_id)?) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let record = self
+                    .indexer
+                    .load(entry.key())?
+                    .unwrap_or_else(|| TransferRecord::new(transfer_id.clone()));
+                Ok(entry.insert(record))
+            }
+        }
+    }
+
+    /// Returns `false` for events that are not part of a transfer lifecycle.
+    fn apply(
+        &mut self,
+        event: &OmniBridgeEvent,
+        location: &EventLocation,
+    ) -> Result<bool, IndexerError> {
+        let name = event.event_name();
+        match event {
+            OmniBridgeEvent::InitTransferEvent { transfer_message }
+            | OmniBridgeEvent::UpdateFeeEvent { transfer_message } => {
+                self.message_record(transfer_message)?
+                    .push_event(name, location);
+            }
+            OmniBridgeEvent::SignTransferEvent {
+                message_payload, ..
+            } => {
+                let record = self.record(&message_payload.transfer_id.into())?;
+                record.advance(TransferStatus::Signed);
+                record.push_event(name, location);
+            }
+            OmniBridgeEvent::FinTransferEvent {
+                transfer_message, ..
+            } => {
+                let record = self.message_record(transfer_message)?;
+                // A fast transfer already delivered the tokens, finalisation only repays the relayer
+                if record.fast_transfer.is_some()
+                    || transfer_message.get_destination_chain() == ChainKind::Near
+                {
+                    record.advance(TransferStatus::Completed);
+                } else {
+                    record.advance(TransferStatus::Pending);
+                }
+                record.push_event(name, location);
+            }
+            OmniBridgeEvent::FailedFinTransferEvent { transfer_message } => {
+                let record = self.message_record(transfer_message)?;
+                record.advance(TransferStatus::Failed);
+                record.push_event(name, location);
+            }
+            OmniBridgeEvent::ClaimFeeEvent {
+                transfer_message, ..
+            } => {
+                let record = self.message_record(transfer_message)?;
+                record.advance(TransferStatus::Completed);
+                record.push_event(name, location);
+            }
+            OmniBridgeEvent::FastTransferEvent {
+                fast_transfer,
+                new_transfer_id,
+            } => {
+                let transfer_id = fast_transfer.transfer_id.clone();
+                let record = self.record(&transfer_id)?;
+                record.fast_transfer = Some(fast_transfer.clone());
+                record.advance(TransferStatus::FastTransferred);
+                record.push_event(name, location);
+                self.fast_transfers
+                    .push((fast_transfer.id(), transfer_id.clone()));
+
+                if let Some(new_transfer_id) = new_transfer_id {
+                    self.link(&transfer_id, &(*new_transfer_id).into())?
+                        .push_event(name, location);
+                }
+            }
+            OmniBridgeEvent::UtxoTransferEvent {
+                token_id,
+                utxo_transfer_message,
+                new_transfer_id,
+                ..
+            } => {
+                let Some(chain_kind) = self.indexer.config.utxo_chain_tokens.get(token_id) else {
+                    return Ok(false);
+                };
+                let transfer_id = utxo_transfer_message.get_transfer_id(*chain_kind);
+                let record = self.record(&transfer_id)?;
+                record.utxo_transfer = Some(utxo_transfer_message.clone());
+                record.advance(TransferStatus::Completed);
+                record.push_event(name, location);
+
+                if let Some(new_transfer_id) = new_transfer_id {
+                    self.link(&transfer_id, &(*new_transfer_id).into())?
+                        .push_event(name, location);
+                }
+            }
+            OmniBridgeEvent::LogMetadataEvent { .. }
+            | OmniBridgeEvent::DeployTokenEvent { .. }
+            | OmniBridgeEvent::BindTokenEvent { .. }
+            | OmniBridgeEvent::MigrateTokenEvent { .. }
+            | OmniBridgeEvent::FailedWithdrawEvent { .. }
+            | OmniBridgeEvent::TokenReviewRequestedEvent { .. }
+            | OmniBridgeEvent::TokenReviewApprovedEvent { .. }
+            | OmniBridgeEvent::TokenReviewRejectedEvent { .. } => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    /// Record of the transfer carried by `transfer_message`, with the message updated.
+    fn message_record(
+        &mut self,
+        transfer_message: &TransferMessage,
+    ) -> Result<&mut TransferRecord, IndexerError> {
+        let transfer_id: UnifiedTransferId = transfer_message.get_transfer_id().into();
+        if let Some(origin_transfer_id) = &transfer_message.origin_transfer_id {
+            self.link(origin_transfer_id, &transfer_id)?;
+        }
+
+        let record = self.record(&transfer_id)?;
+        record.transfer_message = Some(transfer_message.clone());
+        Ok(record)
+    }
+
+    /// Links the leg `child` created on NEAR to `parent` and returns the child record.
+    fn link(
+        &mut self,
+        parent: &UnifiedTransferId,
+        child: &UnifiedTransferId,
+    ) -> Result<&mut TransferRecord, IndexerError> {
+        self.record(parent)?.child = Some(child.clone());
+
+        let record = self.record(child)?;
+        record.parent = Some(parent.clone());
+        Ok(record)
+    }
+
+    fn commit(self, block_height: u64) -> Result<(), IndexerError> {
+        let mut batch = sled::Batch::default();
+        for (key, record) in self.records {
+            batch.insert(
+                key,
+                borsh::to_vec(&record).map_err(|_| IndexerError::Borsh)?,
+            );
+        }
+        for (fast_transfer_id, transfer_id) in self.fast_transfers {
+            batch.insert(
+                fast_transfer_key(&fast_transfer_id),
+                borsh::to_vec(&transfer_id).map_err(|_| IndexerError::Borsh)?,
+            );
+        }
+        batch.insert(LAST_BLOCK_HEIGHT_KEY, block_height.to_be_bytes().to_vec());
+
+        Ok(self.indexer.db.apply_batch(batch)?)
+    }
+}
+
+fn transfer_key(transfer_id: &UnifiedTransferId) -> Result<Vec<u8>, IndexerError> {
+    let mut key = TRANSFER_PREFIX.to_vec();
+    borsh::to_writer(&mut key, transfer_id).map_err(|_| IndexerError::Borsh)?;
+    Ok(key)
+}
+
+fn fast_transfer_key(fast_transfer_id: &FastTransferId) -> Vec<u8> {
+    [FAST_TRANSFER_PREFIX, &fast_transfer_id.0].concat()
+}
@@ -0,0 +1,104 @@
+//! The subset of the NEAR Lake `StreamerMessage` format needed to find bridge logs.
+//!
+//! Unknown fields are ignored, so full NEAR Lake blocks (as produced by
+//! `near-lake-framework` or stored in the `near-lake-data-*` buckets) can be fed as is.
+
+use near_sdk::AccountId;
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StreamerMessage {
+    pub block: BlockView,
+    pub shards: Vec<IndexerShard>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BlockView {
+    pub header: BlockHeaderView,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct BlockHeaderView {
+    pub height: u64,
+    pub hash: String,
+    /// Block timestamp in nanoseconds.
+    pub timestamp: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct IndexerShard {
+    pub shard_id: u64,
+    pub receipt_execution_outcomes: Vec<ReceiptExecutionOutcome>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ReceiptExecutionOutcome {
+    pub execution_outcome: ExecutionOutcomeWithId,
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExecutionOutcomeWithId {
+    /// Receipt ID.
+    pub id: String,
+    pub outcome: ExecutionOutcome,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ExecutionOutcome {
+    pub logs: Vec<String>,
+    pub executor_id: AccountId,
+    pub status: ExecutionStatus,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum ExecutionStatus {
+    Unknown,
+    Failure(serde_json::Value),
+    SuccessValue(String),
+    SuccessReceiptId(String),
+}
+
+/// A log line written by the bridge contract, with its position in the chain.
+#[derive(Debug, Clone)]
+pub struct BridgeLog<'a> {
+    pub block_height: u64,
+    pub block_timestamp: u64,
+    pub receipt_id: &'a str,
+    pub log_index: u32,
+    pub log: &'a str,
+}
+
+impl StreamerMessage {
+    /// Logs of the successful receipts executed by `bridge_account_id`, in execution order.
+    ///
+    /// Logs of failed receipts are skipped since their state changes were reverted.
+    pub fn bridge_logs<'a>(
+        &'a self,
+        bridge_account_id: &'a AccountId,
+    ) -> impl Iterator<Item = BridgeLog<'a>> + 'a {
+        let header = &self.block.header;
+        self.shards
+            .iter()
+            .flat_map(|shard| &shard.receipt_execution_outcomes)
+            .map(|outcome| &outcome.execution_outcome)
+            .filter(move |outcome| {
+                &outcome.outcome.executor_id == bridge_account_id
+                    && !matches!(
+                        outcome.outcome.status,
+                        ExecutionStatus::Unknown | ExecutionStatus::Failure(_)
+                    )
+            })
+            .flat_map(move |outcome| {
+                (0u32..)
+                    .zip(&outcome.outcome.logs)
+                    .map(move |(log_index, log)| BridgeLog {
+                        block_height: header.height,
+                        block_timestamp: header.timestamp,
+                        receipt_id: &outcome.id,
+                        log_index,
+                        log,
+                    })
+            })
+    }
+}
@@ -0,0 +1,21 @@
+//! Off-chain indexer of the omni-bridge transfer lifecycle.
+//!
+//! Consumes NEAR Lake `StreamerMessage`s, decodes the [`OmniBridgeEvent`]s logged by the
+//! bridge contract and keeps one [`TransferRecord`] per transfer leg in an embedded sled
+//! database. Fast transfer and UTXO legs are linked to the legs they were forwarded to, so
+//! a whole cross-chain transfer can be queried at once.
+//!
+//! [`OmniBridgeEvent`]: omni_types::near_events::OmniBridgeEvent
+
+pub mod error;
+pub mod indexer;
+pub mod lake;
+pub mod record;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::IndexerError;
+pub use indexer::{Indexer, IndexerConfig};
+pub use lake::StreamerMessage;
+pub use record::{EventEntry, EventLocation, TransferRecord, TransferStatus};
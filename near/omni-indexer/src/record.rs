@@ -0,0 +1,95 @@
+//! Lifecycle of a single transfer leg, as seen from the bridge logs.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use omni_types::{FastTransfer, TransferMessage, UnifiedTransferId, UtxoFinTransferMsg};
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum TransferStatus {
+    /// Initiated on NEAR or finalised on NEAR towards another chain, waiting for the signature.
+    Pending,
+    /// Signed by the MPC network, waiting for the fee to be claimed on NEAR. Final for
+    /// transfers without a fee.
+    Signed,
+    /// Delivered on NEAR by a fast transfer relayer before the origin leg was finalised.
+    FastTransferred,
+    /// Tokens released on NEAR, or the fee was claimed after finalisation on the destination chain.
+    Completed,
+    /// Tokens could not be delivered on NEAR and were returned to the bridge.
+    Failed,
+}
+
+/// Position of an event in the chain.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EventLocation {
+    pub block_height: u64,
+    /// Block timestamp in nanoseconds.
+    pub block_timestamp: u64,
+    pub receipt_id: String,
+    pub log_index: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct EventEntry {
+    /// NEP-297 event name, e.g. `init_transfer_event`.
+    pub event: String,
+    pub location: EventLocation,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
+pub struct TransferRecord {
+    pub transfer_id: UnifiedTransferId,
+    pub status: TransferStatus,
+    /// Latest known transfer message. `None` for legs only known from a fast or UTXO
+    /// transfer event so far.
+    pub transfer_message: Option<TransferMessage>,
+    pub fast_transfer: Option<FastTransfer>,
+    pub utxo_transfer: Option<UtxoFinTransferMsg>,
+    /// Leg this transfer was created from on NEAR, e.g. the origin of a forwarded fast transfer.
+    pub parent: Option<UnifiedTransferId>,
+    /// Leg created on NEAR to forward this transfer to another chain.
+    pub child: Option<UnifiedTransferId>,
+    pub events: Vec<EventEntry>,
+}
+
+impl TransferRecord {
+    pub const fn new(transfer_id: UnifiedTransferId) -> Self {
+        Self {
+            transfer_id,
+            status: TransferStatus::Pending,
+            transfer_message: None,
+            fast_transfer: None,
+            utxo_transfer: None,
+            parent: None,
+            child: None,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn created_at(&self) -> Option<&EventLocation> {
+        self.events.first().map(|entry| &entry.location)
+    }
+
+    pub fn updated_at(&self) -> Option<&EventLocation> {
+        self.events.last().map(|entry| &entry.location)
+    }
+
+    pub(crate) fn push_event(&mut self, event: &str, location: &EventLocation) {
+        self.events.push(EventEntry {
+            event: event.to_string(),
+            location: location.clone(),
+        });
+    }
+
+    /// Moves to `status` unless the transfer already reached a final state.
+    pub(crate) fn advance(&mut self, status: TransferStatus) {
+        if !matches!(
+            self.status,
+            TransferStatus::Completed | TransferStatus::Failed
+        ) {
+            self.status = status;
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use omni_types::{ChainKind, TransferId, TransferIdKind, UnifiedTransferId};
+
+use crate::{Indexer, IndexerConfig, TransferStatus};
+
+const FIXTURE: &str = include_str!("../fixtures/near_lake_blocks.json");
+
+fn indexer() -> Indexer {
+    Indexer::temporary(IndexerConfig {
+        bridge_account_id: "omni.bridge.near".parse().unwrap(),
+        utxo_chain_tokens: HashMap::from([("nbtc.near".parse().unwrap(), ChainKind::Btc)]),
+    })
+    .unwrap()
+}
+
+fn transfer_id(origin_chain: ChainKind, origin_nonce: u64) -> UnifiedTransferId {
+    TransferId {
+        origin_chain,
+        origin_nonce,
+    }
+    .into()
+}
+
+fn event_names(indexer: &Indexer, transfer_id: &UnifiedTransferId) -> Vec<String> {
+    indexer
+        .transfer(transfer_id)
+        .unwrap()
+        .unwrap()
+        .events
+        .into_iter()
+        .map(|entry| entry.event)
+        .collect()
+}
+
+#[test]
+fn test_near_transfer_lifecycle() {
+    let indexer = indexer();
+    assert_eq!(indexer.process_reader(FIXTURE.as_bytes()).unwrap(), 8);
+    assert_eq!(indexer.last_block_height().unwrap(), Some(103));
+
+    let near_transfer = transfer_id(ChainKind::Near, 1);
+    let record = indexer.transfer(&near_transfer).unwrap().unwrap();
+    assert_eq!(record.status, TransferStatus::Completed);
+    assert_eq!(record.created_at().unwrap().block_height, 100);
+    assert_eq!(record.updated_at().unwrap().receipt_id, "ClaimFeeReceipt");
+    assert_eq!(record.transfer_message.unwrap().fee.fee.0, 10);
+    assert_eq!(
+        event_names(&indexer, &near_transfer),
+        [
+            "init_transfer_event",
+            "sign_transfer_event",
+            "claim_fee_event"
+        ]
+    );
+
+    let record = indexer
+        .transfer(&transfer_id(ChainKind::Eth, 5))
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.status, TransferStatus::Completed);
+}
+
+#[test]
+fn test_foreign_and_failed_logs_are_ignored() {
+    let indexer = indexer();
+    indexer.process_reader(FIXTURE.as_bytes()).unwrap();
+
+    // Logged by a receipt that failed
+    assert!(indexer
+        .transfer(&transfer_id(ChainKind::Near, 99))
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        indexer
+            .transfers_with_status(TransferStatus::Failed)
+            .unwrap()
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_fast_transfer_legs_are_joined() {
+    let indexer = indexer();
+    indexer.process_reader(FIXTURE.as_bytes()).unwrap();
+
+    let origin = transfer_id(ChainKind::Eth, 7);
+    let forwarded = transfer_id(ChainKind::Near, 2);
+
+    let legs = indexer.transfer_legs(&forwarded).unwrap();
+    assert_eq!(
+        legs.iter()
+            .map(|leg| leg.transfer_id.clone())
+            .collect::<Vec<_>>(),
+        [origin.clone(), forwarded.clone()]
+    );
+    assert_eq!(legs[0].status, TransferStatus::Completed);
+    assert_eq!(legs[0].child, Some(forwarded.clone()));
+    assert_eq!(legs[1].status, TransferStatus::Signed);
+    assert_eq!(legs[1].parent, Some(origin.clone()));
+    assert_eq!(
+        event_names(&indexer, &origin),
+        ["fast_transfer_event", "fin_transfer_event"]
+    );
+
+    let fast_transfer_id = legs[0].fast_transfer.as_ref().unwrap().id();
+    let record = indexer
+        .transfer_by_fast_transfer_id(&fast_transfer_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(record.transfer_id, origin);
+
+    let signed = indexer
+        .transfers_with_status(TransferStatus::Signed)
+        .unwrap();
+    assert_eq!(signed.len(), 1);
+    assert_eq!(signed[0].transfer_id, forwarded);
+}
+
+#[test]
+fn test_utxo_transfer_is_keyed_by_utxo_id() {
+    let indexer = indexer();
+    indexer.process_reader(FIXTURE.as_bytes()).unwrap();
+
+    let utxo_transfer = UnifiedTransferId {
+        origin_chain: ChainKind::Btc,
+        kind: TransferIdKind::Utxo(
+            "5d1f2a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f7@0"
+                .parse()
+                .unwrap(),
+        ),
+    };
+    let record = indexer.transfer(&utxo_transfer).unwrap().unwrap();
+    assert_eq!(record.status, TransferStatus::Completed);
+    assert_eq!(record.utxo_transfer.unwrap().relayer_fee.0, 30);
+}
+
+#[test]
+fn test_replayed_blocks_are_skipped() {
+    let indexer = indexer();
+    indexer.process_reader(FIXTURE.as_bytes()).unwrap();
+    assert_eq!(indexer.process_reader(FIXTURE.as_bytes()).unwrap(), 0);
+
+    assert_eq!(
+        event_names(&indexer, &transfer_id(ChainKind::Near, 1)).len(),
+        3
+    );
+}
+
+#[test]
+fn test_invalid_input_is_rejected() {
+    let indexer = indexer();
+    assert!(matches!(
+        indexer.process_reader("{\"block\":".as_bytes()),
+        Err(crate::IndexerError::InvalidInput(_))
+    ));
+}
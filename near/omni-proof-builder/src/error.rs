@@ -0,0 +1,16 @@
+use omni_utils::ErrorDisplay;
+use strum_macros::AsRefStr;
+
+#[derive(Debug, Clone, PartialEq, Eq, AsRefStr, ErrorDisplay)]
+#[strum(serialize_all = "shouty_snake_case", prefix = "ERR_")]
+#[non_exhaustive]
+pub enum ProofBuilderError {
+    BlockHashMismatch,
+    Borsh,
+    InvalidRpcData,
+    LogNotFound,
+    ReceiptNotFound,
+    ReceiptsRootMismatch,
+    UnsupportedChain,
+    UnsupportedReceipt,
+}
@@ -0,0 +1,215 @@
+//! Construction of [`EvmProof`]s from raw `eth_getBlockByHash` / `eth_getBlockReceipts`
+//! JSON-RPC responses.
+
+use ethereum_types::{Address, Bloom, H256, H64, U256, U64};
+use omni_types::evm::header::BlockHeader;
+use omni_types::evm::receipt::LogEntry;
+use omni_types::prover_args::{EvmProof, EvmVerifyProofArgs};
+use omni_types::prover_result::ProofKind;
+use omni_types::utils::keccak256;
+use rlp::RlpStream;
+use serde::Deserialize;
+
+use crate::error::ProofBuilderError;
+use crate::trie::Trie;
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlock {
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub sha3_uncles: H256,
+    pub miner: Address,
+    pub state_root: H256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+    pub logs_bloom: Bloom,
+    pub difficulty: U256,
+    pub number: U64,
+    pub gas_limit: U256,
+    pub gas_used: U256,
+    pub timestamp: U64,
+    #[serde(with = "hex_bytes")]
+    pub extra_data: Vec<u8>,
+    pub mix_hash: H256,
+    pub nonce: H64,
+    pub base_fee_per_gas: Option<U64>,
+    pub withdrawals_root: Option<H256>,
+    pub blob_gas_used: Option<U64>,
+    pub excess_blob_gas: Option<U64>,
+    pub parent_beacon_block_root: Option<H256>,
+    pub requests_hash: Option<H256>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcReceipt {
+    #[serde(rename = "type")]
+    pub tx_type: Option<U64>,
+    pub status: Option<U64>,
+    pub transaction_hash: H256,
+    pub transaction_index: U64,
+    pub cumulative_gas_used: U256,
+    pub logs_bloom: Bloom,
+    pub logs: Vec<RpcLog>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcLog {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    #[serde(with = "hex_bytes")]
+    pub data: Vec<u8>,
+    pub block_hash: H256,
+    pub block_number: U64,
+    pub transaction_hash: H256,
+    pub transaction_index: U64,
+    pub log_index: U64,
+    #[serde(default)]
+    pub removed: bool,
+}
+
+impl RpcBlock {
+    pub fn header(&self) -> BlockHeader {
+        BlockHeader {
+            parent_hash: self.parent_hash,
+            sha3_uncles: self.sha3_uncles,
+            miner: self.miner,
+            state_root: self.state_root,
+            transactions_root: self.transactions_root,
+            receipts_root: self.receipts_root,
+            logs_bloom: self.logs_bloom,
+            difficulty: self.difficulty,
+            number: self.number,
+            gas_limit: self.gas_limit,
+            gas_used: self.gas_used,
+            timestamp: self.timestamp,
+            extra_data: self.extra_data.clone(),
+            mix_hash: self.mix_hash,
+            nonce: self.nonce,
+            base_fee_per_gas: self.base_fee_per_gas,
+            withdrawals_root: self.withdrawals_root,
+            blob_gas_used: self.blob_gas_used,
+            excess_blob_gas: self.excess_blob_gas,
+            parent_beacon_block_root: self.parent_beacon_block_root,
+            requests_hash: self.requests_hash,
+            hash: Some(self.hash),
+        }
+    }
+
+    /// RLP encoded header, checked against the block hash reported by the node so that an
+    /// unknown or missing header field is caught here rather than by the prover.
+    pub fn header_rlp(&self) -> Result<Vec<u8>, ProofBuilderError> {
+        let header_data = rlp::encode(&self.header()).to_vec();
+        if keccak256(&header_data) != self.hash.0 {
+            return Err(ProofBuilderError::BlockHashMismatch);
+        }
+
+        Ok(header_data)
+    }
+}
+
+impl RpcReceipt {
+    /// Consensus encoding of the receipt as stored in the receipts trie
+    /// (<https://eips.ethereum.org/EIPS/eip-2718#receipts>).
+    pub fn encode(&self) -> Result<Vec<u8>, ProofBuilderError> {
+        // Pre-Byzantium receipts carry an intermediate state root instead of a status.
+        let status = self.status.ok_or(ProofBuilderError::UnsupportedReceipt)?;
+
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&!status.is_zero());
+        stream.append(&self.cumulative_gas_used);
+        stream.append(&self.logs_bloom);
+        stream.begin_list(self.logs.len());
+        for log in &self.logs {
+            stream.append(&log.to_log_entry());
+        }
+
+        let tx_type = self.tx_type.unwrap_or_default();
+        if tx_type.is_zero() {
+            return Ok(stream.out().to_vec());
+        }
+
+        let tx_type =
+            u8::try_from(tx_type.as_u64()).map_err(|_| ProofBuilderError::UnsupportedReceipt)?;
+        let mut data = vec![tx_type];
+        data.extend_from_slice(&stream.out());
+        Ok(data)
+    }
+}
+
+impl RpcLog {
+    pub fn to_log_entry(&self) -> LogEntry {
+        LogEntry {
+            address: self.address,
+            topics: self.topics.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+/// Builds the receipts trie of a block and returns the proof for the receipt of
+/// `transaction_hash`. `log_index` is the position of the log inside that receipt.
+///
+/// `receipts` must contain every receipt of the block, in any order.
+pub fn build_evm_proof(
+    block: &RpcBlock,
+    receipts: &[RpcReceipt],
+    transaction_hash: H256,
+    log_index: u64,
+) -> Result<EvmProof, ProofBuilderError> {
+    let receipt = receipts
+        .iter()
+        .find(|receipt| receipt.transaction_hash == transaction_hash)
+        .ok_or(ProofBuilderError::ReceiptNotFound)?;
+    let log = receipt
+        .logs
+        .get(usize::try_from(log_index).map_err(|_| ProofBuilderError::LogNotFound)?)
+        .ok_or(ProofBuilderError::LogNotFound)?;
+
+    let mut trie = Trie::default();
+    for receipt in receipts {
+        trie.insert(
+            &rlp::encode(&receipt.transaction_index.as_u64()),
+            receipt.encode()?,
+        );
+    }
+
+    if trie.root_hash() != block.receipts_root.0 {
+        return Err(ProofBuilderError::ReceiptsRootMismatch);
+    }
+
+    let receipt_index = receipt.transaction_index.as_u64();
+
+    Ok(EvmProof {
+        log_index,
+        log_entry_data: rlp::encode(&log.to_log_entry()).to_vec(),
+        receipt_index,
+        receipt_data: receipt.encode()?,
+        header_data: block.header_rlp()?,
+        proof: trie.proof(&rlp::encode(&receipt_index)),
+    })
+}
+
+pub fn build_evm_verify_proof_args(
+    proof_kind: ProofKind,
+    block: &RpcBlock,
+    receipts: &[RpcReceipt],
+    transaction_hash: H256,
+    log_index: u64,
+) -> Result<EvmVerifyProofArgs, ProofBuilderError> {
+    Ok(EvmVerifyProofArgs {
+        proof_kind,
+        proof: build_evm_proof(block, receipts, transaction_hash, log_index)?,
+    })
+}
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s.strip_prefix("0x").unwrap_or(&s)).map_err(serde::de::Error::custom)
+    }
+}
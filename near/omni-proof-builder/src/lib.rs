@@ -0,0 +1,21 @@
+//! Off-chain construction of the proof arguments accepted by the omni provers.
+//!
+//! The inputs are plain JSON-RPC responses / VAA bytes, so relayers can fetch the data with
+//! any client and tests can run from fixtures.
+
+pub mod error;
+pub mod evm;
+pub mod mpc;
+mod trie;
+pub mod wormhole;
+
+#[cfg(test)]
+mod tests;
+
+pub use error::ProofBuilderError;
+pub use evm::{build_evm_proof, build_evm_verify_proof_args, RpcBlock, RpcLog, RpcReceipt};
+pub use mpc::{
+    build_evm_logs_sign_payload, build_evm_sign_payload, build_mpc_verify_proof_args,
+    build_sign_payload,
+};
+pub use wormhole::{build_wormhole_verify_proof_args, encode_vaa, GuardianSignature, VaaBody};
@@ -0,0 +1,108 @@
+//! Construction of `ForeignTxSignPayload`s and [`MpcVerifyProofArgs`] for `mpc-omni-prover`.
+
+use ethereum_types::H256;
+use near_mpc_sdk::near_mpc_contract_interface::types::{
+    EvmExtractedValue, EvmExtractor, EvmFinality, EvmLog, EvmRpcRequest, EvmTxId, ExtractedValue,
+    ForeignChainRpcRequest, ForeignTxSignPayload, ForeignTxSignPayloadV1, Hash160, Hash256,
+};
+use omni_types::prover_args::MpcVerifyProofArgs;
+use omni_types::prover_result::ProofKind;
+use omni_types::ChainKind;
+
+use crate::error::ProofBuilderError;
+use crate::evm::{RpcLog, RpcReceipt};
+
+pub fn build_sign_payload(
+    request: ForeignChainRpcRequest,
+    values: Vec<ExtractedValue>,
+) -> ForeignTxSignPayload {
+    ForeignTxSignPayload::V1(ForeignTxSignPayloadV1 { request, values })
+}
+
+/// `value_index` selects which of the signed payload's extracted values the prover parses.
+pub fn build_mpc_verify_proof_args(
+    proof_kind: ProofKind,
+    sign_payload: &ForeignTxSignPayload,
+    value_index: u32,
+) -> Result<MpcVerifyProofArgs, ProofBuilderError> {
+    Ok(MpcVerifyProofArgs {
+        proof_kind,
+        sign_payload: borsh::to_vec(sign_payload).map_err(|_| ProofBuilderError::Borsh)?,
+        value_index,
+    })
+}
+
+/// Only Ethereum and Abstract have an EVM request variant that `mpc-omni-prover` accepts.
+pub fn evm_rpc_request(
+    chain_kind: ChainKind,
+    request: EvmRpcRequest,
+) -> Result<ForeignChainRpcRequest, ProofBuilderError> {
+    match chain_kind {
+        ChainKind::Eth => Ok(ForeignChainRpcRequest::Ethereum(request)),
+        ChainKind::Abs => Ok(ForeignChainRpcRequest::Abstract(request)),
+        _ => Err(ProofBuilderError::UnsupportedChain),
+    }
+}
+
+pub fn evm_log_value(log: &RpcLog) -> ExtractedValue {
+    ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(EvmLog {
+        removed: log.removed,
+        log_index: log.log_index.as_u64(),
+        transaction_index: log.transaction_index.as_u64(),
+        transaction_hash: to_hash256(log.transaction_hash),
+        block_hash: to_hash256(log.block_hash),
+        block_number: log.block_number.as_u64(),
+        address: Hash160(log.address.0),
+        data: format!("0x{}", hex::encode(&log.data)),
+        topics: log.topics.iter().copied().map(to_hash256).collect(),
+    }))
+}
+
+/// Builds the payload the MPC network signs for a single EVM log. `log_index` is the
+/// position of the log inside `receipt`, the same index used by [`crate::evm::build_evm_proof`].
+pub fn build_evm_sign_payload(
+    chain_kind: ChainKind,
+    finality: EvmFinality,
+    receipt: &RpcReceipt,
+    log_index: u64,
+) -> Result<ForeignTxSignPayload, ProofBuilderError> {
+    build_evm_logs_sign_payload(chain_kind, finality, receipt, &[log_index])
+}
+
+/// Same as [`build_evm_sign_payload`] for several logs of one transaction, so all of them are
+/// proven by a single MPC call. The value index of a log is its position in `log_indices`.
+pub fn build_evm_logs_sign_payload(
+    chain_kind: ChainKind,
+    finality: EvmFinality,
+    receipt: &RpcReceipt,
+    log_indices: &[u64],
+) -> Result<ForeignTxSignPayload, ProofBuilderError> {
+    let values = log_indices
+        .iter()
+        .map(|&log_index| {
+            usize::try_from(log_index)
+                .ok()
+                .and_then(|index| receipt.logs.get(index))
+                .map(evm_log_value)
+                .ok_or(ProofBuilderError::LogNotFound)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let request = evm_rpc_request(
+        chain_kind,
+        EvmRpcRequest {
+            tx_id: EvmTxId(receipt.transaction_hash.0),
+            extractors: log_indices
+                .iter()
+                .map(|&log_index| EvmExtractor::Log { log_index })
+                .collect(),
+            finality,
+        },
+    )?;
+
+    Ok(build_sign_payload(request, values))
+}
+
+const fn to_hash256(hash: H256) -> Hash256 {
+    Hash256(hash.0)
+}
@@ -0,0 +1,339 @@
+use borsh::BorshDeserialize;
+use ethereum_types::H256;
+use evm_prover::EvmProver;
+use near_mpc_sdk::near_mpc_contract_interface::types::{
+    EvmExtractedValue, EvmFinality, ExtractedValue, ForeignChainRpcRequest, ForeignTxSignPayload,
+};
+use omni_types::errors::ProverError;
+use omni_types::evm::events::parse_evm_proof;
+use omni_types::prover_args::{EvmVerifyProofArgs, MpcVerifyProofArgs, WormholeVerifyProofArgs};
+use omni_types::prover_result::{InitTransferMessage, ProofKind, ProverResult};
+use omni_types::utils::keccak256;
+use omni_types::{ChainKind, Nonce, OmniAddress};
+use wormhole_omni_prover_proxy::parsed_vaa::ParsedVAA;
+
+use crate::evm::{build_evm_proof, build_evm_verify_proof_args, RpcBlock, RpcReceipt};
+use crate::mpc::{
+    build_evm_logs_sign_payload, build_evm_sign_payload, build_mpc_verify_proof_args,
+};
+use crate::wormhole::{build_wormhole_verify_proof_args, encode_vaa, GuardianSignature, VaaBody};
+use crate::ProofBuilderError;
+
+const BRIDGE_TX_INDEX: usize = 17;
+const INIT_TRANSFER_LOG_INDEX: u64 = 1;
+
+fn block() -> RpcBlock {
+    serde_json::from_str(include_str!("../fixtures/eth_block.json")).unwrap()
+}
+
+fn receipts() -> Vec<RpcReceipt> {
+    serde_json::from_str(include_str!("../fixtures/eth_block_receipts.json")).unwrap()
+}
+
+fn bridge_tx_hash() -> H256 {
+    receipts()[BRIDGE_TX_INDEX].transaction_hash
+}
+
+fn assert_init_transfer(result: &ProverResult) {
+    let ProverResult::InitTransfer(message) = result else {
+        panic!("expected InitTransfer");
+    };
+    assert_eq!(message.amount.0, 1);
+    assert_eq!(message.recipient.to_string(), "near:frolik.testnet");
+    assert_eq!(
+        message.emitter_address,
+        "eth:0x1a7eba78b12f2a82d812f25155e6c7fc2ab1ed32"
+            .parse()
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_evm_proof_accepted_by_evm_prover() {
+    let block = block();
+    let receipts = receipts();
+
+    for receipt in &receipts {
+        for log_index in 0..receipt.logs.len() {
+            let proof = build_evm_proof(
+                &block,
+                &receipts,
+                receipt.transaction_hash,
+                log_index.try_into().unwrap(),
+            )
+            .unwrap();
+
+            let header = EvmProver::verify_proof_data(&proof).unwrap();
+            assert_eq!(header.hash, Some(block.hash));
+            assert_eq!(header.number, block.number);
+        }
+    }
+}
+
+#[test]
+fn test_evm_verify_proof_args_roundtrip() {
+    let args = build_evm_verify_proof_args(
+        ProofKind::InitTransfer,
+        &block(),
+        &receipts(),
+        bridge_tx_hash(),
+        INIT_TRANSFER_LOG_INDEX,
+    )
+    .unwrap();
+
+    let decoded = EvmVerifyProofArgs::try_from_slice(&borsh::to_vec(&args).unwrap()).unwrap();
+    assert_eq!(decoded.proof_kind, ProofKind::InitTransfer);
+    EvmProver::verify_proof_data(&decoded.proof).unwrap();
+
+    let result = parse_evm_proof(
+        decoded.proof_kind,
+        ChainKind::Eth,
+        decoded.proof.log_entry_data,
+    )
+    .unwrap();
+    assert_init_transfer(&result);
+}
+
+#[test]
+fn test_evm_proof_receipt_order_does_not_matter() {
+    let mut receipts = receipts();
+    receipts.reverse();
+
+    let proof = build_evm_proof(
+        &block(),
+        &receipts,
+        bridge_tx_hash(),
+        INIT_TRANSFER_LOG_INDEX,
+    )
+    .unwrap();
+    EvmProver::verify_proof_data(&proof).unwrap();
+}
+
+#[test]
+fn test_evm_proof_missing_receipt() {
+    let receipts = receipts();
+    assert_eq!(
+        build_evm_proof(&block(), &receipts[1..], bridge_tx_hash(), 0).unwrap_err(),
+        ProofBuilderError::ReceiptsRootMismatch
+    );
+    assert_eq!(
+        build_evm_proof(&block(), &receipts, H256::zero(), 0).unwrap_err(),
+        ProofBuilderError::ReceiptNotFound
+    );
+    assert_eq!(
+        build_evm_proof(&block(), &receipts, bridge_tx_hash(), 2).unwrap_err(),
+        ProofBuilderError::LogNotFound
+    );
+}
+
+#[test]
+fn test_evm_proof_wrong_block_hash() {
+    let mut block = block();
+    block.hash = H256::zero();
+    assert_eq!(
+        build_evm_proof(&block, &receipts(), bridge_tx_hash(), 0).unwrap_err(),
+        ProofBuilderError::BlockHashMismatch
+    );
+}
+
+#[test]
+#[should_panic(expected = "assertion failed")]
+fn test_tampered_evm_proof_rejected() {
+    let mut proof = build_evm_proof(
+        &block(),
+        &receipts(),
+        bridge_tx_hash(),
+        INIT_TRANSFER_LOG_INDEX,
+    )
+    .unwrap();
+    proof.proof[1][5] ^= 1;
+
+    let _ = EvmProver::verify_proof_data(&proof);
+}
+
+#[test]
+fn test_mpc_sign_payload_matches_evm_proof() {
+    let receipt = &receipts()[BRIDGE_TX_INDEX];
+    let sign_payload = build_evm_sign_payload(
+        ChainKind::Eth,
+        EvmFinality::Finalized,
+        receipt,
+        INIT_TRANSFER_LOG_INDEX,
+    )
+    .unwrap();
+
+    let args = build_mpc_verify_proof_args(ProofKind::InitTransfer, &sign_payload, 0).unwrap();
+    let args = MpcVerifyProofArgs::try_from_slice(&borsh::to_vec(&args).unwrap()).unwrap();
+    let ForeignTxSignPayload::V1(payload) =
+        ForeignTxSignPayload::try_from_slice(&args.sign_payload).unwrap();
+
+    let ForeignChainRpcRequest::Ethereum(request) = &payload.request else {
+        panic!("expected Ethereum request");
+    };
+    assert_eq!(request.tx_id.0, receipt.transaction_hash.0);
+    assert_eq!(request.finality, EvmFinality::Finalized);
+    assert_eq!(
+        sign_payload.compute_msg_hash().unwrap().0,
+        ForeignTxSignPayload::V1(payload.clone())
+            .compute_msg_hash()
+            .unwrap()
+            .0
+    );
+
+    let [ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(log))] =
+        payload.values.as_slice()
+    else {
+        panic!("expected a single EVM log");
+    };
+    let evm_proof = build_evm_proof(
+        &block(),
+        &receipts(),
+        receipt.transaction_hash,
+        INIT_TRANSFER_LOG_INDEX,
+    )
+    .unwrap();
+    assert_eq!(log.address.0, receipt.logs[1].address.0);
+    assert_eq!(log.block_hash.0, block().hash.0);
+
+    let result = parse_evm_proof(
+        ProofKind::InitTransfer,
+        ChainKind::Eth,
+        evm_proof.log_entry_data,
+    )
+    .unwrap();
+    assert_init_transfer(&result);
+}
+
+#[test]
+fn test_mpc_multi_log_sign_payload() {
+    let receipt = &receipts()[BRIDGE_TX_INDEX];
+    let sign_payload = build_evm_logs_sign_payload(
+        ChainKind::Eth,
+        EvmFinality::Finalized,
+        receipt,
+        &[0, INIT_TRANSFER_LOG_INDEX],
+    )
+    .unwrap();
+
+    let args = build_mpc_verify_proof_args(ProofKind::InitTransfer, &sign_payload, 1).unwrap();
+    let args = MpcVerifyProofArgs::parse(&borsh::to_vec(&args).unwrap()).unwrap();
+    assert_eq!(args.value_index, 1);
+
+    let ForeignTxSignPayload::V1(payload) =
+        ForeignTxSignPayload::try_from_slice(&args.sign_payload).unwrap();
+    let ForeignChainRpcRequest::Ethereum(request) = &payload.request else {
+        panic!("expected Ethereum request");
+    };
+    assert_eq!(request.extractors.len(), 2);
+    assert_eq!(payload.values.len(), 2);
+
+    let ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(log)) = &payload.values[1] else {
+        panic!("expected an EVM log");
+    };
+    assert_eq!(log.log_index, receipt.logs[1].log_index.as_u64());
+
+    assert_eq!(
+        build_evm_logs_sign_payload(ChainKind::Eth, EvmFinality::Finalized, receipt, &[0, 2])
+            .unwrap_err(),
+        ProofBuilderError::LogNotFound
+    );
+}
+
+#[test]
+fn test_mpc_unsupported_chain() {
+    assert_eq!(
+        build_evm_sign_payload(
+            ChainKind::Near,
+            EvmFinality::Finalized,
+            &receipts()[BRIDGE_TX_INDEX],
+            0,
+        )
+        .unwrap_err(),
+        ProofBuilderError::UnsupportedChain
+    );
+}
+
+#[test]
+fn test_wormhole_vaa_parsed_by_proxy() {
+    let token_address: OmniAddress = "sol:11111111111111111111111111111111".parse().unwrap();
+    let sender: OmniAddress = "sol:So11111111111111111111111111111111111111112"
+        .parse()
+        .unwrap();
+    let origin_nonce: Nonce = 42;
+    let payload = borsh::to_vec(&(
+        ProofKind::InitTransfer,
+        sender,
+        token_address,
+        origin_nonce,
+        1_000_u128,
+        10_u128,
+        5_u128,
+        "near:frolik.testnet".to_string(),
+        String::new(),
+    ))
+    .unwrap();
+
+    let body = VaaBody {
+        timestamp: 1_700_000_000,
+        nonce: 0,
+        emitter_chain: 1,
+        emitter_address: [7; 32],
+        sequence: 99,
+        consistency_level: 1,
+        payload,
+    };
+    let signatures = vec![
+        GuardianSignature {
+            guardian_index: 0,
+            signature: [1; 65],
+        },
+        GuardianSignature {
+            guardian_index: 3,
+            signature: [2; 65],
+        },
+    ];
+    let vaa = encode_vaa(4, &signatures, &body);
+
+    let args = build_wormhole_verify_proof_args(ProofKind::InitTransfer, &vaa);
+    let args = WormholeVerifyProofArgs::try_from_slice(&borsh::to_vec(&args).unwrap()).unwrap();
+
+    let parsed = ParsedVAA::parse(&hex::decode(args.vaa).unwrap()).unwrap();
+    assert_eq!(parsed.guardian_set_index, 4);
+    assert_eq!(parsed.len_signers, 2);
+    assert_eq!(parsed.emitter_chain, 1);
+    assert_eq!(parsed.emitter_address, vec![7; 32]);
+    assert_eq!(parsed.sequence, 99);
+    assert_eq!(parsed.hash, keccak256(&body.encode()).to_vec());
+
+    let message: InitTransferMessage = parsed.try_into().unwrap();
+    assert_eq!(message.origin_nonce, origin_nonce);
+    assert_eq!(message.amount.0, 1_000);
+    assert_eq!(message.fee.native_fee.0, 5);
+    assert_eq!(message.recipient.to_string(), "near:frolik.testnet");
+}
+
+#[test]
+fn test_truncated_vaa_rejected_by_proxy() {
+    let body = VaaBody {
+        timestamp: 0,
+        nonce: 0,
+        emitter_chain: 1,
+        emitter_address: [0; 32],
+        sequence: 0,
+        consistency_level: 1,
+        payload: vec![],
+    };
+    let signatures = vec![GuardianSignature {
+        guardian_index: 0,
+        signature: [0; 65],
+    }];
+    let vaa = encode_vaa(0, &signatures, &body);
+
+    assert!(ParsedVAA::parse(&vaa).is_ok());
+    for len in [0, 5, 6 + 66, vaa.len() - 1] {
+        assert_eq!(
+            ParsedVAA::parse(&vaa[..len]).err(),
+            Some(ProverError::InvalidVaa)
+        );
+    }
+}
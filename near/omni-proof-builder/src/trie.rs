@@ -0,0 +1,281 @@
+//! Minimal in-memory Merkle Patricia Trie used to rebuild a block's receipts trie and
+//! extract inclusion proofs in the format expected by `evm-prover`.
+
+use omni_types::utils::keccak256;
+use rlp::RlpStream;
+
+#[derive(Default, Debug, Clone)]
+enum Node {
+    #[default]
+    Empty,
+    Leaf {
+        path: Vec<u8>,
+        value: Vec<u8>,
+    },
+    Extension {
+        path: Vec<u8>,
+        child: Box<Node>,
+    },
+    Branch {
+        children: Box<[Node; 16]>,
+        value: Option<Vec<u8>>,
+    },
+}
+
+impl Node {
+    fn new_branch() -> Self {
+        Self::Branch {
+            children: Box::default(),
+            value: None,
+        }
+    }
+
+    fn with_prefix(prefix: &[u8], node: Self) -> Self {
+        if prefix.is_empty() {
+            node
+        } else {
+            Self::Extension {
+                path: prefix.to_vec(),
+                child: Box::new(node),
+            }
+        }
+    }
+
+    fn insert(self, path: &[u8], value: Vec<u8>) -> Self {
+        match self {
+            Self::Empty => Self::Leaf {
+                path: path.to_vec(),
+                value,
+            },
+            Self::Leaf {
+                path: leaf_path,
+                value: leaf_value,
+            } => {
+                if leaf_path == path {
+                    return Self::Leaf {
+                        path: leaf_path,
+                        value,
+                    };
+                }
+
+                let common = common_prefix_len(&leaf_path, path);
+                let branch = Self::new_branch()
+                    .insert(&leaf_path[common..], leaf_value)
+                    .insert(&path[common..], value);
+
+                Self::with_prefix(&path[..common], branch)
+            }
+            Self::Extension {
+                path: ext_path,
+                child,
+            } => {
+                let common = common_prefix_len(&ext_path, path);
+                if common == ext_path.len() {
+                    return Self::Extension {
+                        child: Box::new(child.insert(&path[common..], value)),
+                        path: ext_path,
+                    };
+                }
+
+                let mut branch = Self::new_branch();
+                if let Self::Branch { children, .. } = &mut branch {
+                    children[usize::from(ext_path[common])] =
+                        Self::with_prefix(&ext_path[common + 1..], *child);
+                }
+                let branch = branch.insert(&path[common..], value);
+
+                Self::with_prefix(&path[..common], branch)
+            }
+            Self::Branch {
+                mut children,
+                value: branch_value,
+            } => {
+                let Some((&nibble, rest)) = path.split_first() else {
+                    return Self::Branch {
+                        children,
+                        value: Some(value),
+                    };
+                };
+
+                let index = usize::from(nibble);
+                children[index] = std::mem::take(&mut children[index]).insert(rest, value);
+
+                Self::Branch {
+                    children,
+                    value: branch_value,
+                }
+            }
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        match self {
+            Self::Empty => {
+                stream.append_empty_data();
+            }
+            Self::Leaf { path, value } => {
+                stream.begin_list(2);
+                stream.append(&compact_path(path, true));
+                stream.append(value);
+            }
+            Self::Extension { path, child } => {
+                stream.begin_list(2);
+                stream.append(&compact_path(path, false));
+                child.append_reference(&mut stream);
+            }
+            Self::Branch { children, value } => {
+                stream.begin_list(17);
+                for child in children.iter() {
+                    child.append_reference(&mut stream);
+                }
+                match value {
+                    Some(value) => stream.append(value),
+                    None => stream.append_empty_data(),
+                };
+            }
+        }
+        stream.out().to_vec()
+    }
+
+    /// Child nodes shorter than 32 bytes are embedded into their parent, all others are
+    /// referenced by hash.
+    fn append_reference(&self, stream: &mut RlpStream) {
+        if matches!(self, Self::Empty) {
+            stream.append_empty_data();
+            return;
+        }
+
+        let encoded = self.encode();
+        if encoded.len() < 32 {
+            stream.append_raw(&encoded, 1);
+        } else {
+            stream.append(&keccak256(&encoded).to_vec());
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct Trie {
+    root: Node,
+}
+
+impl Trie {
+    pub fn insert(&mut self, key: &[u8], value: Vec<u8>) {
+        let root = std::mem::take(&mut self.root);
+        self.root = root.insert(&to_nibbles(key), value);
+    }
+
+    pub fn root_hash(&self) -> [u8; 32] {
+        keccak256(&self.root.encode())
+    }
+
+    /// Returns the RLP encoded nodes on the path from the root to `key`, root first.
+    pub fn proof(&self, key: &[u8]) -> Vec<Vec<u8>> {
+        let path = to_nibbles(key);
+        let mut rest = path.as_slice();
+        let mut node = &self.root;
+        let mut proof = Vec::new();
+
+        loop {
+            match node {
+                Node::Empty => break,
+                Node::Leaf { .. } => {
+                    proof.push(node.encode());
+                    break;
+                }
+                Node::Extension { path, child } => {
+                    proof.push(node.encode());
+                    let Some(remaining) = rest.strip_prefix(path.as_slice()) else {
+                        break;
+                    };
+                    rest = remaining;
+                    node = child;
+                }
+                Node::Branch { children, .. } => {
+                    proof.push(node.encode());
+                    let Some((&nibble, remaining)) = rest.split_first() else {
+                        break;
+                    };
+                    rest = remaining;
+                    node = &children[usize::from(nibble)];
+                }
+            }
+        }
+
+        proof
+    }
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter()
+        .flat_map(|byte| [byte >> 4, byte & 0x0f])
+        .collect()
+}
+
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+/// Hex-prefix encoding of a nibble path: <https://ethereum.org/developers/docs/data-structures-and-encoding/patricia-merkle-trie/#specification>
+fn compact_path(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 2 } else { 0 };
+    let mut encoded = Vec::with_capacity(nibbles.len() / 2 + 1);
+
+    let rest = if nibbles.len() % 2 == 1 {
+        encoded.push(((flag + 1) << 4) | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        encoded.push(flag << 4);
+        nibbles
+    };
+
+    encoded.extend(rest.chunks(2).map(|pair| (pair[0] << 4) | pair[1]));
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_trie_root() {
+        // keccak256(rlp(""))
+        assert_eq!(
+            hex::encode(Trie::default().root_hash()),
+            "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        );
+    }
+
+    #[test]
+    fn reference_vector_root() {
+        let mut trie = Trie::default();
+        trie.insert(b"do", b"verb".to_vec());
+        trie.insert(b"dog", b"puppy".to_vec());
+        trie.insert(b"doge", b"coin".to_vec());
+        trie.insert(b"horse", b"stallion".to_vec());
+
+        // Reference root from the Ethereum trie test vectors ("dogs").
+        assert_eq!(
+            hex::encode(trie.root_hash()),
+            "5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84"
+        );
+    }
+
+    #[test]
+    fn compact_path_encoding() {
+        assert_eq!(
+            compact_path(&[1, 2, 3, 4, 5], false),
+            vec![0x11, 0x23, 0x45]
+        );
+        assert_eq!(
+            compact_path(&[0, 1, 2, 3, 4, 5], false),
+            vec![0x00, 0x01, 0x23, 0x45]
+        );
+        assert_eq!(
+            compact_path(&[0x0f, 1, 0x0c, 0x0b, 8], true),
+            vec![0x3f, 0x1c, 0xb8]
+        );
+        assert_eq!(compact_path(&[0x0f], true), vec![0x3f]);
+    }
+}
@@ -0,0 +1,73 @@
+//! Encoding of Wormhole VAAs and [`WormholeVerifyProofArgs`] for `wormhole-omni-prover-proxy`.
+
+use omni_types::prover_args::WormholeVerifyProofArgs;
+use omni_types::prover_result::ProofKind;
+use omni_types::utils::keccak256;
+
+pub const VAA_VERSION: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaaBody {
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub consistency_level: u8,
+    pub payload: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardianSignature {
+    pub guardian_index: u8,
+    /// `r || s || v` with `v` in `0..=1`.
+    pub signature: [u8; 65],
+}
+
+impl VaaBody {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut data = Vec::with_capacity(51 + self.payload.len());
+        data.extend_from_slice(&self.timestamp.to_be_bytes());
+        data.extend_from_slice(&self.nonce.to_be_bytes());
+        data.extend_from_slice(&self.emitter_chain.to_be_bytes());
+        data.extend_from_slice(&self.emitter_address);
+        data.extend_from_slice(&self.sequence.to_be_bytes());
+        data.push(self.consistency_level);
+        data.extend_from_slice(&self.payload);
+        data
+    }
+
+    /// The digest guardians sign: `keccak256(keccak256(body))`.
+    pub fn digest(&self) -> [u8; 32] {
+        keccak256(&keccak256(&self.encode()))
+    }
+}
+
+/// # Panics
+///
+/// Panics if more than 255 signatures are given.
+pub fn encode_vaa(
+    guardian_set_index: u32,
+    signatures: &[GuardianSignature],
+    body: &VaaBody,
+) -> Vec<u8> {
+    let mut data = vec![VAA_VERSION];
+    data.extend_from_slice(&guardian_set_index.to_be_bytes());
+    data.push(u8::try_from(signatures.len()).expect("Too many guardian signatures"));
+    for signature in signatures {
+        data.push(signature.guardian_index);
+        data.extend_from_slice(&signature.signature);
+    }
+    data.extend_from_slice(&body.encode());
+    data
+}
+
+pub fn build_wormhole_verify_proof_args(
+    proof_kind: ProofKind,
+    vaa: &[u8],
+) -> WormholeVerifyProofArgs {
+    WormholeVerifyProofArgs {
+        proof_kind,
+        vaa: hex::encode(vaa),
+    }
+}
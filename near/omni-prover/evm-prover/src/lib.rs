@@ -6,7 +6,7 @@ use omni_types::errors::ProverError;
 use omni_types::evm::events::parse_evm_proof;
 use omni_types::evm::header::BlockHeader;
 use omni_types::evm::receipt::{LogEntry, Receipt};
-use omni_types::prover_args::EvmVerifyProofArgs;
+use omni_types::prover_args::{EvmProof, EvmVerifyProofArgs};
 use omni_types::prover_result::ProofKind;
 use omni_types::prover_result::ProverResult;
 use omni_types::utils::keccak256;
@@ -61,25 +61,7 @@ impl EvmProver {
             .map_err(|_| ProverError::ParseArgs.to_string())?;
 
         let evm_proof = args.proof;
-        let header: BlockHeader = rlp::decode(&evm_proof.header_data).map_err(|e| e.to_string())?;
-        let log_entry: LogEntry =
-            rlp::decode(&evm_proof.log_entry_data).map_err(|e| e.to_string())?;
-        let receipt: Receipt = rlp::decode(&evm_proof.receipt_data).map_err(|e| e.to_string())?;
-
-        // Verify log_entry included in receipt
-        let log_index_usize = usize::try_from(evm_proof.log_index).map_err(|e| e.to_string())?;
-        require!(receipt.logs[log_index_usize] == log_entry);
-
-        // Verify receipt included into header
-        let data = Self::verify_trie_proof(
-            header.receipts_root.0,
-            rlp::encode(&evm_proof.receipt_index).to_vec(),
-            &evm_proof.proof,
-        );
-
-        if evm_proof.receipt_data != data {
-            return Err(ProverError::InvalidProof.to_string());
-        }
+        let header = Self::verify_proof_data(&evm_proof)?;
 
         // Verify block header was in the bridge
         Ok(evm_client::ext(self.light_client.clone())
@@ -95,6 +77,7 @@ impl EvmProver {
                             .hash
                             .ok_or_else(|| ProverError::HashNotSet.to_string())?
                             .0,
+                        header.number.as_u64(),
                     ),
             ))
     }
@@ -111,6 +94,7 @@ impl EvmProver {
         #[serializer(borsh)] kind: ProofKind,
         #[serializer(borsh)] log_entry_data: Vec<u8>,
         #[serializer(borsh)] expected_block_hash: H256,
+        #[serializer(borsh)] block_number: u64,
         #[callback]
         #[serializer(borsh)]
         block_hash: Option<H256>,
@@ -120,6 +104,46 @@ impl EvmProver {
         }
 
         parse_evm_proof(kind, self.chain_kind, log_entry_data)
+            .map(|result| result.with_sequence(block_number))
+    }
+}
+
+impl EvmProver {
+    /// Checks that the log is part of the receipt and the receipt is part of the header's
+    /// receipts trie. Returns the decoded header, whose hash still has to be checked against
+    /// the light client.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the log index is out of bounds, the log entry doesn't match
+    /// the decoded receipt or the trie proof is malformed.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the proof data cannot be decoded or the receipt
+    /// is not included in the header.
+    pub fn verify_proof_data(evm_proof: &EvmProof) -> Result<BlockHeader, String> {
+        let header: BlockHeader = rlp::decode(&evm_proof.header_data).map_err(|e| e.to_string())?;
+        let log_entry: LogEntry =
+            rlp::decode(&evm_proof.log_entry_data).map_err(|e| e.to_string())?;
+        let receipt: Receipt = rlp::decode(&evm_proof.receipt_data).map_err(|e| e.to_string())?;
+
+        // Verify log_entry included in receipt
+        let log_index_usize = usize::try_from(evm_proof.log_index).map_err(|e| e.to_string())?;
+        require!(receipt.logs[log_index_usize] == log_entry);
+
+        // Verify receipt included into header
+        let data = Self::verify_trie_proof(
+            header.receipts_root.0,
+            rlp::encode(&evm_proof.receipt_index).to_vec(),
+            &evm_proof.proof,
+        );
+
+        if evm_proof.receipt_data != data {
+            return Err(ProverError::InvalidProof.to_string());
+        }
+
+        Ok(header)
     }
 
     /// Verify the proof recursively traversing through the key.
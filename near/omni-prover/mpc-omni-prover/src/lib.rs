@@ -15,7 +15,11 @@ use near_mpc_sdk::{
     },
     sign::DomainId,
 };
-use near_sdk::{ext_contract, near, require, AccountId, Gas, NearToken, PanicOnDefault, Promise};
+use near_plugins::{access_control, access_control_any, AccessControlRole, AccessControllable};
+use near_sdk::serde_json::json;
+use near_sdk::{
+    env, ext_contract, near, require, AccountId, Gas, NearToken, PanicOnDefault, Promise,
+};
 use omni_types::{
     aptos::events::parse_aptos_proof,
     errors::ProverError,
@@ -37,16 +41,58 @@ const VERIFY_FOREIGN_TX_GAS: Gas = Gas::from_tgas(15);
 const VERIFY_CALLBACK_GAS: Gas = Gas::from_tgas(7);
 const ONE_YOCTO: NearToken = NearToken::from_yoctonear(1);
 
+/// `ForeignChainRpcRequest` variants the prover can map to a chain. Other chains (e.g. Base or
+/// Arbitrum) can't be enabled with `set_request_chain`: they need a request variant in the MPC
+/// SDK and a contract update.
+const REQUEST_CHAINS: [&str; 4] = ["Ethereum", "Abstract", "Starknet", "Aptos"];
+
 #[ext_contract(ext_mpc_contract)]
 pub trait MpcContract {
     fn verify_foreign_transaction(&mut self, request: VerifyForeignTransactionRequestArgs);
 }
 
+#[near(serializers = [json])]
+#[derive(AccessControlRole, Copy, Clone)]
+pub enum Role {
+    DAO,
+}
+
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub enum MpcProverEvent {
+    SetFinalityEvent {
+        chain_kind: ChainKind,
+        old_finality: Option<MpcFinality>,
+        new_finality: Option<MpcFinality>,
+    },
+    SetRequestChainEvent {
+        request_chain: String,
+        old_chain_kind: Option<ChainKind>,
+        new_chain_kind: Option<ChainKind>,
+    },
+}
+
+impl MpcProverEvent {
+    pub fn to_log_string(&self) -> String {
+        json!(self).to_string()
+    }
+}
+
 #[near(contract_state)]
 #[derive(PanicOnDefault)]
+#[access_control(role_type(Role))]
 pub struct MpcOmniProver {
     pub mpc_contract_id: AccountId,
     pub finalities: HashMap<ChainKind, MpcFinality>,
+    /// Maps the `ForeignChainRpcRequest` variant name (e.g. `Ethereum`) to the chain it queries.
+    /// Keys are limited to `REQUEST_CHAINS`.
+    pub request_chains: HashMap<String, ChainKind>,
+}
+
+#[derive(BorshDeserialize)]
+struct OldState {
+    mpc_contract_id: AccountId,
+    finalities: HashMap<ChainKind, MpcFinality>,
 }
 
 #[near]
@@ -55,42 +101,126 @@ impl MpcOmniProver {
     #[private]
     #[must_use]
     pub fn init(mpc_contract_id: AccountId) -> Self {
-        let mut finalities = HashMap::new();
-        finalities.insert(ChainKind::Abs, MpcFinality::Evm(EvmFinality::Latest));
-        finalities.insert(
-            ChainKind::Strk,
-            MpcFinality::Starknet(StarknetFinality::AcceptedOnL2),
-        );
-        finalities.insert(
-            ChainKind::Aptos,
-            MpcFinality::Aptos(AptosFinality::Committed),
-        );
-
-        Self {
+        let mut contract = Self {
             mpc_contract_id,
-            finalities,
-        }
+            finalities: Self::default_finalities(),
+            request_chains: Self::default_request_chains(),
+        };
+
+        contract.acl_init_super_admin(env::predecessor_account_id());
+        contract.acl_grant_role(Role::DAO.into(), env::predecessor_account_id());
+        contract
+    }
+
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(dao: AccountId) -> Self {
+        let old_state: OldState = env::state_read().unwrap_or_else(|| env::panic_str("OldState"));
+
+        let mut contract = Self {
+            mpc_contract_id: old_state.mpc_contract_id,
+            finalities: old_state.finalities,
+            request_chains: Self::default_request_chains(),
+        };
+
+        contract.acl_init_super_admin(env::predecessor_account_id());
+        contract.acl_grant_role(Role::DAO.into(), dao);
+        contract
     }
 
     pub fn get_finalities(&self) -> Vec<(&ChainKind, &MpcFinality)> {
         self.finalities.iter().collect()
     }
 
-    #[private]
+    pub fn get_request_chains(&self) -> Vec<(&String, &ChainKind)> {
+        self.request_chains.iter().collect()
+    }
+
+    #[access_control_any(roles(Role::DAO))]
     pub fn set_finality(&mut self, chain_kind: ChainKind, finality: MpcFinality) {
-        self.finalities.insert(chain_kind, finality);
+        require!(
+            Self::finality_matches_chain(chain_kind, &finality),
+            ProverError::FinalityMismatch.as_ref()
+        );
+
+        let old_finality = self.finalities.insert(chain_kind, finality.clone());
+
+        env::log_str(
+            &MpcProverEvent::SetFinalityEvent {
+                chain_kind,
+                old_finality,
+                new_finality: Some(finality),
+            }
+            .to_log_string(),
+        );
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_finality(&mut self, chain_kind: ChainKind) {
+        let old_finality = self.finalities.remove(&chain_kind);
+
+        env::log_str(
+            &MpcProverEvent::SetFinalityEvent {
+                chain_kind,
+                old_finality,
+                new_finality: None,
+            }
+            .to_log_string(),
+        );
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_request_chain(&mut self, request_chain: String, chain_kind: ChainKind) {
+        require!(
+            REQUEST_CHAINS.contains(&request_chain.as_str())
+                && Self::is_supported_chain(chain_kind),
+            ProverError::UnsupportedChain.as_ref()
+        );
+
+        let old_chain_kind = self
+            .request_chains
+            .insert(request_chain.clone(), chain_kind);
+
+        env::log_str(
+            &MpcProverEvent::SetRequestChainEvent {
+                request_chain,
+                old_chain_kind,
+                new_chain_kind: Some(chain_kind),
+            }
+            .to_log_string(),
+        );
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_request_chain(&mut self, request_chain: String) {
+        let old_chain_kind = self.request_chains.remove(&request_chain);
+
+        env::log_str(
+            &MpcProverEvent::SetRequestChainEvent {
+                request_chain,
+                old_chain_kind,
+                new_chain_kind: None,
+            }
+            .to_log_string(),
+        );
     }
 
     #[allow(clippy::needless_pass_by_value)]
     pub fn verify_proof(&self, #[serializer(borsh)] input: Vec<u8>) -> Promise {
-        let args = MpcVerifyProofArgs::try_from_slice(&input).near_expect(ProverError::ParseArgs);
+        let args = MpcVerifyProofArgs::parse(&input).near_expect(ProverError::ParseArgs);
 
         let sign_payload = ForeignTxSignPayload::try_from_slice(&args.sign_payload)
             .near_expect(ProverError::ParseArgs);
 
         let ForeignTxSignPayload::V1(ref payload_v1) = sign_payload;
 
-        let chain_kind = Self::request_to_chain_kind(&payload_v1.request)
+        require!(
+            Self::extracted_value(payload_v1, args.value_index).is_ok(),
+            ProverError::InvalidPayloadValuesLength.as_ref()
+        );
+
+        let chain_kind = self
+            .request_to_chain_kind(&payload_v1.request)
             .near_expect(ProverError::UnsupportedChain);
 
         let finality = self
@@ -114,9 +244,14 @@ impl MpcOmniProver {
             .with_attached_deposit(ONE_YOCTO)
             .verify_foreign_transaction(request_args)
             .then(
-                Self::ext(near_sdk::env::current_account_id())
+                Self::ext(env::current_account_id())
                     .with_static_gas(VERIFY_CALLBACK_GAS)
-                    .verify_callback(args.proof_kind, args.sign_payload, chain_kind),
+                    .verify_callback(
+                        args.proof_kind,
+                        args.sign_payload,
+                        chain_kind,
+                        args.value_index,
+                    ),
             )
     }
 
@@ -129,6 +264,7 @@ impl MpcOmniProver {
         #[serializer(borsh)] proof_kind: ProofKind,
         #[serializer(borsh)] sign_payload_bytes: Vec<u8>,
         #[serializer(borsh)] chain_kind: ChainKind,
+        #[serializer(borsh)] value_index: u32,
         #[callback_result] call_result: Result<
             VerifyForeignTransactionResponse,
             near_sdk::PromiseError,
@@ -150,21 +286,33 @@ impl MpcOmniProver {
         let ForeignTxSignPayload::V1(ref payload_v1) = sign_payload;
 
         match chain_kind {
-            ChainKind::Strk => Self::parse_starknet_result(proof_kind, chain_kind, payload_v1),
-            ChainKind::Aptos => Self::parse_aptos_result(proof_kind, payload_v1),
+            ChainKind::Strk => {
+                Self::parse_starknet_result(proof_kind, chain_kind, payload_v1, value_index)
+            }
+            ChainKind::Aptos => Self::parse_aptos_result(proof_kind, payload_v1, value_index),
             _ => {
-                let log_entry_data = Self::extract_evm_log(payload_v1)?;
+                let (log_entry_data, block_number) =
+                    Self::extract_evm_log(payload_v1, value_index)?;
                 parse_evm_proof(proof_kind, chain_kind, log_entry_data)
+                    .map(|result| result.with_sequence(block_number))
             }
         }
     }
 
-    fn request_to_chain_kind(request: &ForeignChainRpcRequest) -> Option<ChainKind> {
+    fn request_to_chain_kind(&self, request: &ForeignChainRpcRequest) -> Option<ChainKind> {
+        self.request_chains
+            .get(Self::request_chain_name(request)?)
+            .copied()
+    }
+
+    /// Stable name of the `ForeignChainRpcRequest` variant, used as the key of `request_chains`.
+    /// Must return one of `REQUEST_CHAINS`.
+    const fn request_chain_name(request: &ForeignChainRpcRequest) -> Option<&'static str> {
         match request {
-            ForeignChainRpcRequest::Abstract(_) => Some(ChainKind::Abs),
-            ForeignChainRpcRequest::Ethereum(_) => Some(ChainKind::Eth),
-            ForeignChainRpcRequest::Starknet(_) => Some(ChainKind::Strk),
-            ForeignChainRpcRequest::Aptos(_) => Some(ChainKind::Aptos),
+            ForeignChainRpcRequest::Ethereum(_) => Some("Ethereum"),
+            ForeignChainRpcRequest::Abstract(_) => Some("Abstract"),
+            ForeignChainRpcRequest::Starknet(_) => Some("Starknet"),
+            ForeignChainRpcRequest::Aptos(_) => Some("Aptos"),
             _ => None,
         }
     }
@@ -185,31 +333,76 @@ impl MpcOmniProver {
         }
     }
 
-    fn extract_evm_log(payload: &ForeignTxSignPayloadV1) -> Result<Vec<u8>, String> {
-        if payload.values.len() != 1 {
-            return Err(ProverError::InvalidPayloadValuesLength.to_string());
+    const fn is_supported_chain(chain_kind: ChainKind) -> bool {
+        chain_kind.is_evm_chain() || matches!(chain_kind, ChainKind::Strk | ChainKind::Aptos)
+    }
+
+    const fn finality_matches_chain(chain_kind: ChainKind, finality: &MpcFinality) -> bool {
+        match finality {
+            MpcFinality::Evm(_) => chain_kind.is_evm_chain(),
+            MpcFinality::Starknet(_) => matches!(chain_kind, ChainKind::Strk),
+            MpcFinality::Aptos(_) => matches!(chain_kind, ChainKind::Aptos),
         }
+    }
 
-        let Some(ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(evm_log))) =
-            payload.values.first()
+    fn default_request_chains() -> HashMap<String, ChainKind> {
+        [
+            ("Ethereum", ChainKind::Eth),
+            ("Abstract", ChainKind::Abs),
+            ("Starknet", ChainKind::Strk),
+            ("Aptos", ChainKind::Aptos),
+        ]
+        .into_iter()
+        .map(|(request_chain, chain_kind)| (request_chain.to_owned(), chain_kind))
+        .collect()
+    }
+
+    fn default_finalities() -> HashMap<ChainKind, MpcFinality> {
+        HashMap::from([
+            (ChainKind::Abs, MpcFinality::Evm(EvmFinality::Latest)),
+            (
+                ChainKind::Strk,
+                MpcFinality::Starknet(StarknetFinality::AcceptedOnL2),
+            ),
+            (
+                ChainKind::Aptos,
+                MpcFinality::Aptos(AptosFinality::Committed),
+            ),
+        ])
+    }
+
+    fn extracted_value(
+        payload: &ForeignTxSignPayloadV1,
+        value_index: u32,
+    ) -> Result<&ExtractedValue, String> {
+        usize::try_from(value_index)
+            .ok()
+            .and_then(|index| payload.values.get(index))
+            .ok_or_else(|| ProverError::InvalidPayloadValuesLength.to_string())
+    }
+
+    /// Returns the RLP-encoded log and the number of the block it was emitted in.
+    fn extract_evm_log(
+        payload: &ForeignTxSignPayloadV1,
+        value_index: u32,
+    ) -> Result<(Vec<u8>, u64), String> {
+        let ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(evm_log)) =
+            Self::extracted_value(payload, value_index)?
         else {
             return Err(ProverError::InvalidProof.to_string());
         };
 
-        evm_log_to_rlp(evm_log)
+        Ok((evm_log_to_rlp(evm_log)?, evm_log.block_number))
     }
 
     fn parse_starknet_result(
         kind: ProofKind,
         chain_kind: ChainKind,
         payload: &ForeignTxSignPayloadV1,
+        value_index: u32,
     ) -> Result<ProverResult, String> {
-        if payload.values.len() != 1 {
-            return Err(ProverError::InvalidPayloadValuesLength.to_string());
-        }
-
-        let Some(ExtractedValue::StarknetExtractedValue(StarknetExtractedValue::Log(starknet_log))) =
-            payload.values.first()
+        let ExtractedValue::StarknetExtractedValue(StarknetExtractedValue::Log(starknet_log)) =
+            Self::extracted_value(payload, value_index)?
         else {
             return Err(ProverError::InvalidProof.to_string());
         };
@@ -218,18 +411,16 @@ impl MpcOmniProver {
         let data: Vec<[u8; 32]> = starknet_log.data.iter().map(|d| d.0).collect();
 
         parse_starknet_proof(kind, chain_kind, &starknet_log.from_address.0, &keys, &data)
+            .map(|result| result.with_sequence(starknet_log.block_number))
     }
 
     fn parse_aptos_result(
         kind: ProofKind,
         payload: &ForeignTxSignPayloadV1,
+        value_index: u32,
     ) -> Result<ProverResult, String> {
-        if payload.values.len() != 1 {
-            return Err(ProverError::InvalidPayloadValuesLength.to_string());
-        }
-
-        let Some(ExtractedValue::AptosExtractedValue(AptosExtractedValue::Event(event))) =
-            payload.values.first()
+        let ExtractedValue::AptosExtractedValue(AptosExtractedValue::Event(event)) =
+            Self::extracted_value(payload, value_index)?
         else {
             return Err(ProverError::InvalidProof.to_string());
         };
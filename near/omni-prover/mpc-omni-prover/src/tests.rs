@@ -10,12 +10,21 @@ use near_mpc_sdk::near_mpc_contract_interface::types::{
 };
 
 use near_sdk::base64::Engine;
-use omni_types::prover_args::MpcVerifyProofArgs;
+use omni_types::errors::ProverError;
+use omni_types::prover_args::{MpcVerifyProofArgs, MpcVerifyProofArgsV0};
 use omni_types::prover_result::{ProofKind, ProverResult};
 
 use omni_types::{ChainKind, OmniAddress, H256};
 
-use crate::{evm_log_to_rlp, MpcFinality, MpcOmniProver};
+use crate::{evm_log_to_rlp, MpcFinality, MpcOmniProver, REQUEST_CHAINS};
+
+fn test_prover() -> MpcOmniProver {
+    MpcOmniProver {
+        mpc_contract_id: "mpc.testnet".parse().unwrap(),
+        finalities: MpcOmniProver::default_finalities(),
+        request_chains: MpcOmniProver::default_request_chains(),
+    }
+}
 
 fn test_aptos_request() -> AptosRpcRequest {
     AptosRpcRequest {
@@ -40,7 +49,7 @@ fn test_aptos_init_transfer_event() -> AptosEvent {
 fn test_request_to_chain_kind_aptos() {
     let request = ForeignChainRpcRequest::Aptos(test_aptos_request());
     assert_eq!(
-        MpcOmniProver::request_to_chain_kind(&request),
+        test_prover().request_to_chain_kind(&request),
         Some(ChainKind::Aptos)
     );
 }
@@ -95,7 +104,7 @@ fn test_parse_aptos_result_init_transfer() {
         )],
     };
 
-    let result = MpcOmniProver::parse_aptos_result(ProofKind::InitTransfer, &payload).unwrap();
+    let result = MpcOmniProver::parse_aptos_result(ProofKind::InitTransfer, &payload, 0).unwrap();
     match result {
         ProverResult::InitTransfer(m) => {
             assert_eq!(m.origin_nonce, 7);
@@ -124,7 +133,7 @@ fn test_parse_aptos_result_rejects_non_aptos_value() {
             test_evm_log(),
         ))],
     };
-    assert!(MpcOmniProver::parse_aptos_result(ProofKind::InitTransfer, &payload).is_err());
+    assert!(MpcOmniProver::parse_aptos_result(ProofKind::InitTransfer, &payload, 0).is_err());
 }
 
 fn hex_to_hash256(hex_str: &str) -> Hash256 {
@@ -301,6 +310,7 @@ fn test_mpc_verify_proof_args_serialization() {
     let args = MpcVerifyProofArgs {
         proof_kind: ProofKind::InitTransfer,
         sign_payload: payload_bytes.clone(),
+        value_index: 0,
     };
 
     let serialized = borsh::to_vec(&args).unwrap();
@@ -310,6 +320,78 @@ fn test_mpc_verify_proof_args_serialization() {
     assert_eq!(deserialized.proof_kind, ProofKind::InitTransfer);
 }
 
+#[test]
+fn test_mpc_verify_proof_args_v0_accepted() {
+    let payload_bytes = borsh::to_vec(&test_sign_payload()).unwrap();
+    let legacy = borsh::to_vec(&MpcVerifyProofArgsV0 {
+        proof_kind: ProofKind::FinTransfer,
+        sign_payload: payload_bytes.clone(),
+    })
+    .unwrap();
+
+    let args = MpcVerifyProofArgs::parse(&legacy).unwrap();
+    assert_eq!(args.proof_kind, ProofKind::FinTransfer);
+    assert_eq!(args.sign_payload, payload_bytes);
+    assert_eq!(args.value_index, 0);
+
+    let current = borsh::to_vec(&MpcVerifyProofArgs {
+        proof_kind: ProofKind::FinTransfer,
+        sign_payload: payload_bytes,
+        value_index: 3,
+    })
+    .unwrap();
+    assert_eq!(MpcVerifyProofArgs::parse(&current).unwrap().value_index, 3);
+    assert!(MpcVerifyProofArgs::parse(&current[..current.len() - 1]).is_err());
+}
+
+#[test]
+fn test_extract_evm_log_by_value_index() {
+    let first = test_evm_log();
+    let mut second = test_evm_log();
+    second.log_index += 1;
+    second.data = "0x01".to_string();
+
+    let payload = ForeignTxSignPayloadV1 {
+        request: ForeignChainRpcRequest::Ethereum(test_evm_request()),
+        values: vec![
+            ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(first.clone())),
+            ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(second.clone())),
+        ],
+    };
+
+    assert_eq!(
+        MpcOmniProver::extract_evm_log(&payload, 0).unwrap(),
+        (evm_log_to_rlp(&first).unwrap(), first.block_number)
+    );
+    assert_eq!(
+        MpcOmniProver::extract_evm_log(&payload, 1).unwrap(),
+        (evm_log_to_rlp(&second).unwrap(), second.block_number)
+    );
+    assert_eq!(
+        MpcOmniProver::extract_evm_log(&payload, 2).unwrap_err(),
+        ProverError::InvalidPayloadValuesLength.to_string()
+    );
+}
+
+#[test]
+fn test_parse_aptos_result_by_value_index() {
+    let payload = ForeignTxSignPayloadV1 {
+        request: ForeignChainRpcRequest::Aptos(test_aptos_request()),
+        values: vec![
+            ExtractedValue::EvmExtractedValue(EvmExtractedValue::Log(test_evm_log())),
+            ExtractedValue::AptosExtractedValue(AptosExtractedValue::Event(
+                test_aptos_init_transfer_event(),
+            )),
+        ],
+    };
+
+    assert!(MpcOmniProver::parse_aptos_result(ProofKind::InitTransfer, &payload, 0).is_err());
+    assert!(matches!(
+        MpcOmniProver::parse_aptos_result(ProofKind::InitTransfer, &payload, 1),
+        Ok(ProverResult::InitTransfer(_))
+    ));
+}
+
 #[test]
 fn test_payload_hash_mismatch_detected() {
     let payload = test_sign_payload();
@@ -344,7 +426,7 @@ fn test_forged_payload_produces_different_hash() {
 fn test_request_to_chain_kind_ethereum() {
     let request = ForeignChainRpcRequest::Ethereum(test_evm_request());
     assert_eq!(
-        MpcOmniProver::request_to_chain_kind(&request),
+        test_prover().request_to_chain_kind(&request),
         Some(ChainKind::Eth)
     );
 }
@@ -353,7 +435,7 @@ fn test_request_to_chain_kind_ethereum() {
 fn test_request_to_chain_kind_abstract() {
     let request = ForeignChainRpcRequest::Abstract(test_evm_request());
     assert_eq!(
-        MpcOmniProver::request_to_chain_kind(&request),
+        test_prover().request_to_chain_kind(&request),
         Some(ChainKind::Abs)
     );
 }
@@ -362,7 +444,7 @@ fn test_request_to_chain_kind_abstract() {
 fn test_request_to_chain_kind_starknet() {
     let request = ForeignChainRpcRequest::Starknet(test_starknet_request());
     assert_eq!(
-        MpcOmniProver::request_to_chain_kind(&request),
+        test_prover().request_to_chain_kind(&request),
         Some(ChainKind::Strk)
     );
 }
@@ -374,7 +456,64 @@ fn test_request_to_chain_kind_unsupported() {
         finality: SolanaFinality::Confirmed,
         extractors: vec![],
     });
-    assert_eq!(MpcOmniProver::request_to_chain_kind(&solana_request), None);
+    assert_eq!(test_prover().request_to_chain_kind(&solana_request), None);
+}
+
+#[test]
+fn test_request_to_chain_kind_configurable() {
+    let request = ForeignChainRpcRequest::Ethereum(test_evm_request());
+    let mut prover = test_prover();
+    prover.request_chains.remove("Ethereum");
+    assert_eq!(prover.request_to_chain_kind(&request), None);
+
+    prover
+        .request_chains
+        .insert("Ethereum".to_string(), ChainKind::Eth);
+    assert_eq!(prover.request_to_chain_kind(&request), Some(ChainKind::Eth));
+}
+
+#[test]
+fn test_default_request_chains() {
+    let prover = test_prover();
+    for (request_chain, chain_kind) in &prover.request_chains {
+        assert!(REQUEST_CHAINS.contains(&request_chain.as_str()));
+        assert!(MpcOmniProver::is_supported_chain(*chain_kind));
+    }
+    assert_eq!(prover.request_chains.len(), REQUEST_CHAINS.len());
+}
+
+#[test]
+fn test_finality_matches_chain() {
+    let evm = MpcFinality::Evm(EvmFinality::Finalized);
+    let starknet = MpcFinality::Starknet(StarknetFinality::AcceptedOnL1);
+    let aptos = MpcFinality::Aptos(AptosFinality::Committed);
+
+    assert!(MpcOmniProver::finality_matches_chain(ChainKind::Base, &evm));
+    assert!(MpcOmniProver::finality_matches_chain(
+        ChainKind::Strk,
+        &starknet
+    ));
+    assert!(MpcOmniProver::finality_matches_chain(
+        ChainKind::Aptos,
+        &aptos
+    ));
+    assert!(!MpcOmniProver::finality_matches_chain(
+        ChainKind::Strk,
+        &evm
+    ));
+    assert!(!MpcOmniProver::finality_matches_chain(
+        ChainKind::Bnb,
+        &starknet
+    ));
+    assert!(!MpcOmniProver::finality_matches_chain(ChainKind::Sol, &evm));
+}
+
+#[test]
+fn test_is_supported_chain() {
+    assert!(MpcOmniProver::is_supported_chain(ChainKind::HyperEvm));
+    assert!(MpcOmniProver::is_supported_chain(ChainKind::Aptos));
+    assert!(!MpcOmniProver::is_supported_chain(ChainKind::Sol));
+    assert!(!MpcOmniProver::is_supported_chain(ChainKind::Btc));
 }
 
 #[test]
@@ -509,6 +648,7 @@ fn test_abs_testnet_verify_proof_args() {
     let args = MpcVerifyProofArgs {
         proof_kind: ProofKind::InitTransfer,
         sign_payload: borsh::to_vec(&sign_payload).unwrap(),
+        value_index: 0,
     };
 
     // Verify serialization roundtrip
@@ -606,6 +746,7 @@ fn test_starknet_sepolia_verify_proof_args() {
     let args = MpcVerifyProofArgs {
         proof_kind: ProofKind::InitTransfer,
         sign_payload: borsh::to_vec(&sign_payload).unwrap(),
+        value_index: 0,
     };
 
     let inner_bytes = borsh::to_vec(&args).unwrap();
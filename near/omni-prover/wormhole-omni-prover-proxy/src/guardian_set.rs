@@ -0,0 +1,158 @@
+//https://github.com/wormhole-foundation/wormhole/blob/main/whitepapers/0001_generic_message_passing.md
+//https://github.com/wormhole-foundation/wormhole/blob/main/whitepapers/0002_governance_messaging.md
+
+use near_sdk::{env, near};
+use omni_types::errors::ProverError;
+use omni_types::H160;
+
+use crate::byte_utils::ByteUtils;
+use crate::parsed_vaa::ParsedVAA;
+
+pub const GOVERNANCE_CHAIN_ID: u16 = 1;
+pub const GOVERNANCE_EMITTER: [u8; 32] = {
+    let mut emitter = [0; 32];
+    emitter[31] = 4;
+    emitter
+};
+/// Wormhole chain ID of NEAR, governance messages may target all chains (0) or NEAR only.
+pub const WORMHOLE_NEAR_CHAIN_ID: u16 = 15;
+/// Period during which a replaced guardian set is still accepted.
+pub const GUARDIAN_SET_EXPIRATION_SEC: u64 = 24 * 60 * 60;
+
+const CORE_MODULE: [u8; 32] = {
+    let mut module = [0; 32];
+    module[28] = b'C';
+    module[29] = b'o';
+    module[30] = b'r';
+    module[31] = b'e';
+    module
+};
+const ACTION_GUARDIAN_SET_UPGRADE: u8 = 2;
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardianSet {
+    pub keys: Vec<H160>,
+    /// Unix timestamp in seconds after which the set is no longer valid, `0` if it never
+    /// expires (the current set).
+    pub expiration_time: u64,
+}
+
+impl GuardianSet {
+    pub fn quorum(&self) -> usize {
+        self.keys.len() * 2 / 3 + 1
+    }
+
+    pub const fn is_expired(&self, now_sec: u64) -> bool {
+        self.expiration_time != 0 && self.expiration_time < now_sec
+    }
+
+    /// Checks that a quorum of distinct guardians of this set signed the VAA body.
+    pub fn verify(&self, vaa: &ParsedVAA) -> Result<(), ProverError> {
+        if vaa.signatures.len() < self.quorum() {
+            return Err(ProverError::NoQuorum);
+        }
+
+        let digest = env::keccak256_array(&vaa.hash);
+        let mut last_index = None;
+
+        for signature in &vaa.signatures {
+            // Strictly increasing indexes rule out counting the same guardian twice.
+            if last_index.is_some_and(|last| signature.guardian_index <= last) {
+                return Err(ProverError::InvalidSignature);
+            }
+            last_index = Some(signature.guardian_index);
+
+            let key = self
+                .keys
+                .get(usize::from(signature.guardian_index))
+                .ok_or(ProverError::InvalidSignature)?;
+
+            let public_key = env::ecrecover(
+                &digest,
+                &signature.signature[..64],
+                signature.signature[64],
+                true,
+            )
+            .ok_or(ProverError::InvalidSignature)?;
+
+            if env::keccak256_array(&public_key)[12..] != key.0 {
+                return Err(ProverError::InvalidSignature);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct GuardianSetUpgrade {
+    pub new_guardian_set_index: u32,
+    pub keys: Vec<H160>,
+}
+
+impl GuardianSetUpgrade {
+    /* Payload format:
+
+    0   [32]uint8   module ("Core", left padded)
+    32  uint8       action (2)
+    33  uint16      target chain (0 for all chains)
+    35  uint32      new guardian set index
+    39  uint8       number of guardians
+    per guardian:
+    0   [20]uint8   guardian address
+    */
+
+    const ACTION_POS: usize = 32;
+    const CHAIN_POS: usize = 33;
+    const GUARDIAN_SET_INDEX_POS: usize = 35;
+    const LEN_GUARDIANS_POS: usize = 39;
+    const GUARDIANS_POS: usize = 40;
+    const GUARDIAN_LEN: usize = 20;
+
+    pub fn parse(vaa: &ParsedVAA) -> Result<Self, ProverError> {
+        if vaa.emitter_chain != GOVERNANCE_CHAIN_ID || vaa.emitter_address != GOVERNANCE_EMITTER {
+            return Err(ProverError::InvalidGovernanceVaa);
+        }
+
+        let payload = vaa.payload.as_slice();
+        if payload.get(..32) != Some(CORE_MODULE.as_slice())
+            || payload.get_u8(Self::ACTION_POS) != Some(ACTION_GUARDIAN_SET_UPGRADE)
+        {
+            return Err(ProverError::InvalidGovernanceVaa);
+        }
+
+        let chain = payload
+            .get_u16(Self::CHAIN_POS)
+            .ok_or(ProverError::InvalidVaa)?;
+        if chain != 0 && chain != WORMHOLE_NEAR_CHAIN_ID {
+            return Err(ProverError::InvalidGovernanceVaa);
+        }
+
+        let new_guardian_set_index = payload
+            .get_u32(Self::GUARDIAN_SET_INDEX_POS)
+            .ok_or(ProverError::InvalidVaa)?;
+        let len_guardians = usize::from(
+            payload
+                .get_u8(Self::LEN_GUARDIANS_POS)
+                .ok_or(ProverError::InvalidVaa)?,
+        );
+
+        let guardians = payload
+            .get(Self::GUARDIANS_POS..)
+            .filter(|guardians| guardians.len() == len_guardians * Self::GUARDIAN_LEN)
+            .ok_or(ProverError::InvalidVaa)?;
+        if guardians.is_empty() {
+            return Err(ProverError::InvalidGovernanceVaa);
+        }
+
+        let keys = guardians
+            .chunks_exact(Self::GUARDIAN_LEN)
+            .map(|key| H160(key.try_into().unwrap_or_default()))
+            .collect();
+
+        Ok(Self {
+            new_guardian_set_index,
+            keys,
+        })
+    }
+}
@@ -1,26 +1,93 @@
-use near_sdk::borsh::BorshDeserialize;
+use std::collections::HashMap;
+
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::serde_json::json;
+use near_sdk::store::LookupMap;
 use near_sdk::{
-    env, ext_contract, near, near_bindgen, require, AccountId, Gas, PanicOnDefault, Promise,
-    PromiseError,
+    env, ext_contract, near, near_bindgen, require, AccountId, BorshStorageKey, Gas,
+    PanicOnDefault, PromiseError, PromiseOrValue,
 };
+use omni_types::errors::ProverError;
 use omni_types::prover_args::WormholeVerifyProofArgs;
 use omni_types::prover_result::{ProofKind, ProverResult};
+use omni_types::{ChainKind, H160};
+
+use crate::guardian_set::{GuardianSet, GuardianSetUpgrade, GUARDIAN_SET_EXPIRATION_SEC};
+use crate::parsed_vaa::ParsedVAA;
 
 mod byte_utils;
-mod parsed_vaa;
+pub mod guardian_set;
+pub mod parsed_vaa;
+#[cfg(test)]
+mod tests;
 
 pub const VERIFY_VAA_GAS: Gas = Gas::from_tgas(10);
 pub const VERIFY_VAA_CALLBACK_GAS: Gas = Gas::from_tgas(5);
 
+/// Wormhole chain IDs: <https://wormhole.com/docs/products/reference/chain-ids/>
+pub const WORMHOLE_SOLANA_CHAIN_ID: u16 = 1;
+pub const WORMHOLE_FOGO_CHAIN_ID: u16 = 51;
+/// Consistency level of messages published with `Finality::Finalized` on SVM chains.
+pub const SVM_FINALIZED_CONSISTENCY_LEVEL: u8 = 1;
+
 #[ext_contract(ext_prover)]
 pub trait Prover {
     fn verify_vaa(&self, vaa: &str) -> u32;
 }
 
+#[derive(BorshSerialize, BorshStorageKey)]
+enum StorageKey {
+    GuardianSets,
+}
+
+/// `External` delegates signature checks to the Wormhole core contract, `InContract` checks
+/// them against the guardian sets stored in this contract.
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerificationMode {
+    #[default]
+    External,
+    InContract,
+}
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WormholeChainConfig {
+    pub chain_kind: ChainKind,
+    pub consistency_level: u8,
+}
+
+/// Replays are rejected by the bridge, so verified VAAs are only logged to let indexers spot
+/// duplicates.
+#[near(serializers = [json])]
+#[derive(Clone, Debug)]
+pub enum WormholeProverEvent {
+    VaaVerifiedEvent {
+        emitter_chain: u16,
+        emitter_address: String,
+        sequence: u64,
+    },
+}
+
+impl WormholeProverEvent {
+    pub fn to_log_string(&self) -> String {
+        json!(self).to_string()
+    }
+}
+
 #[near(contract_state)]
 #[derive(PanicOnDefault)]
 pub struct WormholeOmniProverProxy {
     pub prover_account: AccountId,
+    pub chains: HashMap<u16, WormholeChainConfig>,
+    pub verification_mode: VerificationMode,
+    pub guardian_sets: LookupMap<u32, GuardianSet>,
+    pub current_guardian_set_index: Option<u32>,
+}
+
+#[derive(BorshDeserialize)]
+struct OldState {
+    prover_account: AccountId,
 }
 
 #[near_bindgen]
@@ -28,18 +95,159 @@ impl WormholeOmniProverProxy {
     #[init]
     #[private]
     #[must_use]
-    pub const fn init(prover_account: AccountId) -> Self {
-        Self { prover_account }
+    pub fn init(prover_account: AccountId) -> Self {
+        Self {
+            prover_account,
+            chains: Self::default_chains(),
+            verification_mode: VerificationMode::External,
+            guardian_sets: LookupMap::new(StorageKey::GuardianSets),
+            current_guardian_set_index: None,
+        }
+    }
+
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: OldState = env::state_read().unwrap_or_else(|| env::panic_str("OldState"));
+        Self::init(old_state.prover_account)
+    }
+
+    pub fn get_chains(&self) -> Vec<(&u16, &WormholeChainConfig)> {
+        self.chains.iter().collect()
+    }
+
+    #[private]
+    pub fn set_chain(&mut self, wormhole_chain_id: u16, config: WormholeChainConfig) {
+        self.chains.insert(wormhole_chain_id, config);
     }
 
+    #[private]
+    pub fn remove_chain(&mut self, wormhole_chain_id: u16) {
+        self.chains.remove(&wormhole_chain_id);
+    }
+
+    pub const fn get_verification_mode(&self) -> VerificationMode {
+        self.verification_mode
+    }
+
+    pub const fn get_current_guardian_set_index(&self) -> Option<u32> {
+        self.current_guardian_set_index
+    }
+
+    pub fn get_guardian_set(&self, index: u32) -> Option<&GuardianSet> {
+        self.guardian_sets.get(&index)
+    }
+
+    #[private]
+    pub fn set_verification_mode(&mut self, mode: VerificationMode) {
+        if mode == VerificationMode::InContract {
+            require!(
+                self.current_guardian_set_index.is_some(),
+                ProverError::GuardianSetNotFound.as_ref()
+            );
+        }
+
+        self.verification_mode = mode;
+    }
+
+    /// Bootstraps the in-contract guardian set. Later sets can only be installed through
+    /// `submit_guardian_set_upgrade`.
+    #[private]
+    pub fn init_guardian_set(&mut self, index: u32, keys: Vec<H160>) {
+        require!(
+            self.current_guardian_set_index.is_none(),
+            ProverError::GuardianSetExists.as_ref()
+        );
+        require!(!keys.is_empty(), ProverError::InvalidPublicKey.as_ref());
+
+        self.guardian_sets.insert(
+            index,
+            GuardianSet {
+                keys,
+                expiration_time: 0,
+            },
+        );
+        self.current_guardian_set_index = Some(index);
+    }
+
+    /// Installs the guardian set from a `GuardianSetUpgrade` governance VAA signed by the
+    /// current guardian set. The replaced set stays valid for `GUARDIAN_SET_EXPIRATION_SEC`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the VAA is malformed, not a guardian set upgrade,
+    /// not signed by the current guardian set or doesn't increment the guardian set index.
     #[allow(clippy::needless_pass_by_value)]
-    pub fn verify_proof(&self, #[serializer(borsh)] input: Vec<u8>) -> Promise {
+    #[handle_result]
+    pub fn submit_guardian_set_upgrade(&mut self, vaa: String) -> Result<(), String> {
+        let data = hex::decode(vaa).map_err(|_| ProverError::InvalidVaa.to_string())?;
+        let parsed_vaa = ParsedVAA::parse(&data).map_err(|e| e.to_string())?;
+
+        let current_index = self
+            .current_guardian_set_index
+            .ok_or_else(|| ProverError::GuardianSetNotFound.to_string())?;
+        if parsed_vaa.guardian_set_index != current_index {
+            return Err(ProverError::InvalidGovernanceVaa.to_string());
+        }
+
+        self.verify_signatures(&parsed_vaa)
+            .map_err(|e| e.to_string())?;
+
+        let upgrade = GuardianSetUpgrade::parse(&parsed_vaa).map_err(|e| e.to_string())?;
+        if Some(upgrade.new_guardian_set_index) != current_index.checked_add(1) {
+            return Err(ProverError::InvalidGovernanceVaa.to_string());
+        }
+
+        if let Some(current_set) = self.guardian_sets.get_mut(&current_index) {
+            current_set.expiration_time = now_sec() + GUARDIAN_SET_EXPIRATION_SEC;
+        }
+
+        self.guardian_sets.insert(
+            upgrade.new_guardian_set_index,
+            GuardianSet {
+                keys: upgrade.keys,
+                expiration_time: 0,
+            },
+        );
+        self.current_guardian_set_index = Some(upgrade.new_guardian_set_index);
+
+        env::log_str(&format!(
+            "Guardian set upgraded: {current_index} -> {}",
+            upgrade.new_guardian_set_index
+        ));
+
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// This function will return an error if the args can't be parsed. In `InContract` mode it
+    /// also returns the errors of `verify_vaa_callback` and the signature check errors.
+    #[allow(clippy::needless_pass_by_value)]
+    #[handle_result]
+    #[result_serializer(borsh)]
+    pub fn verify_proof(
+        &self,
+        #[serializer(borsh)] input: Vec<u8>,
+    ) -> Result<PromiseOrValue<ProverResult>, String> {
         let args = WormholeVerifyProofArgs::try_from_slice(&input)
-            .unwrap_or_else(|_| env::panic_str("ErrorOnArgsParsing"));
+            .map_err(|_| ProverError::ParseArgs.to_string())?;
 
         env::log_str(&args.vaa);
 
-        ext_prover::ext(self.prover_account.clone())
+        if self.verification_mode == VerificationMode::InContract {
+            let data = hex::decode(&args.vaa).map_err(|_| ProverError::InvalidVaa.to_string())?;
+            let parsed_vaa = ParsedVAA::parse(&data).map_err(|e| e.to_string())?;
+            self.verify_signatures(&parsed_vaa)
+                .map_err(|e| e.to_string())?;
+
+            return self
+                .process_vaa(args.proof_kind, parsed_vaa)
+                .map(PromiseOrValue::Value)
+                .map_err(|e| e.to_string());
+        }
+
+        Ok(ext_prover::ext(self.prover_account.clone())
             .with_static_gas(VERIFY_VAA_GAS)
             .verify_vaa(&args.vaa)
             .then(
@@ -47,14 +255,16 @@ impl WormholeOmniProverProxy {
                     .with_static_gas(VERIFY_VAA_CALLBACK_GAS)
                     .verify_vaa_callback(args.proof_kind, args.vaa),
             )
+            .into())
     }
 
-    /// # Panics
+    /// # Errors
     ///
-    /// This function will panic in the following situations:
-    /// - If the `vaa` string cannot be decoded as a valid hexadecimal string.
-    /// - If the `ParsedVAA::parse` function fails to parse the decoded VAA data.
+    /// This function will return an error in the following situations:
+    /// - If the `vaa` string cannot be decoded as a valid hexadecimal string or VAA.
+    /// - If the emitter chain is not configured or the consistency level doesn't match.
     /// - If the `proof_kind` doesn't match the first byte of the VAA payload.
+    /// - If the message chain doesn't match the emitter chain.
     #[private]
     #[handle_result]
     #[result_serializer(borsh)]
@@ -68,19 +278,105 @@ impl WormholeOmniProverProxy {
             return Err("Proof is not valid!".to_owned());
         }
 
-        let h = hex::decode(vaa).expect("invalidVaa");
-        let parsed_vaa = parsed_vaa::ParsedVAA::parse(&h);
+        let h = hex::decode(vaa).map_err(|_| ProverError::InvalidVaa.to_string())?;
+        let parsed_vaa = ParsedVAA::parse(&h).map_err(|e| e.to_string())?;
 
-        require!(
-            u8::from(proof_kind) == parsed_vaa.payload[0],
-            "Invalid proof kind"
-        );
+        self.process_vaa(proof_kind, parsed_vaa)
+            .map_err(|e| e.to_string())
+    }
+}
 
-        match proof_kind {
-            ProofKind::InitTransfer => Ok(ProverResult::InitTransfer(parsed_vaa.try_into()?)),
-            ProofKind::FinTransfer => Ok(ProverResult::FinTransfer(parsed_vaa.try_into()?)),
-            ProofKind::DeployToken => Ok(ProverResult::DeployToken(parsed_vaa.try_into()?)),
-            ProofKind::LogMetadata => Ok(ProverResult::LogMetadata(parsed_vaa.try_into()?)),
+impl WormholeOmniProverProxy {
+    fn default_chains() -> HashMap<u16, WormholeChainConfig> {
+        HashMap::from([
+            (
+                WORMHOLE_SOLANA_CHAIN_ID,
+                WormholeChainConfig {
+                    chain_kind: ChainKind::Sol,
+                    consistency_level: SVM_FINALIZED_CONSISTENCY_LEVEL,
+                },
+            ),
+            (
+                WORMHOLE_FOGO_CHAIN_ID,
+                WormholeChainConfig {
+                    chain_kind: ChainKind::Fogo,
+                    consistency_level: SVM_FINALIZED_CONSISTENCY_LEVEL,
+                },
+            ),
+        ])
+    }
+
+    fn process_vaa(
+        &self,
+        proof_kind: ProofKind,
+        parsed_vaa: ParsedVAA,
+    ) -> Result<ProverResult, ProverError> {
+        let chain_config = self
+            .chains
+            .get(&parsed_vaa.emitter_chain)
+            .ok_or(ProverError::UnsupportedChain)?;
+
+        if parsed_vaa.consistency_level != chain_config.consistency_level {
+            return Err(ProverError::FinalityMismatch);
+        }
+
+        if parsed_vaa.payload.first() != Some(&u8::from(proof_kind)) {
+            return Err(ProverError::InvalidProofKind);
+        }
+
+        let expected_chain = chain_config.chain_kind;
+        let event = WormholeProverEvent::VaaVerifiedEvent {
+            emitter_chain: parsed_vaa.emitter_chain,
+            emitter_address: hex::encode(&parsed_vaa.emitter_address),
+            sequence: parsed_vaa.sequence,
+        };
+
+        let result = Self::to_prover_result(proof_kind, parsed_vaa)?;
+
+        if emitter_chain(&result) != expected_chain {
+            return Err(ProverError::ChainMismatch);
+        }
+
+        env::log_str(&event.to_log_string());
+
+        Ok(result)
+    }
+
+    fn verify_signatures(&self, parsed_vaa: &ParsedVAA) -> Result<(), ProverError> {
+        let guardian_set = self
+            .guardian_sets
+            .get(&parsed_vaa.guardian_set_index)
+            .ok_or(ProverError::GuardianSetNotFound)?;
+
+        if guardian_set.is_expired(now_sec()) {
+            return Err(ProverError::GuardianSetExpired);
         }
+
+        guardian_set.verify(parsed_vaa)
+    }
+
+    fn to_prover_result(
+        proof_kind: ProofKind,
+        parsed_vaa: ParsedVAA,
+    ) -> Result<ProverResult, ProverError> {
+        Ok(match proof_kind {
+            ProofKind::InitTransfer => ProverResult::InitTransfer(parsed_vaa.try_into()?),
+            ProofKind::FinTransfer => ProverResult::FinTransfer(parsed_vaa.try_into()?),
+            ProofKind::DeployToken => ProverResult::DeployToken(parsed_vaa.try_into()?),
+            ProofKind::LogMetadata => ProverResult::LogMetadata(parsed_vaa.try_into()?),
+        })
+    }
+}
+
+const fn emitter_chain(result: &ProverResult) -> ChainKind {
+    match result {
+        ProverResult::InitTransfer(message) => message.emitter_address.get_chain(),
+        ProverResult::FinTransfer(message) => message.emitter_address.get_chain(),
+        ProverResult::DeployToken(message) => message.emitter_address.get_chain(),
+        ProverResult::LogMetadata(message) => message.emitter_address.get_chain(),
     }
 }
+
+fn now_sec() -> u64 {
+    env::block_timestamp_ms() / 1000
+}
@@ -5,14 +5,22 @@ use {
     borsh::BorshDeserialize,
     near_sdk::env,
     omni_types::{
+        errors::ProverError,
         prover_result::{
             DeployTokenMessage, FinTransferMessage, InitTransferMessage, LogMetadataMessage,
             ProofKind,
         },
-        stringify, Fee, Nonce, OmniAddress, TransferId,
+        BoundedString, Fee, Nonce, OmniAddress, TokenName, TokenSymbol, TransferId,
+        MAX_ACCOUNT_ID_LEN,
     },
 };
 
+pub struct GuardianSignature {
+    pub guardian_index: u8,
+    /// `r || s || v`
+    pub signature: [u8; 65],
+}
+
 // Validator Action Approval(VAA) data
 #[allow(dead_code)]
 pub struct ParsedVAA {
@@ -21,6 +29,7 @@ pub struct ParsedVAA {
     pub timestamp: u32,
     pub nonce: u32,
     pub len_signers: usize,
+    pub signatures: Vec<GuardianSignature>,
 
     pub emitter_chain: u16,
     pub emitter_address: Vec<u8>,
@@ -66,50 +75,67 @@ impl ParsedVAA {
     pub const VAA_CONSISTENCY_LEVEL_POS: usize = 50;
     pub const VAA_PAYLOAD_POS: usize = 51;
 
-    pub fn parse(data: &[u8]) -> Self {
-        let version = data.get_u8(0);
+    pub fn parse(data: &[u8]) -> Result<Self, ProverError> {
+        let version = data.get_u8(0).ok_or(ProverError::InvalidVaa)?;
 
         // Load 4 bytes starting from index 1
-        let guardian_set_index: u32 = data.get_u32(Self::GUARDIAN_SET_INDEX_POS);
-        let len_signers = data.get_u8(Self::LEN_SIGNER_POS).into();
+        let guardian_set_index: u32 = data
+            .get_u32(Self::GUARDIAN_SET_INDEX_POS)
+            .ok_or(ProverError::InvalidVaa)?;
+        let len_signers = data
+            .get_u8(Self::LEN_SIGNER_POS)
+            .ok_or(ProverError::InvalidVaa)?
+            .into();
         let body_offset: usize = Self::HEADER_LEN + Self::SIGNATURE_LEN * len_signers;
 
         // Hash the body
-        if body_offset >= data.len() {
-            env::panic_str("InvalidVAA");
+        if body_offset + Self::VAA_PAYLOAD_POS > data.len() {
+            return Err(ProverError::InvalidVaa);
         }
+        let signatures = data[Self::HEADER_LEN..body_offset]
+            .chunks_exact(Self::SIGNATURE_LEN)
+            .map(|chunk| GuardianSignature {
+                guardian_index: chunk[0],
+                signature: chunk[1..].try_into().unwrap_or([0; 65]),
+            })
+            .collect();
         let body = &data[body_offset..];
 
         let hash = env::keccak256(body);
 
-        // Signatures valid, apply VAA
-        if body_offset + Self::VAA_PAYLOAD_POS > data.len() {
-            env::panic_str("InvalidVAA");
-        }
-
-        let timestamp = data.get_u32(body_offset);
-        let nonce = data.get_u32(body_offset + Self::VAA_NONCE_POS);
-        let emitter_chain = data.get_u16(body_offset + Self::VAA_EMITTER_CHAIN_POS);
-        let emitter_address = data
-            .get_bytes32(body_offset + Self::VAA_EMITTER_ADDRESS_POS)
+        let timestamp = body.get_u32(0).ok_or(ProverError::InvalidVaa)?;
+        let nonce = body
+            .get_u32(Self::VAA_NONCE_POS)
+            .ok_or(ProverError::InvalidVaa)?;
+        let emitter_chain = body
+            .get_u16(Self::VAA_EMITTER_CHAIN_POS)
+            .ok_or(ProverError::InvalidVaa)?;
+        let emitter_address = body
+            .get_bytes32(Self::VAA_EMITTER_ADDRESS_POS)
+            .ok_or(ProverError::InvalidVaa)?
             .to_vec();
-        let sequence = data.get_u64(body_offset + Self::VAA_SEQUENCE_POS);
-        let consistency_level = data.get_u8(body_offset + Self::VAA_CONSISTENCY_LEVEL_POS);
-        let payload = data[body_offset + Self::VAA_PAYLOAD_POS..].to_vec();
-
-        Self {
+        let sequence = body
+            .get_u64(Self::VAA_SEQUENCE_POS)
+            .ok_or(ProverError::InvalidVaa)?;
+        let consistency_level = body
+            .get_u8(Self::VAA_CONSISTENCY_LEVEL_POS)
+            .ok_or(ProverError::InvalidVaa)?;
+        let payload = body[Self::VAA_PAYLOAD_POS..].to_vec();
+
+        Ok(Self {
             version,
             guardian_set_index,
             timestamp,
             nonce,
             len_signers,
+            signatures,
             emitter_chain,
             emitter_address,
             sequence,
             consistency_level,
             payload,
             hash,
-        }
+        })
     }
 }
 
@@ -126,8 +152,8 @@ struct DeployTokenWh {
 struct LogMetadataWh {
     payload_type: ProofKind,
     token_address: OmniAddress,
-    name: String,
-    symbol: String,
+    name: TokenName,
+    symbol: TokenSymbol,
     decimals: u8,
 }
 
@@ -137,7 +163,7 @@ struct FinTransferWh {
     transfer_id: TransferId,
     token_address: OmniAddress,
     amount: u128,
-    fee_recipient: String,
+    fee_recipient: BoundedString<MAX_ACCOUNT_ID_LEN>,
 }
 
 #[derive(Debug, BorshDeserialize)]
@@ -154,13 +180,14 @@ struct InitTransferWh {
 }
 
 impl TryInto<InitTransferMessage> for ParsedVAA {
-    type Error = String;
+    type Error = ProverError;
 
-    fn try_into(self) -> Result<InitTransferMessage, String> {
-        let transfer: InitTransferWh = borsh::from_slice(&self.payload).map_err(stringify)?;
+    fn try_into(self) -> Result<InitTransferMessage, ProverError> {
+        let transfer: InitTransferWh =
+            borsh::from_slice(&self.payload).map_err(|_| ProverError::InvalidVaa)?;
 
         if transfer.payload_type != ProofKind::InitTransfer {
-            return Err("Invalid proof kind".to_owned());
+            return Err(ProverError::InvalidProofKind);
         }
 
         Ok(InitTransferMessage {
@@ -170,71 +197,89 @@ impl TryInto<InitTransferMessage> for ParsedVAA {
                 fee: transfer.fee.into(),
                 native_fee: transfer.native_fee.into(),
             },
-            recipient: transfer.recipient.parse().map_err(stringify)?,
+            recipient: transfer
+                .recipient
+                .parse()
+                .map_err(|_| ProverError::InvalidVaa)?,
             origin_nonce: transfer.origin_nonce,
             sender: transfer.sender,
             msg: transfer.message,
             emitter_address: OmniAddress::new_from_slice(
                 transfer.token_address.get_chain(),
                 &self.emitter_address,
-            )?,
+            )
+            .map_err(|_| ProverError::InvalidVaa)?,
         })
     }
 }
 
 impl TryInto<FinTransferMessage> for ParsedVAA {
-    type Error = String;
+    type Error = ProverError;
 
-    fn try_into(self) -> Result<FinTransferMessage, String> {
-        let transfer: FinTransferWh = borsh::from_slice(&self.payload).map_err(stringify)?;
+    fn try_into(self) -> Result<FinTransferMessage, ProverError> {
+        let mut payload = self.payload.as_slice();
+        let transfer =
+            FinTransferWh::deserialize(&mut payload).map_err(|_| ProverError::InvalidVaa)?;
 
         if transfer.payload_type != ProofKind::FinTransfer {
-            return Err("Invalid proof kind".to_owned());
+            return Err(ProverError::InvalidProofKind);
+        }
+
+        // Newer emitters append the global transfer hash
+        if !payload.is_empty() && payload != transfer.transfer_id.global_hash().0 {
+            return Err(ProverError::InvalidVaa);
         }
 
         Ok(FinTransferMessage {
             transfer_id: transfer.transfer_id,
-            fee_recipient: transfer.fee_recipient.parse().ok(),
+            fee_recipient: transfer.fee_recipient.as_str().parse().ok(),
             amount: transfer.amount.into(),
             emitter_address: OmniAddress::new_from_slice(
                 transfer.token_address.get_chain(),
                 &self.emitter_address,
-            )?,
+            )
+            .map_err(|_| ProverError::InvalidVaa)?,
         })
     }
 }
 
 impl TryInto<DeployTokenMessage> for ParsedVAA {
-    type Error = String;
+    type Error = ProverError;
 
-    fn try_into(self) -> Result<DeployTokenMessage, String> {
-        let parsed_payload: DeployTokenWh = borsh::from_slice(&self.payload).map_err(stringify)?;
+    fn try_into(self) -> Result<DeployTokenMessage, ProverError> {
+        let parsed_payload: DeployTokenWh =
+            borsh::from_slice(&self.payload).map_err(|_| ProverError::InvalidVaa)?;
 
         if parsed_payload.payload_type != ProofKind::DeployToken {
-            return Err("Invalid proof kind".to_owned());
+            return Err(ProverError::InvalidProofKind);
         }
 
         Ok(DeployTokenMessage {
-            token: parsed_payload.token.parse().map_err(stringify)?,
+            token: parsed_payload
+                .token
+                .parse()
+                .map_err(|_| ProverError::InvalidVaa)?,
             token_address: parsed_payload.token_address.clone(),
             decimals: parsed_payload.decimals,
             origin_decimals: parsed_payload.origin_decimals,
             emitter_address: OmniAddress::new_from_slice(
                 parsed_payload.token_address.get_chain(),
                 &self.emitter_address,
-            )?,
+            )
+            .map_err(|_| ProverError::InvalidVaa)?,
         })
     }
 }
 
 impl TryInto<LogMetadataMessage> for ParsedVAA {
-    type Error = String;
+    type Error = ProverError;
 
-    fn try_into(self) -> Result<LogMetadataMessage, String> {
-        let parsed_payload: LogMetadataWh = borsh::from_slice(&self.payload).map_err(stringify)?;
+    fn try_into(self) -> Result<LogMetadataMessage, ProverError> {
+        let parsed_payload: LogMetadataWh =
+            borsh::from_slice(&self.payload).map_err(|_| ProverError::InvalidVaa)?;
 
         if parsed_payload.payload_type != ProofKind::LogMetadata {
-            return Err("Invalid proof kind".to_owned());
+            return Err(ProverError::InvalidProofKind);
         }
 
         let chain_kind = parsed_payload.token_address.get_chain();
@@ -243,7 +288,12 @@ impl TryInto<LogMetadataMessage> for ParsedVAA {
             name: parsed_payload.name,
             symbol: parsed_payload.symbol,
             decimals: parsed_payload.decimals,
-            emitter_address: OmniAddress::new_from_slice(chain_kind, &self.emitter_address)?,
+            emitter_address: OmniAddress::new_from_slice(chain_kind, &self.emitter_address)
+                .map_err(|_| ProverError::InvalidVaa)?,
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            sequence: Some(self.sequence),
         })
     }
 }
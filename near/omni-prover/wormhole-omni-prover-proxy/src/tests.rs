@@ -0,0 +1,426 @@
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use near_sdk::test_utils::{get_logs, VMContextBuilder};
+use near_sdk::{testing_env, PromiseOrValue};
+use omni_types::errors::ProverError;
+use omni_types::prover_args::WormholeVerifyProofArgs;
+use omni_types::prover_result::{FinTransferMessage, ProofKind, ProverResult};
+use omni_types::utils::keccak256;
+use omni_types::{ChainKind, Nonce, OmniAddress, TransferId, H160};
+
+use crate::guardian_set::{GuardianSet, GOVERNANCE_EMITTER, GUARDIAN_SET_EXPIRATION_SEC};
+use crate::parsed_vaa::ParsedVAA;
+use crate::{
+    VerificationMode, WormholeChainConfig, WormholeOmniProverProxy, WormholeProverEvent,
+    WORMHOLE_FOGO_CHAIN_ID, WORMHOLE_SOLANA_CHAIN_ID,
+};
+
+const NOW_SEC: u64 = 1_700_000_000;
+
+fn set_block_timestamp(sec: u64) {
+    testing_env!(VMContextBuilder::new()
+        .block_timestamp(sec * 1_000_000_000)
+        .build());
+}
+
+fn guardian_keys(count: u8, seed: u8) -> Vec<SigningKey> {
+    (0..count)
+        .map(|i| SigningKey::from_slice(&[seed.wrapping_add(i); 32]).unwrap())
+        .collect()
+}
+
+fn guardian_address(key: &SigningKey) -> H160 {
+    let point = key.verifying_key().to_encoded_point(false);
+    H160(keccak256(&point.as_bytes()[1..])[12..].try_into().unwrap())
+}
+
+fn vaa_body(emitter_chain: u16, emitter_address: [u8; 32], payload: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    body.extend_from_slice(&1_u32.to_be_bytes());
+    body.extend_from_slice(&0_u32.to_be_bytes());
+    body.extend_from_slice(&emitter_chain.to_be_bytes());
+    body.extend_from_slice(&emitter_address);
+    body.extend_from_slice(&7_u64.to_be_bytes());
+    body.push(1);
+    body.extend_from_slice(payload);
+    body
+}
+
+fn sign(key: &SigningKey, body: &[u8]) -> [u8; 65] {
+    let digest = keccak256(&keccak256(body));
+    let (signature, recovery_id) = key.sign_prehash_recoverable(&digest).unwrap();
+    let mut result = [0; 65];
+    result[..64].copy_from_slice(&signature.to_bytes());
+    result[64] = recovery_id.to_byte();
+    result
+}
+
+fn encode_vaa(guardian_set_index: u32, signatures: &[(u8, [u8; 65])], body: &[u8]) -> Vec<u8> {
+    let mut data = vec![1];
+    data.extend_from_slice(&guardian_set_index.to_be_bytes());
+    data.push(u8::try_from(signatures.len()).unwrap());
+    for (index, signature) in signatures {
+        data.push(*index);
+        data.extend_from_slice(signature);
+    }
+    data.extend_from_slice(body);
+    data
+}
+
+fn signed_vaa(
+    guardian_set_index: u32,
+    keys: &[SigningKey],
+    signers: &[u8],
+    body: &[u8],
+) -> Vec<u8> {
+    let signatures: Vec<_> = signers
+        .iter()
+        .map(|&i| (i, sign(&keys[usize::from(i)], body)))
+        .collect();
+    encode_vaa(guardian_set_index, &signatures, body)
+}
+
+fn init_transfer_payload() -> Vec<u8> {
+    let sender: OmniAddress = "sol:So11111111111111111111111111111111111111112"
+        .parse()
+        .unwrap();
+    let token_address: OmniAddress = "sol:11111111111111111111111111111111".parse().unwrap();
+    let origin_nonce: Nonce = 42;
+    borsh::to_vec(&(
+        ProofKind::InitTransfer,
+        sender,
+        token_address,
+        origin_nonce,
+        1_000_u128,
+        10_u128,
+        5_u128,
+        "near:frolik.testnet".to_string(),
+        String::new(),
+    ))
+    .unwrap()
+}
+
+fn fin_transfer_payload(transfer_id: TransferId) -> Vec<u8> {
+    let token_address: OmniAddress = "sol:11111111111111111111111111111111".parse().unwrap();
+    borsh::to_vec(&(
+        ProofKind::FinTransfer,
+        transfer_id,
+        token_address,
+        1_000_u128,
+        "relayer.testnet".to_string(),
+    ))
+    .unwrap()
+}
+
+fn guardian_set_upgrade_body(new_index: u32, keys: &[SigningKey]) -> Vec<u8> {
+    let mut payload = vec![0; 28];
+    payload.extend_from_slice(b"Core");
+    payload.push(2);
+    payload.extend_from_slice(&0_u16.to_be_bytes());
+    payload.extend_from_slice(&new_index.to_be_bytes());
+    payload.push(u8::try_from(keys.len()).unwrap());
+    for key in keys {
+        payload.extend_from_slice(&guardian_address(key).0);
+    }
+    vaa_body(1, GOVERNANCE_EMITTER, &payload)
+}
+
+fn guardian_set(keys: &[SigningKey]) -> GuardianSet {
+    GuardianSet {
+        keys: keys.iter().map(guardian_address).collect(),
+        expiration_time: 0,
+    }
+}
+
+fn contract(keys: &[SigningKey]) -> WormholeOmniProverProxy {
+    set_block_timestamp(NOW_SEC);
+    let mut contract = WormholeOmniProverProxy::init("wormhole.testnet".parse().unwrap());
+    contract.init_guardian_set(0, keys.iter().map(guardian_address).collect());
+    contract.set_verification_mode(VerificationMode::InContract);
+    contract
+}
+
+fn vaa_verified_log(emitter_chain: u16) -> String {
+    WormholeProverEvent::VaaVerifiedEvent {
+        emitter_chain,
+        emitter_address: hex::encode([7; 32]),
+        sequence: 7,
+    }
+    .to_log_string()
+}
+
+fn verify_proof(contract: &WormholeOmniProverProxy, vaa: &[u8]) -> Result<ProverResult, String> {
+    let input = borsh::to_vec(&WormholeVerifyProofArgs {
+        proof_kind: ProofKind::InitTransfer,
+        vaa: hex::encode(vaa),
+    })
+    .unwrap();
+    match contract.verify_proof(input)? {
+        PromiseOrValue::Value(result) => Ok(result),
+        PromiseOrValue::Promise(_) => panic!("expected in-contract verification"),
+    }
+}
+
+#[test]
+fn test_quorum() {
+    for (count, quorum) in [(1, 1), (3, 3), (4, 3), (19, 13)] {
+        let set = GuardianSet {
+            keys: vec![H160([0; 20]); count],
+            expiration_time: 0,
+        };
+        assert_eq!(set.quorum(), quorum);
+    }
+}
+
+#[test]
+fn test_guardian_set_verify() {
+    set_block_timestamp(NOW_SEC);
+    let keys = guardian_keys(4, 1);
+    let set = guardian_set(&keys);
+    let body = vaa_body(WORMHOLE_SOLANA_CHAIN_ID, [7; 32], &init_transfer_payload());
+
+    let vaa = ParsedVAA::parse(&signed_vaa(0, &keys, &[0, 1, 3], &body)).unwrap();
+    assert_eq!(set.verify(&vaa), Ok(()));
+
+    let vaa = ParsedVAA::parse(&signed_vaa(0, &keys, &[0, 1], &body)).unwrap();
+    assert_eq!(set.verify(&vaa), Err(ProverError::NoQuorum));
+
+    let vaa = ParsedVAA::parse(&signed_vaa(0, &keys, &[0, 1, 1], &body)).unwrap();
+    assert_eq!(set.verify(&vaa), Err(ProverError::InvalidSignature));
+
+    let vaa = ParsedVAA::parse(&signed_vaa(0, &keys, &[1, 0, 2], &body)).unwrap();
+    assert_eq!(set.verify(&vaa), Err(ProverError::InvalidSignature));
+
+    let other_keys = guardian_keys(4, 100);
+    let vaa = ParsedVAA::parse(&signed_vaa(0, &other_keys, &[0, 1, 2], &body)).unwrap();
+    assert_eq!(set.verify(&vaa), Err(ProverError::InvalidSignature));
+
+    let mut tampered = signed_vaa(0, &keys, &[0, 1, 2], &body);
+    *tampered.last_mut().unwrap() ^= 1;
+    let vaa = ParsedVAA::parse(&tampered).unwrap();
+    assert_eq!(set.verify(&vaa), Err(ProverError::InvalidSignature));
+}
+
+#[test]
+fn test_in_contract_verify_proof() {
+    let keys = guardian_keys(4, 1);
+    let contract = contract(&keys);
+    let body = vaa_body(WORMHOLE_SOLANA_CHAIN_ID, [7; 32], &init_transfer_payload());
+
+    let ProverResult::InitTransfer(message) =
+        verify_proof(&contract, &signed_vaa(0, &keys, &[0, 2, 3], &body)).unwrap()
+    else {
+        panic!("expected InitTransfer");
+    };
+    assert_eq!(message.origin_nonce, 42);
+    assert_eq!(message.amount.0, 1_000);
+    assert!(get_logs().contains(&vaa_verified_log(WORMHOLE_SOLANA_CHAIN_ID)));
+
+    assert_eq!(
+        verify_proof(&contract, &signed_vaa(0, &keys, &[0, 2], &body)).unwrap_err(),
+        ProverError::NoQuorum.to_string()
+    );
+    assert_eq!(
+        verify_proof(&contract, &signed_vaa(1, &keys, &[0, 2, 3], &body)).unwrap_err(),
+        ProverError::GuardianSetNotFound.to_string()
+    );
+}
+
+#[test]
+fn test_fin_transfer_global_hash_suffix() {
+    set_block_timestamp(NOW_SEC);
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Near,
+        origin_nonce: 42,
+    };
+    let parse = |payload: &[u8]| -> Result<FinTransferMessage, ProverError> {
+        ParsedVAA::parse(&encode_vaa(
+            0,
+            &[],
+            &vaa_body(WORMHOLE_SOLANA_CHAIN_ID, [7; 32], payload),
+        ))
+        .unwrap()
+        .try_into()
+    };
+
+    let payload = fin_transfer_payload(transfer_id);
+    assert_eq!(parse(&payload).unwrap().transfer_id, transfer_id);
+
+    let mut with_hash = payload.clone();
+    with_hash.extend_from_slice(&transfer_id.global_hash().0);
+    assert_eq!(parse(&with_hash).unwrap().transfer_id, transfer_id);
+
+    let mut wrong_hash = payload.clone();
+    wrong_hash.extend_from_slice(&[1; 32]);
+    assert_eq!(parse(&wrong_hash).unwrap_err(), ProverError::InvalidVaa);
+
+    let mut truncated_hash = payload;
+    truncated_hash.extend_from_slice(&transfer_id.global_hash().0[..31]);
+    assert_eq!(parse(&truncated_hash).unwrap_err(), ProverError::InvalidVaa);
+}
+
+#[test]
+fn test_guardian_set_upgrade() {
+    let keys = guardian_keys(4, 1);
+    let new_keys = guardian_keys(3, 50);
+    let mut contract = contract(&keys);
+
+    let upgrade = signed_vaa(
+        0,
+        &keys,
+        &[0, 1, 2],
+        &guardian_set_upgrade_body(1, &new_keys),
+    );
+    contract
+        .submit_guardian_set_upgrade(hex::encode(&upgrade))
+        .unwrap();
+
+    assert_eq!(contract.get_current_guardian_set_index(), Some(1));
+    assert_eq!(contract.get_guardian_set(1), Some(&guardian_set(&new_keys)));
+    assert_eq!(
+        contract.get_guardian_set(0).unwrap().expiration_time,
+        NOW_SEC + GUARDIAN_SET_EXPIRATION_SEC
+    );
+
+    // Replaying the upgrade signed by the previous set must fail.
+    assert_eq!(
+        contract
+            .submit_guardian_set_upgrade(hex::encode(&upgrade))
+            .unwrap_err(),
+        ProverError::InvalidGovernanceVaa.to_string()
+    );
+
+    // Skipping a guardian set index must fail.
+    let skip = signed_vaa(
+        1,
+        &new_keys,
+        &[0, 1, 2],
+        &guardian_set_upgrade_body(3, &keys),
+    );
+    assert_eq!(
+        contract
+            .submit_guardian_set_upgrade(hex::encode(skip))
+            .unwrap_err(),
+        ProverError::InvalidGovernanceVaa.to_string()
+    );
+
+    // Upgrades must be emitted by the governance contract.
+    let mut body = guardian_set_upgrade_body(2, &keys);
+    body[10] ^= 1;
+    let forged = signed_vaa(1, &new_keys, &[0, 1, 2], &body);
+    assert_eq!(
+        contract
+            .submit_guardian_set_upgrade(hex::encode(forged))
+            .unwrap_err(),
+        ProverError::InvalidGovernanceVaa.to_string()
+    );
+}
+
+#[test]
+fn test_expired_guardian_set() {
+    let keys = guardian_keys(4, 1);
+    let new_keys = guardian_keys(3, 50);
+    let mut contract = contract(&keys);
+
+    let upgrade = signed_vaa(
+        0,
+        &keys,
+        &[0, 1, 2],
+        &guardian_set_upgrade_body(1, &new_keys),
+    );
+    contract
+        .submit_guardian_set_upgrade(hex::encode(upgrade))
+        .unwrap();
+
+    let body = vaa_body(WORMHOLE_SOLANA_CHAIN_ID, [7; 32], &init_transfer_payload());
+    let old_vaa = signed_vaa(0, &keys, &[0, 1, 2], &body);
+
+    set_block_timestamp(NOW_SEC + GUARDIAN_SET_EXPIRATION_SEC);
+    verify_proof(&contract, &old_vaa).unwrap();
+
+    set_block_timestamp(NOW_SEC + GUARDIAN_SET_EXPIRATION_SEC + 1);
+    assert_eq!(
+        verify_proof(&contract, &old_vaa).unwrap_err(),
+        ProverError::GuardianSetExpired.to_string()
+    );
+    verify_proof(&contract, &signed_vaa(1, &new_keys, &[0, 1, 2], &body)).unwrap();
+}
+
+#[test]
+fn test_duplicate_vaa_is_logged() {
+    let keys = guardian_keys(4, 1);
+    let contract = contract(&keys);
+    let body = vaa_body(WORMHOLE_SOLANA_CHAIN_ID, [7; 32], &init_transfer_payload());
+
+    // Replays are rejected by the bridge, so a resubmitted VAA still verifies.
+    verify_proof(&contract, &signed_vaa(0, &keys, &[0, 1, 2], &body)).unwrap();
+    verify_proof(&contract, &signed_vaa(0, &keys, &[1, 2, 3], &body)).unwrap();
+
+    let expected_log = vaa_verified_log(WORMHOLE_SOLANA_CHAIN_ID);
+    assert_eq!(
+        get_logs()
+            .iter()
+            .filter(|log| **log == expected_log)
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_emitter_chain_mismatch() {
+    let keys = guardian_keys(4, 1);
+    let contract = contract(&keys);
+
+    let body = vaa_body(WORMHOLE_FOGO_CHAIN_ID, [7; 32], &init_transfer_payload());
+    assert_eq!(
+        verify_proof(&contract, &signed_vaa(0, &keys, &[0, 1, 2], &body)).unwrap_err(),
+        ProverError::ChainMismatch.to_string()
+    );
+    assert!(!get_logs().contains(&vaa_verified_log(WORMHOLE_FOGO_CHAIN_ID)));
+
+    let body = vaa_body(2, [7; 32], &init_transfer_payload());
+    assert_eq!(
+        verify_proof(&contract, &signed_vaa(0, &keys, &[0, 1, 2], &body)).unwrap_err(),
+        ProverError::UnsupportedChain.to_string()
+    );
+}
+
+#[test]
+fn test_consistency_level_mismatch() {
+    let keys = guardian_keys(4, 1);
+    let mut contract = contract(&keys);
+
+    let mut body = vaa_body(WORMHOLE_SOLANA_CHAIN_ID, [7; 32], &init_transfer_payload());
+    body[50] = 0;
+    let vaa = signed_vaa(0, &keys, &[0, 1, 2], &body);
+    assert_eq!(
+        verify_proof(&contract, &vaa).unwrap_err(),
+        ProverError::FinalityMismatch.to_string()
+    );
+    assert!(!get_logs().contains(&vaa_verified_log(WORMHOLE_SOLANA_CHAIN_ID)));
+
+    contract.set_chain(
+        WORMHOLE_SOLANA_CHAIN_ID,
+        WormholeChainConfig {
+            chain_kind: ChainKind::Sol,
+            consistency_level: 0,
+        },
+    );
+    verify_proof(&contract, &vaa).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "ERR_GUARDIAN_SET_EXISTS")]
+fn test_init_guardian_set_only_once() {
+    let keys = guardian_keys(4, 1);
+    let mut contract = contract(&keys);
+    contract.init_guardian_set(5, vec![guardian_address(&keys[0])]);
+}
+
+#[test]
+#[should_panic(expected = "ERR_GUARDIAN_SET_NOT_FOUND")]
+fn test_in_contract_mode_requires_guardian_set() {
+    set_block_timestamp(NOW_SEC);
+    let mut contract = WormholeOmniProverProxy::init("wormhole.testnet".parse().unwrap());
+    contract.set_verification_mode(VerificationMode::InContract);
+}
@@ -11,6 +11,7 @@ use near_workspaces::{network::Sandbox, Account, Contract, Worker};
 use omni_types::{
     locker_args::{FinTransferArgs, StorageDepositAction},
     prover_result::{InitTransferMessage, ProverResult},
+    utxo::UtxoNetwork,
     BasicMetadata, ChainKind, Fee, OmniAddress,
 };
 
@@ -196,9 +197,12 @@ impl TestEnvBuilder {
 
         let init_token_address = OmniAddress::new_zero(ChainKind::Eth).unwrap();
         let token_metadata = BasicMetadata {
-            name: "ETH from Ethereum".to_string(),
-            symbol: "ETH".to_string(),
+            name: "ETH from Ethereum".parse().unwrap(),
+            symbol: "ETH".parse().unwrap(),
             decimals: 18,
+            icon: None,
+            reference: None,
+            reference_hash: None,
         };
 
         let required_storage: NearToken = bridge_contract
@@ -287,9 +291,12 @@ impl TestEnvBuilder {
                 &eth_token_address(),
                 &eth_factory_address(),
                 &BasicMetadata {
-                    name: "Test Token".to_string(),
-                    symbol: "TEST".to_string(),
+                    name: "Test Token".parse().unwrap(),
+                    symbol: "TEST".parse().unwrap(),
                     decimals: 18,
+                    icon: None,
+                    reference: None,
+                    reference_hash: None,
                 },
             ))
             .deposit(required_storage)
@@ -365,6 +372,7 @@ impl TestEnvBuilder {
                 "utxo_chain_connector_id": utxo_connector.id(),
                 "utxo_chain_token_id": token_contract.id(),
                 "decimals": 8,
+                "network": UtxoNetwork::Mainnet,
             }))
             .deposit(NEP141_DEPOSIT.saturating_mul(3))
             .max_gas()
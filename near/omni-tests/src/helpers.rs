@@ -2,10 +2,11 @@
 pub mod tests {
     use std::path::Path;
 
-    use near_sdk::{borsh, json_types::U128, serde_json, AccountId, CryptoHash};
+    use near_sdk::{borsh, json_types::U128, AccountId, CryptoHash};
     use near_workspaces::{result::ExecutionSuccess, types::NearToken};
     use omni_types::{
         locker_args::{BindTokenArgs, ClaimFeeArgs, DeployTokenArgs},
+        near_events::OmniBridgeEvent,
         prover_result::{DeployTokenMessage, FinTransferMessage, LogMetadataMessage, ProverResult},
         BasicMetadata, ChainKind, Nonce, OmniAddress, TransferId,
     };
@@ -199,6 +200,12 @@ pub mod tests {
             .unwrap()
     }
 
+    pub fn sui_factory_address() -> OmniAddress {
+        "sui:0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf"
+            .parse()
+            .unwrap()
+    }
+
     pub fn sol_factory_address() -> OmniAddress {
         "sol:11111111111111111111111111111111".parse().unwrap()
     }
@@ -286,6 +293,10 @@ pub mod tests {
             symbol: token_metadata.symbol.clone(),
             decimals: token_metadata.decimals,
             emitter_address: factory_contract_address.clone(),
+            icon: token_metadata.icon.clone(),
+            reference: token_metadata.reference.clone(),
+            reference_hash: token_metadata.reference_hash.clone(),
+            sequence: None,
         };
 
         let prover_result = ProverResult::LogMetadata(log_metadata_message);
@@ -321,14 +332,12 @@ pub mod tests {
         }
     }
 
-    pub fn get_event_data(
-        event_name: &str,
-        logs: &Vec<&String>,
-    ) -> anyhow::Result<Option<serde_json::Value>> {
+    /// Decodes the first bridge event named `event_name` (e.g. `init_transfer_event`),
+    /// accepting both NEP-297 and legacy logs.
+    pub fn get_bridge_event(event_name: &str, logs: &[&String]) -> Option<OmniBridgeEvent> {
         logs.iter()
-            .find(|log| log.contains(event_name))
-            .map(|log| serde_json::from_str(log).map_err(anyhow::Error::from))
-            .transpose()
+            .filter_map(|log| OmniBridgeEvent::from_log_string(log).ok())
+            .find(|event| event.event_name() == event_name)
     }
 
     /// Returns `true` if any log emitted across all receipt outcomes of `result`
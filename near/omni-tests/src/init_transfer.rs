@@ -7,9 +7,9 @@ mod tests {
     };
     use near_workspaces::{result::ExecutionSuccess, types::NearToken, AccountId};
     use omni_types::{
-        near_events::OmniBridgeEvent, BoundedString, BridgeOnTransferMsg, ChainKind, Fee,
-        InitTransferMsg, OmniAddress, TransferId, TransferMessage, TransferMessageStorageAccount,
-        UpdateFee,
+        near_events::OmniBridgeEvent, utxo::UtxoNetwork, BoundedString, BridgeOnTransferMsg,
+        ChainKind, Fee, InitTransferMsg, OmniAddress, ResolvedTransfer, TransferId,
+        TransferMessage, TransferMessageStorageAccount, UpdateFee,
     };
     use rstest::rstest;
 
@@ -17,7 +17,7 @@ mod tests {
         environment::TestEnvBuilder,
         helpers::tests::{
             account_n, build_artifacts, eth_eoa_address, eth_factory_address,
-            execution_contains_log, get_claim_fee_args_near, get_event_data, relayer_account_id,
+            execution_contains_log, get_bridge_event, get_claim_fee_args_near, relayer_account_id,
             BuildArtifacts,
         },
     };
@@ -80,7 +80,7 @@ mod tests {
         custom_deposit: Option<NearToken>,
         update_fee: Option<UpdateFee>,
         is_relayer_sign: bool,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<TransferMessage> {
         let storage_deposit_amount = get_balance_required_for_account(
             &env.locker_contract,
             &env.sender_account,
@@ -179,7 +179,7 @@ mod tests {
 
         // Wait for async gas-refund receipts to settle before the fee assertions read the balance.
         settle_relayer_gas_refunds(env).await?;
-        Ok(())
+        Ok(transfer_message)
     }
 
     /// Advance blocks until the relayer's balance stops changing.
@@ -251,10 +251,8 @@ mod tests {
             .flat_map(|outcome| &outcome.logs)
             .collect::<Vec<_>>();
 
-        let omni_bridge_event: OmniBridgeEvent = serde_json::from_value(
-            get_event_data("InitTransferEvent", &logs)?
-                .ok_or_else(|| anyhow::anyhow!("InitTransferEvent not found"))?,
-        )?;
+        let omni_bridge_event = get_bridge_event("init_transfer_event", &logs)
+            .ok_or_else(|| anyhow::anyhow!("InitTransferEvent not found"))?;
         let OmniBridgeEvent::InitTransferEvent { transfer_message } = omni_bridge_event else {
             anyhow::bail!("InitTransferEvent is found in unexpected event")
         };
@@ -374,6 +372,39 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_resolve_global_transfer_hash_after_claim_fee(
+        build_artifacts: &BuildArtifacts,
+    ) -> anyhow::Result<()> {
+        let init_transfer_msg = InitTransferMsg {
+            native_token_fee: U128(0),
+            fee: U128(1000),
+            recipient: eth_eoa_address(),
+            msg: None,
+            external_id: None,
+        };
+
+        let env = TestEnv::new(1_000_000, false, build_artifacts).await?;
+
+        let transfer_message =
+            init_transfer_flow_on_near(&env, 5000, init_transfer_msg, None, None, true).await?;
+
+        let resolved: Option<ResolvedTransfer> = env
+            .locker_contract
+            .view("resolve_global_transfer_hash")
+            .args_json(json!({
+                "global_transfer_hash": transfer_message.get_transfer_id().global_hash(),
+            }))
+            .await?
+            .json()?;
+        assert!(
+            resolved.is_none(),
+            "Global transfer hash was not removed with the claimed transfer"
+        );
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_transfer_fee(build_artifacts: &BuildArtifacts) -> anyhow::Result<()> {
@@ -1008,6 +1039,7 @@ mod tests {
         let res = env
             .locker_contract
             .call("migrate")
+            .args_json(json!({ "utxo_network": UtxoNetwork::Testnet }))
             .max_gas()
             .transact()
             .await?;
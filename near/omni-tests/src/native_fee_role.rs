@@ -9,7 +9,7 @@ mod tests {
     use rstest::rstest;
 
     use crate::helpers::tests::{
-        account_n, eth_eoa_address, eth_factory_address, get_event_data, locker_wasm,
+        account_n, eth_eoa_address, eth_factory_address, get_bridge_event, locker_wasm,
         mock_prover_wasm, mock_token_wasm, NEP141_DEPOSIT,
     };
 
@@ -267,10 +267,8 @@ mod tests {
 
             let log_refs = logs.iter().collect::<Vec<&String>>();
 
-            let omni_bridge_event: OmniBridgeEvent = serde_json::from_value(
-                get_event_data("InitTransferEvent", &log_refs)?
-                    .ok_or_else(|| anyhow::anyhow!("InitTransferEvent not found"))?,
-            )?;
+            let omni_bridge_event = get_bridge_event("init_transfer_event", &log_refs)
+                .ok_or_else(|| anyhow::anyhow!("InitTransferEvent not found"))?;
 
             let OmniBridgeEvent::InitTransferEvent { transfer_message } = omni_bridge_event else {
                 anyhow::bail!("InitTransferEvent is found in unexpected event")
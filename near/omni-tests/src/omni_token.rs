@@ -2,24 +2,29 @@
 mod tests {
     use std::str::FromStr;
 
-    use near_sdk::json_types::{Base64VecU8, U128};
+    use near_sdk::json_types::{Base64VecU8, U128, U64};
     use near_sdk::serde_json::json;
     use near_sdk::{borsh, json_types::Base58CryptoHash, CryptoHash};
-    use near_workspaces::{types::NearToken, AccountId};
+    use near_workspaces::{result::ExecutionSuccess, types::NearToken, AccountId};
     use omni_types::locker_args::{FinTransferArgs, StorageDepositAction};
+    use omni_types::near_events::OmniBridgeEvent;
     use omni_types::prover_result::InitTransferMessage;
     use omni_types::prover_result::ProverResult;
     use omni_types::Fee;
-    use omni_types::{BasicMetadata, ChainKind, OmniAddress};
+    use omni_types::{
+        parse_token_icon, BasicMetadata, ChainKind, OmniAddress, MAX_TOKEN_ICON_LEN,
+        MAX_TOKEN_NAME_LEN, MAX_TOKEN_REFERENCE_LEN, MAX_TOKEN_SYMBOL_LEN,
+    };
     use rstest::rstest;
 
     use crate::helpers::tests::{
         abs_factory_address, account_n, aptos_factory_address, arb_factory_address,
         arb_token_address, base_factory_address, base_token_address, bnb_factory_address,
         bnb_token_address, eth_eoa_address, eth_factory_address, eth_token_address,
-        fogo_factory_address, get_test_deploy_token_args, hyperevm_factory_address, locker_wasm,
-        mock_global_contract_deployer_wasm, mock_prover_wasm, omni_token_wasm, pol_factory_address,
-        sol_factory_address, sol_token_address, strk_factory_address, token_deployer_wasm,
+        fogo_factory_address, get_bridge_event, get_test_deploy_token_args,
+        hyperevm_factory_address, locker_wasm, mock_global_contract_deployer_wasm,
+        mock_prover_wasm, omni_token_wasm, pol_factory_address, sol_factory_address,
+        sol_token_address, strk_factory_address, sui_factory_address, token_deployer_wasm,
         wasm_code_hash, GLOBAL_STORAGE_COST_PER_BYTE, NEP141_DEPOSIT, STORAGE_DEPOSIT_PER_BYTE,
     };
 
@@ -29,6 +34,8 @@ mod tests {
     enum DepositStrategy {
         MinimumRequired,
         WithBuffer,
+        /// Minimum required balance without the storage of the longest icon and reference
+        WithoutIconAndReference,
     }
 
     struct TestEnv {
@@ -144,6 +151,7 @@ mod tests {
                 ChainKind::HyperEvm => hyperevm_factory_address(),
                 ChainKind::Strk => strk_factory_address(),
                 ChainKind::Aptos => aptos_factory_address(),
+                ChainKind::Sui => sui_factory_address(),
                 ChainKind::Near | ChainKind::Btc | ChainKind::Zcash => panic!("Unsupported chain"),
             };
 
@@ -200,9 +208,12 @@ mod tests {
     impl TestEnv {
         fn default_token_metadata() -> BasicMetadata {
             BasicMetadata {
-                name: "Test Token".to_string(),
-                symbol: "TEST".to_string(),
+                name: "Test Token".parse().unwrap(),
+                symbol: "TEST".parse().unwrap(),
                 decimals: 18,
+                icon: None,
+                reference: None,
+                reference_hash: None,
             }
         }
 
@@ -226,20 +237,55 @@ mod tests {
             .await
         }
 
+        fn max_token_metadata() -> BasicMetadata {
+            let icon_prefix = "data:image/svg+xml;base64,";
+            let max_icon = format!(
+                "{icon_prefix}{}",
+                "A".repeat(MAX_TOKEN_ICON_LEN - icon_prefix.len())
+            );
+            let reference_prefix = "https://";
+            let max_reference = format!(
+                "{reference_prefix}{}",
+                "r".repeat(MAX_TOKEN_REFERENCE_LEN - reference_prefix.len())
+            );
+
+            BasicMetadata {
+                name: "N".repeat(MAX_TOKEN_NAME_LEN).parse().unwrap(),
+                symbol: "S".repeat(MAX_TOKEN_SYMBOL_LEN).parse().unwrap(),
+                decimals: 18,
+                icon: parse_token_icon(&max_icon),
+                reference: Some(max_reference.parse().unwrap()),
+                reference_hash: None,
+            }
+        }
+
+        fn metadata_len(token_metadata: &BasicMetadata) -> usize {
+            token_metadata
+                .name
+                .as_str()
+                .len()
+                .saturating_add(token_metadata.symbol.as_str().len())
+                .saturating_add(
+                    token_metadata
+                        .icon
+                        .as_ref()
+                        .map_or(0, |icon| icon.as_str().len()),
+                )
+                .saturating_add(
+                    token_metadata
+                        .reference
+                        .as_ref()
+                        .map_or(0, |reference| reference.as_str().len()),
+                )
+        }
+
         fn deposit_with_metadata_buffer(
             required_storage: NearToken,
             token_metadata: &BasicMetadata,
             omni_token_wasm_len: usize,
         ) -> NearToken {
-            let base_metadata = Self::default_token_metadata();
-            let base_len = base_metadata
-                .name
-                .len()
-                .saturating_add(base_metadata.symbol.len());
-            let metadata_len = token_metadata
-                .name
-                .len()
-                .saturating_add(token_metadata.symbol.len());
+            let base_len = Self::metadata_len(&Self::default_token_metadata());
+            let metadata_len = Self::metadata_len(token_metadata);
             let metadata_delta = metadata_len.saturating_sub(base_len);
 
             let code_storage_deposit = STORAGE_DEPOSIT_PER_BYTE
@@ -385,6 +431,13 @@ mod tests {
                     omni_token_wasm_len,
                 ),
                 DepositStrategy::MinimumRequired => required_storage,
+                DepositStrategy::WithoutIconAndReference => required_storage.saturating_sub(
+                    STORAGE_DEPOSIT_PER_BYTE.saturating_mul(
+                        (MAX_TOKEN_ICON_LEN + MAX_TOKEN_REFERENCE_LEN)
+                            .try_into()
+                            .unwrap_or_default(),
+                    ),
+                ),
             };
 
             if init_token_address == &OmniAddress::new_zero(init_token_address.get_chain()).unwrap()
@@ -516,28 +569,26 @@ mod tests {
 
     #[rstest]
     #[tokio::test]
-    async fn test_deploy_token_with_huge_metadata(
+    async fn test_deploy_token_with_max_metadata(
         mock_prover_wasm: Vec<u8>,
         locker_wasm: Vec<u8>,
         omni_token_wasm: Vec<u8>,
         token_deployer_wasm: Vec<u8>,
         mock_global_contract_deployer_wasm: Vec<u8>,
+        #[values(DepositStrategy::WithBuffer, DepositStrategy::MinimumRequired)]
+        deposit_strategy: DepositStrategy,
     ) -> anyhow::Result<()> {
-        let huge_name = "TEST NAME".repeat(256);
-        let huge_symbol = "TEST".repeat(64);
+        let token_metadata = TestEnv::max_token_metadata();
 
-        let env = TestEnv::new_with_metadata(
+        let env = TestEnv::new_with_metadata_and_strategy(
             eth_token_address(),
-            BasicMetadata {
-                name: huge_name.clone(),
-                symbol: huge_symbol.clone(),
-                decimals: 18,
-            },
+            token_metadata.clone(),
             mock_prover_wasm,
             locker_wasm,
             omni_token_wasm,
             token_deployer_wasm,
             mock_global_contract_deployer_wasm,
+            deposit_strategy,
         )
         .await?;
 
@@ -547,27 +598,25 @@ mod tests {
             .await?
             .json()?;
 
-        assert_eq!(fetched_metadata.name, huge_name);
-        assert_eq!(fetched_metadata.symbol, huge_symbol);
+        assert_eq!(fetched_metadata.name, token_metadata.name);
+        assert_eq!(fetched_metadata.symbol, token_metadata.symbol);
         assert_eq!(fetched_metadata.decimals, 18);
+        assert_eq!(fetched_metadata.icon, token_metadata.icon);
+        assert_eq!(fetched_metadata.reference, token_metadata.reference);
 
         Ok(())
     }
 
     #[rstest]
     #[tokio::test]
-    async fn test_deploy_token_with_huge_metadata_insufficient_deposit(
+    async fn test_deploy_token_with_max_metadata_insufficient_deposit(
         mock_prover_wasm: Vec<u8>,
         locker_wasm: Vec<u8>,
         omni_token_wasm: Vec<u8>,
         token_deployer_wasm: Vec<u8>,
         mock_global_contract_deployer_wasm: Vec<u8>,
     ) -> anyhow::Result<()> {
-        let token_metadata = BasicMetadata {
-            name: "TEST NAME".repeat(256),
-            symbol: "TEST".repeat(64),
-            decimals: 18,
-        };
+        let token_metadata = TestEnv::max_token_metadata();
 
         let deploy_env = DeployEnv::new(
             eth_token_address(),
@@ -581,10 +630,10 @@ mod tests {
         .await?;
 
         let err = match deploy_env
-            .deploy_token(DepositStrategy::MinimumRequired, 2)
+            .deploy_token(DepositStrategy::WithoutIconAndReference, 2)
             .await
         {
-            Ok(_) => panic!("deployment with minimal deposit should fail"),
+            Ok(_) => panic!("deployment with insufficient deposit should fail"),
             Err(err) => err,
         };
 
@@ -865,6 +914,467 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_bridge_out(
+        mock_prover_wasm: Vec<u8>,
+        locker_wasm: Vec<u8>,
+        omni_token_wasm: Vec<u8>,
+        token_deployer_wasm: Vec<u8>,
+        mock_global_contract_deployer_wasm: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let env = TestEnv::new(
+            eth_token_address(),
+            mock_prover_wasm,
+            locker_wasm,
+            omni_token_wasm,
+            token_deployer_wasm,
+            mock_global_contract_deployer_wasm,
+        )
+        .await?;
+        let sender = env.create_registered_account(3).await?;
+        let amount = U128(1_000_000);
+
+        fake_finalize_transfer(
+            &env.locker_contract,
+            &env.token_account_id,
+            &sender,
+            env.init_token_address.clone(),
+            env.factory_contract_address.clone(),
+            amount,
+        )
+        .await?;
+
+        // Rejected by the bridge: tokens are minted back
+        let rejected: bool = sender
+            .call(&env.token_account_id, "bridge_out")
+            .args_json(json!({
+                "amount": amount,
+                "recipient": "near:alice.near",
+                "fee": U128(0),
+                "native_fee": U128(0),
+            }))
+            .deposit(NearToken::from_near(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()?;
+        assert!(!rejected);
+        let balance: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_balance_of")
+            .args_json(json!({ "account_id": sender.id() }))
+            .await?
+            .json()?;
+        assert_eq!(balance, amount);
+
+        let result = sender
+            .call(&env.token_account_id, "bridge_out")
+            .args_json(json!({
+                "amount": amount,
+                "recipient": eth_eoa_address(),
+                "fee": U128(10),
+                "native_fee": U128(0),
+            }))
+            .deposit(NearToken::from_near(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        assert!(result.json::<bool>()?);
+
+        let logs = result
+            .receipt_outcomes()
+            .iter()
+            .flat_map(|outcome| &outcome.logs)
+            .collect::<Vec<_>>();
+        let Some(OmniBridgeEvent::InitTransferEvent { transfer_message }) =
+            get_bridge_event("init_transfer_event", &logs)
+        else {
+            anyhow::bail!("InitTransferEvent not found");
+        };
+        assert_eq!(transfer_message.amount, amount);
+        assert_eq!(transfer_message.fee.fee, U128(10));
+        assert_eq!(transfer_message.recipient, eth_eoa_address());
+        assert_eq!(
+            transfer_message.sender,
+            OmniAddress::Near(sender.id().as_str().parse()?)
+        );
+
+        let balance: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_balance_of")
+            .args_json(json!({ "account_id": sender.id() }))
+            .await?
+            .json()?;
+        assert_eq!(balance, U128(0));
+        let total_supply: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_total_supply")
+            .await?
+            .json()?;
+        assert_eq!(total_supply, U128(0));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_freeze_account(
+        mock_prover_wasm: Vec<u8>,
+        locker_wasm: Vec<u8>,
+        omni_token_wasm: Vec<u8>,
+        token_deployer_wasm: Vec<u8>,
+        mock_global_contract_deployer_wasm: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let env = TestEnv::new(
+            eth_token_address(),
+            mock_prover_wasm,
+            locker_wasm,
+            omni_token_wasm,
+            token_deployer_wasm,
+            mock_global_contract_deployer_wasm,
+        )
+        .await?;
+        let sender = env.create_registered_account(3).await?;
+        let receiver = env.create_registered_account(4).await?;
+        let amount = U128(1_000_000);
+
+        fake_finalize_transfer(
+            &env.locker_contract,
+            &env.token_account_id,
+            &sender,
+            env.init_token_address.clone(),
+            env.factory_contract_address.clone(),
+            amount,
+        )
+        .await?;
+
+        // Tokens have to opt in before accounts can be frozen
+        let result = env
+            .locker_contract
+            .call("freeze_token_account")
+            .args_json(json!({
+                "token": env.token_account_id,
+                "account_id": sender.id(),
+            }))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(result.into_result().is_err());
+
+        env.locker_contract
+            .call("set_token_freeze_enabled")
+            .args_json(json!({
+                "token": env.token_account_id,
+                "enabled": true,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        env.locker_contract
+            .call("freeze_token_account")
+            .args_json(json!({
+                "token": env.token_account_id,
+                "account_id": sender.id(),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let is_frozen: bool = env
+            .worker
+            .view(&env.token_account_id, "is_frozen")
+            .args_json(json!({ "account_id": sender.id() }))
+            .await?
+            .json()?;
+        assert!(is_frozen);
+
+        let result = sender
+            .call(&env.token_account_id, "ft_transfer")
+            .args_json(json!({
+                "receiver_id": receiver.id(),
+                "amount": amount,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(result.into_result().is_err());
+
+        let wiped: U128 = env
+            .locker_contract
+            .call("wipe_frozen_token_account")
+            .args_json(json!({
+                "token": env.token_account_id,
+                "account_id": sender.id(),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()?;
+        assert_eq!(wiped, amount);
+
+        let balance: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_balance_of")
+            .args_json(json!({ "account_id": sender.id() }))
+            .await?
+            .json()?;
+        assert_eq!(balance, U128(0));
+        let total_supply: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_total_supply")
+            .await?
+            .json()?;
+        assert_eq!(total_supply, U128(0));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_fin_transfer_with_msg_to_frozen_account(
+        mock_prover_wasm: Vec<u8>,
+        locker_wasm: Vec<u8>,
+        omni_token_wasm: Vec<u8>,
+        token_deployer_wasm: Vec<u8>,
+        mock_global_contract_deployer_wasm: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let env = TestEnv::new(
+            eth_token_address(),
+            mock_prover_wasm,
+            locker_wasm,
+            omni_token_wasm,
+            token_deployer_wasm,
+            mock_global_contract_deployer_wasm,
+        )
+        .await?;
+        let recipient = env.create_registered_account(3).await?;
+
+        env.locker_contract
+            .call("set_token_freeze_enabled")
+            .args_json(json!({
+                "token": env.token_account_id,
+                "enabled": true,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        env.locker_contract
+            .call("freeze_token_account")
+            .args_json(json!({
+                "token": env.token_account_id,
+                "account_id": recipient.id(),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let result = fake_finalize_transfer_with_msg(
+            &env.locker_contract,
+            &env.token_account_id,
+            &recipient,
+            env.init_token_address.clone(),
+            env.factory_contract_address.clone(),
+            U128(1_000_000),
+            "msg".to_string(),
+        )
+        .await?;
+
+        // The mint to the frozen recipient fails, so the transfer is reverted instead of finalised
+        let logs = result
+            .receipt_outcomes()
+            .iter()
+            .flat_map(|outcome| &outcome.logs)
+            .collect::<Vec<_>>();
+        assert!(get_bridge_event("failed_fin_transfer_event", &logs).is_some());
+        assert!(get_bridge_event("fin_transfer_event", &logs).is_none());
+
+        let is_finalised: bool = env
+            .locker_contract
+            .view("is_transfer_finalised")
+            .args_json(json!({
+                "transfer_id": {
+                    "origin_chain": env.init_token_address.get_chain(),
+                    "origin_nonce": 1,
+                },
+            }))
+            .await?
+            .json()?;
+        assert!(!is_finalised);
+
+        let total_supply: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_total_supply")
+            .await?
+            .json()?;
+        assert_eq!(total_supply, U128(0));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_allowance(
+        mock_prover_wasm: Vec<u8>,
+        locker_wasm: Vec<u8>,
+        omni_token_wasm: Vec<u8>,
+        token_deployer_wasm: Vec<u8>,
+        mock_global_contract_deployer_wasm: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let env = TestEnv::new(
+            eth_token_address(),
+            mock_prover_wasm,
+            locker_wasm,
+            omni_token_wasm,
+            token_deployer_wasm,
+            mock_global_contract_deployer_wasm,
+        )
+        .await?;
+        let owner = env.create_registered_account(3).await?;
+        let spender = env.create_registered_account(4).await?;
+        let amount = U128(1_000_000);
+        let allowance = U128(600_000);
+
+        fake_finalize_transfer(
+            &env.locker_contract,
+            &env.token_account_id,
+            &owner,
+            env.init_token_address.clone(),
+            env.factory_contract_address.clone(),
+            amount,
+        )
+        .await?;
+
+        owner
+            .call(&env.token_account_id, "ft_approve")
+            .args_json(json!({
+                "spender_id": spender.id(),
+                "amount": allowance,
+            }))
+            .deposit(NearToken::from_millinear(10))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let fetched_allowance: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_allowance")
+            .args_json(json!({
+                "owner_id": owner.id(),
+                "spender_id": spender.id(),
+            }))
+            .await?
+            .json()?;
+        assert_eq!(fetched_allowance, allowance);
+
+        // Exceeds the allowance
+        let result = spender
+            .call(&env.token_account_id, "ft_transfer_from")
+            .args_json(json!({
+                "owner_id": owner.id(),
+                "receiver_id": spender.id(),
+                "amount": amount,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(result.into_result().is_err());
+
+        spender
+            .call(&env.token_account_id, "ft_transfer_from")
+            .args_json(json!({
+                "owner_id": owner.id(),
+                "receiver_id": spender.id(),
+                "amount": allowance,
+            }))
+            .deposit(NearToken::from_yoctonear(1))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let spender_balance: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_balance_of")
+            .args_json(json!({ "account_id": spender.id() }))
+            .await?
+            .json()?;
+        assert_eq!(spender_balance, allowance);
+        let fetched_allowance: U128 = env
+            .worker
+            .view(&env.token_account_id, "ft_allowance")
+            .args_json(json!({
+                "owner_id": owner.id(),
+                "spender_id": spender.id(),
+            }))
+            .await?
+            .json()?;
+        assert_eq!(fetched_allowance, U128(0));
+
+        // Permits are rejected until the owner sets a permit key, and then only with a
+        // valid signature
+        let permit = json!({
+            "token_id": env.token_account_id,
+            "owner_id": owner.id(),
+            "spender_id": spender.id(),
+            "amount": allowance,
+            "nonce": "0",
+            "deadline": u64::MAX.to_string(),
+        });
+        let result = spender
+            .call(&env.token_account_id, "ft_permit")
+            .args_json(json!({
+                "permit": permit,
+                "signature": Base64VecU8::from(vec![0; 64]),
+            }))
+            .deposit(NearToken::from_millinear(10))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(result.into_result().is_err());
+
+        owner
+            .call(&env.token_account_id, "ft_set_permit_key")
+            .args_json(json!({ "public_key": owner.secret_key().public_key() }))
+            .deposit(NearToken::from_millinear(10))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let result = spender
+            .call(&env.token_account_id, "ft_permit")
+            .args_json(json!({
+                "permit": permit,
+                "signature": Base64VecU8::from(vec![0; 64]),
+            }))
+            .deposit(NearToken::from_millinear(10))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(result.into_result().is_err());
+
+        let nonce: U64 = env
+            .worker
+            .view(&env.token_account_id, "ft_permit_nonce")
+            .args_json(json!({ "owner_id": owner.id() }))
+            .await?
+            .json()?;
+        assert_eq!(nonce, U64(0));
+
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_token_deployer_migration(
@@ -944,6 +1454,150 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_token_deployer_staged_upgrade(
+        token_deployer_wasm: Vec<u8>,
+        omni_token_wasm: Vec<u8>,
+        mock_global_contract_deployer_wasm: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let worker = near_workspaces::sandbox().await?;
+        let root = worker.root_account()?;
+
+        let global_code_hash = Base58CryptoHash::from(
+            TestEnv::deploy_global_omni_token(
+                &worker,
+                &omni_token_wasm,
+                &mock_global_contract_deployer_wasm,
+            )
+            .await?,
+        );
+
+        let create_account = |name: &'static str| {
+            root.create_subaccount(name)
+                .initial_balance(NearToken::from_near(20))
+                .transact()
+        };
+        let dao = create_account("dao").await?.into_result()?;
+        let controller = create_account("controller").await?.into_result()?;
+        let token_deployer = create_account("deployer")
+            .await?
+            .into_result()?
+            .deploy(&token_deployer_wasm)
+            .await?
+            .into_result()?;
+
+        token_deployer
+            .call("new")
+            .args_json(json!({
+                "controller": controller.id(),
+                "dao": dao.id(),
+                "global_code_hash": global_code_hash,
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let canary_token: AccountId = format!("canary.{}", token_deployer.id()).parse()?;
+        let token: AccountId = format!("token.{}", token_deployer.id()).parse()?;
+        for account_id in [&canary_token, &token] {
+            controller
+                .call(token_deployer.id(), "deploy_token")
+                .args_json(json!({
+                    "account_id": account_id,
+                    "metadata": TestEnv::default_token_metadata(),
+                }))
+                .deposit(NearToken::from_near(3))
+                .max_gas()
+                .transact()
+                .await?
+                .into_result()?;
+        }
+
+        // The same global code is registered again as a new version
+        let version: u32 = dao
+            .call(token_deployer.id(), "add_code_version")
+            .args_json(json!({
+                "code_hash": global_code_hash,
+                "release_notes": "Test release",
+                "checksum": "checksum",
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?
+            .json()?;
+        assert_eq!(version, 1);
+
+        dao.call(token_deployer.id(), "add_canary_tokens")
+            .args_json(json!({ "tokens": [&canary_token] }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let upgrade_tokens = |caller: &near_workspaces::Account| {
+            caller
+                .call(token_deployer.id(), "upgrade_tokens")
+                .args_json(json!({ "version": version }))
+                .max_gas()
+                .transact()
+        };
+        let token_code_version = |token: &AccountId| {
+            token_deployer
+                .view("get_token_code_version")
+                .args_json(json!({ "token": token }))
+        };
+
+        assert!(upgrade_tokens(&controller).await?.into_result().is_err());
+
+        // Before promotion only the canary tokens are upgraded
+        let result = upgrade_tokens(&dao).await?;
+        assert!(result
+            .logs()
+            .iter()
+            .any(|log| log.contains("token_upgraded")));
+        let upgraded: Vec<AccountId> = result.into_result()?.json()?;
+        assert_eq!(upgraded, vec![canary_token.clone()]);
+        assert_eq!(
+            token_code_version(&canary_token)
+                .await?
+                .json::<Option<u32>>()?,
+            Some(version)
+        );
+        assert_eq!(
+            token_code_version(&token).await?.json::<Option<u32>>()?,
+            Some(0)
+        );
+
+        dao.call(token_deployer.id(), "promote_code_version")
+            .args_json(json!({ "version": version }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let upgraded: Vec<AccountId> = upgrade_tokens(&dao).await?.into_result()?.json()?;
+        assert_eq!(upgraded, vec![token.clone()]);
+        assert_eq!(
+            token_code_version(&token).await?.json::<Option<u32>>()?,
+            Some(version)
+        );
+
+        let current_version: u32 = token_deployer
+            .view("get_current_code_version")
+            .await?
+            .json()?;
+        assert_eq!(current_version, version);
+
+        let is_using_global_token: bool =
+            worker.view(&token, "is_using_global_token").await?.json()?;
+        assert!(is_using_global_token);
+
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_global_token_upgrade_and_migrate(
@@ -1040,6 +1694,100 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn test_mint_limits(omni_token_wasm: Vec<u8>) -> anyhow::Result<()> {
+        let worker = near_workspaces::sandbox().await?;
+        let root = worker.root_account()?;
+
+        let token_account = root
+            .create_subaccount("limited-token")
+            .initial_balance(NearToken::from_near(10))
+            .transact()
+            .await?
+            .into_result()?;
+        let token_contract = token_account
+            .deploy(&omni_token_wasm)
+            .await?
+            .into_result()?;
+        let controller = root
+            .create_subaccount("controller")
+            .initial_balance(NearToken::from_near(10))
+            .transact()
+            .await?
+            .into_result()?;
+
+        root.call(token_contract.id(), "new")
+            .args_json(json!({
+                "controller": controller.id(),
+                "metadata": TestEnv::default_token_metadata(),
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+        controller
+            .call(token_contract.id(), "storage_deposit")
+            .args_json(json!({ "account_id": controller.id() }))
+            .deposit(NEP141_DEPOSIT)
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        // Only the deployer (the parent account) can set the limits
+        let result = controller
+            .call(token_contract.id(), "set_mint_limits")
+            .args_json(json!({ "max_total_supply": U128(1_000) }))
+            .max_gas()
+            .transact()
+            .await?;
+        assert!(result.into_result().is_err());
+
+        root.call(token_contract.id(), "set_mint_limits")
+            .args_json(json!({
+                "max_total_supply": U128(1_000),
+                "mint_rate_limit": {
+                    "max_amount": U128(600),
+                    "window_sec": 86_400,
+                },
+            }))
+            .max_gas()
+            .transact()
+            .await?
+            .into_result()?;
+
+        let mint = |amount: U128| {
+            controller
+                .call(token_contract.id(), "mint")
+                .args_json(json!({
+                    "account_id": controller.id(),
+                    "amount": amount,
+                }))
+                .max_gas()
+                .transact()
+        };
+
+        mint(U128(500)).await?.into_result()?;
+
+        // Over the rate limit
+        let result = mint(U128(200)).await?;
+        assert!(result
+            .logs()
+            .iter()
+            .any(|log| log.contains("mint_limit_exceeded")));
+        assert!(result.into_result().is_err());
+
+        let limits: near_sdk::serde_json::Value =
+            token_contract.view("get_mint_limits").await?.json()?;
+        assert_eq!(limits["max_total_supply"], json!(U128(1_000)));
+
+        let total_supply: U128 = token_contract.view("ft_total_supply").await?.json()?;
+        assert_eq!(total_supply, U128(500));
+
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_non_global_token_upgrade_and_migrate(
@@ -1062,9 +1810,12 @@ mod tests {
             .into_result()?;
 
         let metadata = BasicMetadata {
-            name: "Local Token".to_string(),
-            symbol: "LOC".to_string(),
+            name: "Local Token".parse().unwrap(),
+            symbol: "LOC".parse().unwrap(),
             decimals: 18,
+            icon: None,
+            reference: None,
+            reference_hash: None,
         };
 
         root.call(token_contract.id(), "new")
@@ -1175,6 +1926,29 @@ mod tests {
         emitter_address: OmniAddress,
         amount: U128,
     ) -> anyhow::Result<()> {
+        fake_finalize_transfer_with_msg(
+            locker_contract,
+            token_account_id,
+            recipient,
+            token_address,
+            emitter_address,
+            amount,
+            String::default(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn fake_finalize_transfer_with_msg(
+        locker_contract: &near_workspaces::Contract,
+        token_account_id: &AccountId,
+        recipient: &near_workspaces::Account,
+        token_address: OmniAddress,
+        emitter_address: OmniAddress,
+        amount: U128,
+        msg: String,
+    ) -> anyhow::Result<ExecutionSuccess> {
         let sender =
             OmniAddress::new_zero(token_address.get_chain()).unwrap_or_else(|_| eth_eoa_address());
         let storage_deposit_actions = vec![StorageDepositAction {
@@ -1190,7 +1964,7 @@ mod tests {
             NEP141_DEPOSIT.saturating_add(required_balance_for_fin_transfer);
 
         // Simulate finalization of transfer through locker
-        locker_contract
+        let result = locker_contract
             .call("fin_transfer")
             .args_borsh(FinTransferArgs {
                 chain_kind: ChainKind::Eth,
@@ -1205,7 +1979,7 @@ mod tests {
                         native_fee: U128(0),
                     },
                     sender,
-                    msg: String::default(),
+                    msg,
                     emitter_address,
                 }))?,
             })
@@ -1215,6 +1989,6 @@ mod tests {
             .await?
             .into_result()?;
 
-        Ok(())
+        Ok(result)
     }
 }
@@ -6,6 +6,10 @@ mod tests {
     };
     use near_workspaces::{types::NearToken, AccountId};
     use omni_types::{
+        utxo::{
+            zcash::{Receiver, UnifiedAddress},
+            UtxoNetwork,
+        },
         BridgeOnTransferMsg, ChainKind, Fee, InitTransferMsg, OmniAddress, TransferId,
         TransferMessage, TransferMessageStorageAccount,
     };
@@ -99,6 +103,7 @@ mod tests {
                 "utxo_chain_connector_id": zcash_connector.id(),
                 "utxo_chain_token_id": nzec_token.id(),
                 "decimals": 8,
+                "network": UtxoNetwork::Mainnet,
             }))
             .deposit(NEP141_DEPOSIT.saturating_mul(3))
             .max_gas()
@@ -193,17 +198,31 @@ mod tests {
 
         // ── Construct the malicious init_transfer ──────────────────────────
         //
-        // A 500-char "Zcash UA" string. The bridge doesn't validate Zcash
-        // address format — `OmniAddress::Zcash(String)` accepts anything —
-        // so we use a string of sufficient length to push the actual encoded
-        // size of TransferMessage well beyond the synthetic placeholder.
+        // A ~500-char Zcash UA. Recipients must be well-formed unified
+        // addresses, but ZIP-316 places no upper bound on their length: a UA
+        // may carry receivers of typecodes unknown to the bridge. We add one
+        // such receiver to push the actual encoded size of TransferMessage
+        // well beyond the synthetic placeholder.
         //
         // Breakeven (short attacker, dev-deployed token names): ~300 chars.
         // Real-world Zcash UAs with all three receivers (transparent +
         // Sapling + Orchard) are typically 280-320 chars, with longer
-        // variants possible. We use 500 to make the overshoot unambiguous
+        // variants possible. We use ~500 to make the overshoot unambiguous
         // and safe across token-name lengths produced by dev-deploy.
-        let long_zcash_ua = format!("u{}", "1".repeat(499));
+        let long_zcash_ua = UnifiedAddress {
+            receivers: vec![
+                Receiver::P2pkh([1; 20]),
+                Receiver::Sapling([2; 43]),
+                Receiver::Orchard([3; 43]),
+                Receiver::Unknown {
+                    typecode: 0x10,
+                    data: vec![4; 180],
+                },
+            ],
+        }
+        .encode(UtxoNetwork::Mainnet)
+        .map_err(anyhow::Error::msg)?;
+        assert!(long_zcash_ua.len() >= 500);
         let recipient = OmniAddress::Zcash(long_zcash_ua.clone());
 
         let transfer_amount: u128 = 50_000;
@@ -379,12 +398,15 @@ mod tests {
             .await?
             .into_result()?;
 
-        // A maximal-length Bech32m BTC address. BIP-173 caps Bech32 strings
-        // at 90 characters. Real wallets use ~62 chars. Both are below the
-        // 64-char NEAR placeholder, so the synthetic estimate over-covers
-        // the actual cost and the storage check passes.
-        let btc_address_90 = format!("bc1p{}", "q".repeat(85)); // length 89
-        let recipient = OmniAddress::Btc(btc_address_90.clone());
+        // A maximal-length segwit BTC address (40-byte v1 program, BIP-350
+        // test vector). BIP-173 caps Bech32 strings at 90 characters. Real
+        // wallets use ~62 chars. Both are close to the 64-char NEAR
+        // placeholder, so the synthetic estimate over-covers the actual cost
+        // and the storage check passes.
+        let btc_address_max =
+            "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y"
+                .to_string();
+        let recipient = OmniAddress::Btc(btc_address_max.clone());
 
         let transfer_amount: u128 = 50_000;
         let init_msg = InitTransferMsg {
@@ -0,0 +1,282 @@
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::{Base64VecU8, U128, U64};
+use near_sdk::{
+    assert_one_yocto, borsh, env, near, require, AccountId, CurveType, NearToken, Promise,
+    PublicKey,
+};
+use omni_types::errors::TokenError;
+
+use crate::{OmniToken, OmniTokenExt};
+
+// Like the freeze list, allowances live outside of the contract state so that tokens
+// sharing the global code don't need a state migration.
+const ALLOWANCES_PREFIX: &[u8] = b"ALLOWANCES";
+const PERMIT_KEYS_PREFIX: &[u8] = b"PERMIT_KEYS";
+const PERMIT_DOMAIN: &[u8] = b"omni-token:permit";
+
+/// Off-chain approval signed by the owner's permit key.
+///
+/// The signed bytes are `b"omni-token:permit"` followed by the borsh encoding of this struct.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct PermitMessage {
+    pub token_id: AccountId,
+    pub owner_id: AccountId,
+    pub spender_id: AccountId,
+    pub amount: U128,
+    pub nonce: U64,
+    /// Block timestamp in nanoseconds after which the permit is rejected.
+    pub deadline: U64,
+}
+
+/// Allowance with the account that paid for its storage, which gets the storage back
+/// once the allowance is reset to zero.
+#[near(serializers=[borsh])]
+struct Allowance {
+    amount: u128,
+    storage_payer: AccountId,
+}
+
+#[near(serializers=[borsh])]
+struct PermitKey {
+    public_key: PublicKey,
+    nonce: u64,
+}
+
+#[near]
+impl OmniToken {
+    /// Sets the amount `spender_id` can transfer from the caller's balance with
+    /// `ft_transfer_from`. Storage for a new allowance is paid from the attached deposit,
+    /// the rest is refunded. Setting the allowance to zero refunds its storage to the
+    /// account that paid for it.
+    #[payable]
+    pub fn ft_approve(&mut self, spender_id: AccountId, amount: U128) {
+        require!(
+            env::attached_deposit() > NearToken::from_yoctonear(0),
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+
+        let owner_id = env::predecessor_account_id();
+        Self::assert_not_frozen(&owner_id);
+
+        let initial_storage_usage = env::storage_usage();
+        let released_storage_payer =
+            Self::set_allowance(&owner_id, &spender_id, amount.0, &owner_id);
+        Self::settle_storage_deposit(owner_id, initial_storage_usage, released_storage_payer);
+    }
+
+    pub fn ft_allowance(&self, owner_id: AccountId, spender_id: AccountId) -> U128 {
+        U128(Self::get_allowance(owner_id, spender_id))
+    }
+
+    #[payable]
+    pub fn ft_transfer_from(
+        &mut self,
+        owner_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+
+        let spender_id = env::predecessor_account_id();
+        Self::assert_not_frozen(&owner_id);
+        Self::assert_not_frozen(&spender_id);
+        Self::assert_not_frozen(&receiver_id);
+
+        let key = (owner_id.clone(), spender_id);
+        let mut allowance = Self::allowances()
+            .get(&key)
+            .unwrap_or_else(|| env::panic_str(TokenError::AllowanceExceeded.as_ref()));
+        require!(
+            amount.0 <= allowance.amount,
+            TokenError::AllowanceExceeded.as_ref()
+        );
+
+        // The spent allowance keeps its storage until the owner resets it with `ft_approve`
+        allowance.amount -= amount.0;
+        Self::allowances().insert(&key, &allowance);
+        self.token
+            .internal_transfer(&owner_id, &receiver_id, amount.0, memo);
+    }
+
+    /// Sets the ed25519 key that signs permits for the caller. The permit nonce is kept
+    /// when the key is replaced.
+    #[payable]
+    pub fn ft_set_permit_key(&mut self, public_key: PublicKey) {
+        require!(
+            env::attached_deposit() > NearToken::from_yoctonear(0),
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        require!(
+            public_key.curve_type() == CurveType::ED25519,
+            TokenError::InvalidPublicKey.as_ref()
+        );
+
+        let owner_id = env::predecessor_account_id();
+        let nonce = Self::permit_keys()
+            .get(&owner_id)
+            .map_or(0, |permit_key| permit_key.nonce);
+
+        let initial_storage_usage = env::storage_usage();
+        Self::permit_keys().insert(&owner_id, &PermitKey { public_key, nonce });
+        Self::settle_storage_deposit(owner_id, initial_storage_usage, None);
+    }
+
+    pub fn ft_permit_key(&self, owner_id: &AccountId) -> Option<PublicKey> {
+        Self::permit_keys()
+            .get(owner_id)
+            .map(|permit_key| permit_key.public_key)
+    }
+
+    pub fn ft_permit_nonce(&self, owner_id: &AccountId) -> U64 {
+        U64(Self::permit_keys()
+            .get(owner_id)
+            .map_or(0, |permit_key| permit_key.nonce))
+    }
+
+    /// Sets an allowance from a permit signed by the owner. Anyone can submit the permit,
+    /// the caller pays for the storage of a new allowance and gets it back once the
+    /// allowance is reset to zero.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if serialization fails.
+    #[payable]
+    pub fn ft_permit(&mut self, permit: PermitMessage, signature: Base64VecU8) {
+        require!(
+            env::attached_deposit() > NearToken::from_yoctonear(0),
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+        require!(
+            permit.token_id == env::current_account_id(),
+            TokenError::InvalidPermit.as_ref()
+        );
+        require!(
+            env::block_timestamp() <= permit.deadline.0,
+            TokenError::PermitExpired.as_ref()
+        );
+
+        let mut permit_key = Self::permit_keys()
+            .get(&permit.owner_id)
+            .unwrap_or_else(|| env::panic_str(TokenError::PermitKeyNotSet.as_ref()));
+        require!(
+            permit.nonce.0 == permit_key.nonce,
+            TokenError::InvalidPermitNonce.as_ref()
+        );
+
+        let signature: [u8; 64] = signature
+            .0
+            .as_slice()
+            .try_into()
+            .unwrap_or_else(|_| env::panic_str(TokenError::InvalidPermitSignature.as_ref()));
+        let public_key: [u8; 32] = permit_key.public_key.as_bytes()[1..]
+            .try_into()
+            .unwrap_or_else(|_| env::panic_str(TokenError::InvalidPublicKey.as_ref()));
+        let message = [PERMIT_DOMAIN, &borsh::to_vec(&permit).unwrap()].concat();
+        require!(
+            env::ed25519_verify(&signature, &message, &public_key),
+            TokenError::InvalidPermitSignature.as_ref()
+        );
+
+        Self::assert_not_frozen(&permit.owner_id);
+
+        let initial_storage_usage = env::storage_usage();
+        let predecessor_id = env::predecessor_account_id();
+        permit_key.nonce += 1;
+        Self::permit_keys().insert(&permit.owner_id, &permit_key);
+        let released_storage_payer = Self::set_allowance(
+            &permit.owner_id,
+            &permit.spender_id,
+            permit.amount.0,
+            &predecessor_id,
+        );
+        Self::settle_storage_deposit(
+            predecessor_id,
+            initial_storage_usage,
+            released_storage_payer,
+        );
+    }
+}
+
+impl OmniToken {
+    fn allowances() -> LookupMap<(AccountId, AccountId), Allowance> {
+        LookupMap::new(ALLOWANCES_PREFIX)
+    }
+
+    fn permit_keys() -> LookupMap<AccountId, PermitKey> {
+        LookupMap::new(PERMIT_KEYS_PREFIX)
+    }
+
+    fn get_allowance(owner_id: AccountId, spender_id: AccountId) -> u128 {
+        Self::allowances()
+            .get(&(owner_id, spender_id))
+            .map_or(0, |allowance| allowance.amount)
+    }
+
+    /// Sets the allowance, recording `payer_id` as the storage payer of a new allowance.
+    /// Returns the storage payer of a removed allowance.
+    fn set_allowance(
+        owner_id: &AccountId,
+        spender_id: &AccountId,
+        amount: u128,
+        payer_id: &AccountId,
+    ) -> Option<AccountId> {
+        let key = (owner_id.clone(), spender_id.clone());
+        if amount == 0 {
+            return Self::allowances()
+                .remove(&key)
+                .map(|allowance| allowance.storage_payer);
+        }
+
+        let storage_payer = Self::allowances()
+            .get(&key)
+            .map_or_else(|| payer_id.clone(), |allowance| allowance.storage_payer);
+        Self::allowances().insert(
+            &key,
+            &Allowance {
+                amount,
+                storage_payer,
+            },
+        );
+        None
+    }
+
+    /// Charges the storage added since `initial_storage_usage` to the attached deposit and
+    /// refunds the rest to `account_id`. Released storage is refunded to `storage_payer`,
+    /// or to `account_id` if it's not set.
+    ///
+    /// Allowances can't be charged to the `StorageManagement` balance: `FungibleToken`
+    /// registers accounts with exactly `storage_balance_bounds().min` and refunds any extra
+    /// deposit, so `available` is always zero and there is nothing to charge.
+    fn settle_storage_deposit(
+        account_id: AccountId,
+        initial_storage_usage: u64,
+        storage_payer: Option<AccountId>,
+    ) {
+        let storage_usage = env::storage_usage();
+        let mut refund = env::attached_deposit();
+        if storage_usage > initial_storage_usage {
+            let required = env::storage_byte_cost()
+                .saturating_mul((storage_usage - initial_storage_usage).into());
+            require!(
+                refund >= required,
+                TokenError::InsufficientStorageDeposit.as_ref()
+            );
+            refund = refund.saturating_sub(required);
+        } else {
+            let released = env::storage_byte_cost()
+                .saturating_mul((initial_storage_usage - storage_usage).into());
+            match storage_payer {
+                Some(storage_payer) if storage_payer != account_id && !released.is_zero() => {
+                    Promise::new(storage_payer).transfer(released).detach();
+                }
+                _ => refund = refund.saturating_add(released),
+            }
+        }
+
+        if !refund.is_zero() {
+            Promise::new(account_id).transfer(refund).detach();
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use near_contract_standards::fungible_token::events::FtBurn;
 use near_contract_standards::fungible_token::metadata::{
     FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC,
 };
@@ -7,20 +8,30 @@ use near_contract_standards::fungible_token::{
 use near_contract_standards::storage_management::{
     StorageBalance, StorageBalanceBounds, StorageManagement,
 };
-use near_sdk::collections::LazyOption;
+use near_sdk::collections::{LazyOption, LookupSet};
 use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::{
-    borsh, env, ext_contract, near, require, AccountContract, AccountId, NearToken, PanicOnDefault,
-    Promise, PromiseOrValue, PublicKey,
+    borsh, env, ext_contract, near, require, AccountContract, AccountId, Gas, NearToken,
+    PanicOnDefault, Promise, PromiseOrValue, PublicKey,
 };
 use omni_ft::{MetadataManagment, MintAndBurn};
 use omni_types::errors::TokenError;
-use omni_types::{BasicMetadata, OmniAddress};
+use omni_types::{
+    BasicMetadata, BoundedString, InitTransferMsg, OmniAddress, MAX_INIT_TRANSFER_MSG_LEN,
+};
 
 const WITHDRAW_RELAYER_ADDRESS: &[u8] = b"WITHDRAW_RELAYER_ADDRESS";
 const WITHDRAW_MEMO_PREFIX: &str = "WITHDRAW_TO:";
-
+// Freeze state lives outside of the contract state so that tokens sharing the global
+// code opt in one by one without a state migration.
+const FREEZE_ENABLED: &[u8] = b"FREEZE_ENABLED";
+const FROZEN_ACCOUNTS_PREFIX: &[u8] = b"FROZEN";
+const INIT_TRANSFER_FROM_TOKEN_GAS: Gas = Gas::from_tgas(30);
+const BRIDGE_OUT_CALLBACK_GAS: Gas = Gas::from_tgas(10);
+
+pub mod allowance;
 mod migrate;
+pub mod mint_limit;
 pub mod omni_ft;
 
 #[near(contract_state)]
@@ -33,14 +44,12 @@ pub struct OmniToken {
 
 #[ext_contract(ext_omni_factory)]
 pub trait ExtOmniTokenFactory {
-    fn init_transfer(
-        &self,
-        sender: AccountId,
+    fn init_transfer_from_token(
+        &mut self,
+        sender_id: AccountId,
         amount: U128,
-        recipient: OmniAddress,
-        fee: U128,
-        native_fee: U128,
-    ) -> Promise;
+        init_transfer_msg: InitTransferMsg,
+    );
 }
 
 #[near]
@@ -67,11 +76,13 @@ impl OmniToken {
                 b"m".to_vec(),
                 Some(&FungibleTokenMetadata {
                     spec: FT_METADATA_SPEC.to_string(),
-                    name: metadata.name,
-                    symbol: metadata.symbol,
-                    icon: None,
-                    reference: None,
-                    reference_hash: None,
+                    name: metadata.name.into(),
+                    symbol: metadata.symbol.into(),
+                    icon: metadata.icon.map(Into::into),
+                    reference: metadata.reference.map(Into::into),
+                    reference_hash: metadata
+                        .reference_hash
+                        .map(|hash| Base64VecU8(hash.0.to_vec())),
                     decimals: metadata.decimals,
                 }),
             ),
@@ -116,9 +127,151 @@ impl OmniToken {
         env::storage_write(WITHDRAW_RELAYER_ADDRESS, &borsh::to_vec(relayer).unwrap());
     }
 
+    /// Enables or disables the freeze list for this token.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if serialization fails.
+    pub fn set_freeze_enabled(&mut self, enabled: bool) {
+        self.assert_controller();
+
+        env::storage_write(FREEZE_ENABLED, &borsh::to_vec(&enabled).unwrap());
+    }
+
+    pub fn is_freeze_enabled(&self) -> bool {
+        Self::read_freeze_enabled()
+    }
+
+    pub fn freeze_account(&mut self, account_id: &AccountId) {
+        self.assert_controller();
+        require!(
+            Self::read_freeze_enabled(),
+            TokenError::FreezeNotEnabled.as_ref()
+        );
+
+        Self::frozen_accounts().insert(account_id);
+    }
+
+    pub fn unfreeze_account(&mut self, account_id: &AccountId) {
+        self.assert_controller();
+
+        require!(
+            Self::frozen_accounts().remove(account_id),
+            TokenError::AccountNotFrozen.as_ref()
+        );
+    }
+
+    pub fn is_frozen(&self, account_id: &AccountId) -> bool {
+        Self::is_account_frozen(account_id)
+    }
+
+    /// Burns the whole balance of a frozen account and returns the burned amount.
+    pub fn wipe_frozen(&mut self, account_id: &AccountId) -> U128 {
+        self.assert_controller();
+        require!(
+            Self::is_account_frozen(account_id),
+            TokenError::AccountNotFrozen.as_ref()
+        );
+
+        let amount = self.token.accounts.get(account_id).unwrap_or_default();
+        if amount > 0 {
+            self.token.internal_withdraw(account_id, amount);
+            FtBurn {
+                owner_id: account_id,
+                amount: U128(amount),
+                memo: Some("Wipe frozen account"),
+            }
+            .emit();
+        }
+
+        U128(amount)
+    }
+
+    fn read_freeze_enabled() -> bool {
+        env::storage_read(FREEZE_ENABLED)
+            .and_then(|data| borsh::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn frozen_accounts() -> LookupSet<AccountId> {
+        LookupSet::new(FROZEN_ACCOUNTS_PREFIX)
+    }
+
+    fn is_account_frozen(account_id: &AccountId) -> bool {
+        Self::read_freeze_enabled() && Self::frozen_accounts().contains(account_id)
+    }
+
+    fn assert_not_frozen(account_id: &AccountId) {
+        require!(
+            !Self::is_account_frozen(account_id),
+            TokenError::AccountFrozen.as_ref()
+        );
+    }
+
     pub fn get_token_storage_key(&self) -> String {
         format!("{:?}", self.token.accounts)
     }
+
+    /// Burns `amount` from the caller and initiates a transfer of it on the bridge.
+    ///
+    /// The attached deposit pays the native fee and the transfer storage on the bridge, which
+    /// transfers any surplus back to the caller and takes a shortfall from the caller's storage
+    /// balance on the bridge. If the bridge rejects the transfer, the tokens are minted back
+    /// and the deposit is transferred back.
+    #[payable]
+    pub fn bridge_out(
+        &mut self,
+        amount: U128,
+        recipient: OmniAddress,
+        fee: U128,
+        native_fee: U128,
+        msg: Option<BoundedString<MAX_INIT_TRANSFER_MSG_LEN>>,
+    ) -> Promise {
+        require!(
+            env::attached_deposit() > NearToken::from_yoctonear(0),
+            "Requires attached deposit of at least 1 yoctoNEAR"
+        );
+
+        let sender_id = env::predecessor_account_id();
+        Self::assert_not_frozen(&sender_id);
+        self.token.internal_withdraw(&sender_id, amount.into());
+
+        ext_omni_factory::ext(self.controller.clone())
+            .with_static_gas(INIT_TRANSFER_FROM_TOKEN_GAS)
+            .with_attached_deposit(env::attached_deposit())
+            .init_transfer_from_token(
+                sender_id.clone(),
+                amount,
+                InitTransferMsg {
+                    recipient,
+                    fee,
+                    native_token_fee: native_fee,
+                    msg,
+                    external_id: None,
+                },
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(BRIDGE_OUT_CALLBACK_GAS)
+                    .bridge_out_callback(sender_id, amount, env::attached_deposit()),
+            )
+    }
+
+    #[private]
+    pub fn bridge_out_callback(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        deposit: NearToken,
+    ) -> bool {
+        if env::promise_result_checked(0, usize::MAX).is_ok() {
+            return true;
+        }
+
+        self.token.internal_deposit(&sender_id, amount.into());
+        Promise::new(sender_id).transfer(deposit).detach();
+        false
+    }
 }
 
 #[near]
@@ -131,6 +284,8 @@ impl MintAndBurn for OmniToken {
         msg: Option<String>,
     ) -> PromiseOrValue<U128> {
         self.assert_controller();
+        Self::assert_not_frozen(&account_id);
+        self.consume_mint_limits(&account_id, amount.0);
 
         if let Some(msg) = msg {
             self.token
@@ -192,6 +347,9 @@ impl MetadataManagment for OmniToken {
 impl FungibleTokenCore for OmniToken {
     #[payable]
     fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>) {
+        Self::assert_not_frozen(&env::predecessor_account_id());
+        Self::assert_not_frozen(&receiver_id);
+
         // Legacy bridging flow used by Near Intents
         if receiver_id == env::current_account_id()
             && memo
@@ -214,6 +372,9 @@ impl FungibleTokenCore for OmniToken {
         memo: Option<String>,
         msg: String,
     ) -> PromiseOrValue<U128> {
+        Self::assert_not_frozen(&env::predecessor_account_id());
+        Self::assert_not_frozen(&receiver_id);
+
         self.token.ft_transfer_call(receiver_id, amount, memo, msg)
     }
 
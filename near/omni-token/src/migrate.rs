@@ -74,7 +74,10 @@ impl OmniToken {
 #[near]
 impl UpgradeAndMigrate for OmniToken {
     fn upgrade_and_migrate(&self) {
-        self.assert_controller();
+        // The deployer rolls out new global code versions to the tokens it deployed
+        if env::predecessor_account_id() != self.controller {
+            Self::assert_deployer();
+        }
 
         // Receive the code directly from the input to avoid the
         // GAS overhead of deserializing parameters
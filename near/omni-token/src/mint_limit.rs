@@ -0,0 +1,151 @@
+use near_sdk::json_types::U128;
+use near_sdk::{borsh, env, near, require, AccountId};
+use omni_types::errors::TokenError;
+use omni_types::MintRateLimit;
+
+use crate::{OmniToken, OmniTokenExt};
+
+// Kept outside of the contract state, like the freeze list and allowances.
+const MINT_LIMITS: &[u8] = b"MINT_LIMITS";
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+#[near(serializers=[json])]
+#[derive(Debug, Clone)]
+pub struct MintLimitsView {
+    pub max_total_supply: Option<U128>,
+    pub mint_rate_limit: Option<MintRateLimit>,
+    /// Amount that can be minted right now under the rate limit.
+    pub available_to_mint: Option<U128>,
+}
+
+#[near(event_json(standard = "omni-token"))]
+pub enum OmniTokenEvent {
+    #[event_version("1.0.0")]
+    MintLimitExceeded {
+        account_id: AccountId,
+        amount: U128,
+        total_supply: U128,
+    },
+}
+
+#[near(serializers=[borsh])]
+#[derive(Default)]
+struct MintLimits {
+    max_total_supply: Option<u128>,
+    mint_rate_limit: Option<MintRateLimit>,
+    available: u128,
+    updated_at: u64,
+}
+
+impl MintLimits {
+    fn read() -> Option<Self> {
+        env::storage_read(MINT_LIMITS).and_then(|data| borsh::from_slice(&data).ok())
+    }
+
+    fn write(&self) {
+        env::storage_write(MINT_LIMITS, &borsh::to_vec(self).unwrap());
+    }
+
+    /// Refills the rate limit linearly, so the full `max_amount` becomes available again
+    /// `window_sec` seconds after it was spent.
+    fn refill(&mut self, timestamp: u64) {
+        let Some(limit) = self.mint_rate_limit else {
+            return;
+        };
+
+        let max_amount = limit.max_amount.0;
+        let window = u128::from(limit.window_sec) * NANOS_PER_SEC;
+        let elapsed = u128::from(timestamp.saturating_sub(self.updated_at));
+        let refill = if elapsed >= window {
+            max_amount
+        } else {
+            max_amount
+                .checked_mul(elapsed)
+                .map_or_else(|| max_amount / window * elapsed, |amount| amount / window)
+        };
+
+        self.available = self.available.saturating_add(refill).min(max_amount);
+        self.updated_at = timestamp;
+    }
+}
+
+#[near]
+impl OmniToken {
+    /// Sets the supply cap and the mint-rate limit. Only callable by the token deployer.
+    /// Setting the rate limit makes its full `max_amount` available immediately.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if serialization fails.
+    pub fn set_mint_limits(
+        &mut self,
+        max_total_supply: Option<U128>,
+        mint_rate_limit: Option<MintRateLimit>,
+    ) {
+        Self::assert_deployer();
+
+        MintLimits {
+            max_total_supply: max_total_supply.map(|supply| supply.0),
+            mint_rate_limit,
+            available: mint_rate_limit.map_or(0, |limit| limit.max_amount.0),
+            updated_at: env::block_timestamp(),
+        }
+        .write();
+    }
+
+    pub fn get_mint_limits(&self) -> MintLimitsView {
+        let mut limits = MintLimits::read().unwrap_or_default();
+        limits.refill(env::block_timestamp());
+
+        MintLimitsView {
+            max_total_supply: limits.max_total_supply.map(U128),
+            mint_rate_limit: limits.mint_rate_limit,
+            available_to_mint: limits.mint_rate_limit.map(|_| U128(limits.available)),
+        }
+    }
+}
+
+impl OmniToken {
+    pub(crate) fn assert_deployer() {
+        let deployer_account = env::current_account_id()
+            .get_parent_account_id()
+            .unwrap_or_else(|| {
+                env::panic_str(TokenError::InvalidParentAccount.to_string().as_str())
+            });
+
+        require!(
+            env::predecessor_account_id().as_str() == deployer_account,
+            TokenError::MissingPermission.as_ref()
+        );
+    }
+
+    /// Records `amount` against the mint limits. Panics after logging a
+    /// `mint_limit_exceeded` event if the amount doesn't fit.
+    pub(crate) fn consume_mint_limits(&self, account_id: &AccountId, amount: u128) {
+        let Some(mut limits) = MintLimits::read() else {
+            return;
+        };
+
+        let total_supply = self.token.total_supply;
+        limits.refill(env::block_timestamp());
+
+        let exceeds_supply = limits
+            .max_total_supply
+            .is_some_and(|max_total_supply| total_supply.saturating_add(amount) > max_total_supply);
+        let exceeds_rate = limits.mint_rate_limit.is_some() && amount > limits.available;
+        if exceeds_supply || exceeds_rate {
+            OmniTokenEvent::MintLimitExceeded {
+                account_id: account_id.clone(),
+                amount: U128(amount),
+                total_supply: U128(total_supply),
+            }
+            .emit();
+            env::panic_str(TokenError::MintLimitExceeded.as_ref());
+        }
+
+        if limits.mint_rate_limit.is_some() {
+            limits.available -= amount;
+            limits.write();
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Writes the omni-types JSON schema to the path given as the first argument,
+//! or to stdout if no path is given.
+
+use std::{env, fs};
+
+use near_sdk::serde_json;
+
+fn main() {
+    let schema = serde_json::to_string_pretty(&omni_types::schema::json_schema())
+        .expect("Failed to serialize the schema");
+
+    match env::args().nth(1) {
+        Some(path) => fs::write(&path, schema).expect("Failed to write the schema"),
+        None => println!("{schema}"),
+    }
+}
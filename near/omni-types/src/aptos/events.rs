@@ -24,6 +24,7 @@ use near_sdk::json_types::U128;
 use near_sdk::serde_json::{self, Value};
 
 use crate::{
+    parse_extended_metadata,
     prover_result::{
         DeployTokenMessage, FinTransferMessage, InitTransferMessage, LogMetadataMessage, ProofKind,
         ProverResult,
@@ -245,21 +246,37 @@ pub fn parse_deploy_token(type_tag: &str, data: &str) -> Result<DeployTokenMessa
 ///
 /// # Move event layout
 /// ```text
-/// token_address: address, name: String, symbol: String, decimals: u8
+/// token_address: address, name: String, symbol: String, decimals: u8,
+/// icon: String, reference: String, reference_hash: vector<u8>
 /// ```
+///
+/// Events without the `icon`, `reference` and `reference_hash` fields are parsed without them.
 pub fn parse_log_metadata(type_tag: &str, data: &str) -> Result<LogMetadataMessage, String> {
     if !type_tag.ends_with(LOG_METADATA_TAG) {
         return Err(format!("LogMetadata: unexpected type tag '{type_tag}'"));
     }
     let emitter_address = OmniAddress::Aptos(H256(type_tag_address(type_tag)?));
     let v = event_json(data)?;
+    let (icon, reference, reference_hash) = if v.get("icon").is_some() {
+        parse_extended_metadata(
+            field_str(&v, "icon")?,
+            field_str(&v, "reference")?,
+            &parse_bytes(field_str(&v, "reference_hash")?)?,
+        )?
+    } else {
+        (None, None, None)
+    };
 
     Ok(LogMetadataMessage {
         token_address: OmniAddress::Aptos(H256(field_address(&v, "token_address")?)),
-        name: field_str(&v, "name")?.to_string(),
-        symbol: field_str(&v, "symbol")?.to_string(),
+        name: field_str(&v, "name")?.parse().map_err(stringify)?,
+        symbol: field_str(&v, "symbol")?.parse().map_err(stringify)?,
         decimals: field_u8(&v, "decimals")?,
         emitter_address,
+        icon,
+        reference,
+        reference_hash,
+        sequence: None,
     })
 }
 
@@ -441,6 +458,34 @@ mod tests {
             msg.emitter_address,
             OmniAddress::Aptos(H256(aptos_addr_bytes()))
         );
+        assert!(msg.icon.is_none());
+        assert!(msg.reference.is_none());
+        assert!(msg.reference_hash.is_none());
+    }
+
+    #[test]
+    fn test_parse_log_metadata_with_extended_metadata() {
+        let reference_hash = "01".repeat(32);
+        let data = format!(
+            r#"{{"token_address":"{APTOS_ADDR}","name":"Wrapped ETH","symbol":"WETH","decimals":8,"icon":"data:image/svg+xml,<svg/>","reference":"https://example.com/weth.json","reference_hash":"0x{reference_hash}"}}"#
+        );
+        let tag = format!("{APTOS_ADDR}::omni_bridge::LogMetadata");
+        let msg = parse_log_metadata(&tag, &data).unwrap();
+        assert_eq!(msg.icon.unwrap(), "data:image/svg+xml,<svg/>");
+        assert_eq!(msg.reference.unwrap(), "https://example.com/weth.json");
+        assert_eq!(msg.reference_hash, Some(H256([1; 32])));
+    }
+
+    #[test]
+    fn test_parse_log_metadata_with_empty_extended_metadata() {
+        let data = format!(
+            r#"{{"token_address":"{APTOS_ADDR}","name":"Wrapped ETH","symbol":"WETH","decimals":8,"icon":"","reference":"","reference_hash":"0x"}}"#
+        );
+        let tag = format!("{APTOS_ADDR}::omni_bridge::LogMetadata");
+        let msg = parse_log_metadata(&tag, &data).unwrap();
+        assert!(msg.icon.is_none());
+        assert!(msg.reference.is_none());
+        assert!(msg.reference_hash.is_none());
     }
 
     #[test]
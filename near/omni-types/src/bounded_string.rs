@@ -4,6 +4,8 @@ use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk::serde::{Deserialize, Serialize};
+use schemars::schema::{InstanceType, Schema, SchemaObject, StringValidation};
+use schemars::JsonSchema;
 
 use crate::errors::TypesError;
 
@@ -13,11 +15,14 @@ use crate::errors::TypesError;
 /// both JSON and Borsh deserialization. Oversized inputs fail with
 /// [`TypesError::StringTooLong`], preventing untrusted callers from passing arbitrarily
 /// large strings that would inflate storage and gas costs or grow hashes without bound.
+///
+/// With `PRINTABLE` set, control characters are rejected as well with
+/// [`TypesError::InvalidCharacters`], so the string is safe to display (e.g. token symbols).
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, BorshSerialize)]
 #[serde(transparent)]
-pub struct BoundedString<const MAX: usize>(String);
+pub struct BoundedString<const MAX: usize, const PRINTABLE: bool = false>(String);
 
-impl<const MAX: usize> BoundedString<MAX> {
+impl<const MAX: usize, const PRINTABLE: bool> BoundedString<MAX, PRINTABLE> {
     pub const MAX_LEN: usize = MAX;
 
     pub fn new(s: impl Into<String>) -> Result<Self, TypesError> {
@@ -25,33 +30,62 @@ impl<const MAX: usize> BoundedString<MAX> {
         if s.len() > MAX {
             return Err(TypesError::StringTooLong);
         }
+        if PRINTABLE && s.chars().any(char::is_control) {
+            return Err(TypesError::InvalidCharacters);
+        }
         Ok(Self(s))
     }
 
+    /// Lossy counterpart of [`BoundedString::new`] for strings that can't be rejected, e.g.
+    /// metadata of already deployed tokens: control characters are stripped with `PRINTABLE`
+    /// set and the rest is truncated to `MAX` bytes on a character boundary.
+    pub fn new_truncated(s: &str) -> Self {
+        let mut truncated = String::with_capacity(s.len().min(MAX));
+        for c in s.chars().filter(|c| !(PRINTABLE && c.is_control())) {
+            if truncated.len() + c.len_utf8() > MAX {
+                break;
+            }
+            truncated.push(c);
+        }
+        Self(truncated)
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
 }
 
-impl<const MAX: usize> fmt::Display for BoundedString<MAX> {
+impl<const MAX: usize, const PRINTABLE: bool> fmt::Display for BoundedString<MAX, PRINTABLE> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&self.0)
     }
 }
 
-impl<const MAX: usize> AsRef<str> for BoundedString<MAX> {
+impl<const MAX: usize, const PRINTABLE: bool> AsRef<str> for BoundedString<MAX, PRINTABLE> {
     fn as_ref(&self) -> &str {
         &self.0
     }
 }
 
-impl<const MAX: usize> From<BoundedString<MAX>> for String {
-    fn from(value: BoundedString<MAX>) -> Self {
+impl<const MAX: usize, const PRINTABLE: bool> From<BoundedString<MAX, PRINTABLE>> for String {
+    fn from(value: BoundedString<MAX, PRINTABLE>) -> Self {
         value.0
     }
 }
 
-impl<const MAX: usize> FromStr for BoundedString<MAX> {
+impl<const MAX: usize, const PRINTABLE: bool> PartialEq<str> for BoundedString<MAX, PRINTABLE> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl<const MAX: usize, const PRINTABLE: bool> PartialEq<&str> for BoundedString<MAX, PRINTABLE> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl<const MAX: usize, const PRINTABLE: bool> FromStr for BoundedString<MAX, PRINTABLE> {
     type Err = TypesError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -59,7 +93,9 @@ impl<const MAX: usize> FromStr for BoundedString<MAX> {
     }
 }
 
-impl<'de, const MAX: usize> Deserialize<'de> for BoundedString<MAX> {
+impl<'de, const MAX: usize, const PRINTABLE: bool> Deserialize<'de>
+    for BoundedString<MAX, PRINTABLE>
+{
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -69,18 +105,62 @@ impl<'de, const MAX: usize> Deserialize<'de> for BoundedString<MAX> {
     }
 }
 
-impl<const MAX: usize> BorshDeserialize for BoundedString<MAX> {
+impl<const MAX: usize, const PRINTABLE: bool> BorshDeserialize for BoundedString<MAX, PRINTABLE> {
     fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
         let s = String::deserialize_reader(reader)?;
         Self::new(s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
     }
 }
 
+impl<const MAX: usize, const PRINTABLE: bool> JsonSchema for BoundedString<MAX, PRINTABLE> {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        if PRINTABLE {
+            format!("PrintableString{MAX}")
+        } else {
+            format!("BoundedString{MAX}")
+        }
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> Schema {
+        SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            string: Some(Box::new(StringValidation {
+                max_length: u32::try_from(MAX).ok(),
+                pattern: PRINTABLE.then(|| r"^\P{Cc}*$".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[cfg(feature = "abi")]
+impl<const MAX: usize, const PRINTABLE: bool> borsh::BorshSchema for BoundedString<MAX, PRINTABLE> {
+    fn add_definitions_recursively(
+        definitions: &mut std::collections::BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        String::add_definitions_recursively(definitions);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        String::declaration()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     type Bs8 = BoundedString<8>;
+    type Printable8 = BoundedString<8, true>;
 
     #[test]
     fn new_accepts_within_limit() {
@@ -122,4 +202,24 @@ mod tests {
         let bytes = borsh::to_vec(&oversized).unwrap();
         assert!(borsh::from_slice::<Bs8>(&bytes).is_err());
     }
+
+    #[test]
+    fn printable_rejects_control_characters() {
+        assert_eq!(Printable8::new("NEAR €").unwrap(), "NEAR €");
+        assert_eq!(
+            Printable8::new("US\u{0}DC").unwrap_err(),
+            TypesError::InvalidCharacters
+        );
+        assert_eq!(
+            Printable8::new("USDC\n").unwrap_err(),
+            TypesError::InvalidCharacters
+        );
+        // Without `PRINTABLE` they are accepted.
+        assert!(Bs8::new("USDC\n").is_ok());
+
+        let bytes = borsh::to_vec(&"a\tb".to_string()).unwrap();
+        assert!(borsh::from_slice::<Printable8>(&bytes).is_err());
+        let err = near_sdk::serde_json::from_str::<Printable8>("\"a\\u0007\"").unwrap_err();
+        assert!(err.to_string().contains("ERR_INVALID_CHARACTERS"));
+    }
 }
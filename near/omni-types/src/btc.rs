@@ -2,9 +2,12 @@ use near_sdk::json_types::U128;
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{near, AccountId};
 
+use crate::utxo::UtxoNetwork;
+
 type OutPoint = String;
 
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "abi", derive(schemars::JsonSchema))]
 pub enum TokenReceiverMessage {
     DepositProtocolFee,
     Withdraw {
@@ -20,6 +23,8 @@ pub enum TokenReceiverMessage {
 pub struct UTXOChainConfig {
     pub connector: AccountId,
     pub token_id: AccountId,
+    /// Network the connector operates on, recipient addresses are validated against it.
+    pub network: UtxoNetwork,
 }
 
 #[near(serializers=[json])]
@@ -34,11 +34,15 @@ pub enum BridgeError {
     InvalidStorageAccountsLen,
     KeyExists,
     LowerFee,
+    MetadataSyncRateLimited,
     NativeFeeForUtxoChain,
     NativeTokenRequiredForChain,
+    NearTokenNotDeployable,
     NearWithdrawFailed,
     NotEnoughAttachedDeposit,
     NotEnoughGasForTokenTransfer(Gas),
+    NotNearToken,
+    NotOriginChain,
     OldTokenNotDeployed,
     OnlyFeeRecipientCanClaim,
     ParseAccountId,
@@ -48,16 +52,24 @@ pub enum BridgeError {
     ReadPromiseYieldId,
     SenderCanUpdateTokenFeeOnly,
     SenderIsNotConnector,
+    StaleMetadataProof,
     StorageFeeRecipientOmitted,
     StorageNativeFeeRecipientOmitted,
     StoragePendingTransfers,
     StorageRecipientOmitted,
+    StorageRegistrationNotFound,
+    TokenAlreadyAllowlisted,
     TokenAlreadyMigrated,
     TokenDecimalsNotFound,
     TokenExists,
+    TokenNotAllowlisted,
+    TokenNotDeployed,
     TokenNotFound,
     TokenNotMigrated,
     TokenNotRegistered,
+    TokenReviewAlreadyRequested,
+    TokenReviewNotFound,
+    TooManyStorageRegistrations,
     TransferAlreadyFinalised,
     TransferNotExist,
     UnknownFactory,
@@ -99,12 +111,26 @@ pub enum StorageError {
 #[strum(serialize_all = "shouty_snake_case", prefix = "ERR_")]
 #[non_exhaustive]
 pub enum TokenError {
+    AccountFrozen,
+    AccountNotFrozen,
+    AllowanceExceeded,
     FailedToReadState,
+    FreezeNotEnabled,
+    InsufficientStorageDeposit,
     InvalidCodeHash,
     InvalidParentAccount,
+    InvalidPermit,
+    InvalidPermitNonce,
+    InvalidPermitSignature,
+    InvalidPublicKey,
+    MintLimitExceeded,
     MissingPermission,
     NoInput,
     NoStateToMigrate,
+    PermitExpired,
+    PermitKeyNotSet,
+    TokenNotRegistered,
+    UnknownCodeVersion,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, AsRefStr, ErrorDisplay)]
@@ -113,13 +139,20 @@ pub enum TokenError {
 pub enum ProverError {
     ChainMismatch,
     FinalityMismatch,
+    GuardianSetExists,
+    GuardianSetExpired,
+    GuardianSetNotFound,
     HashNotSet,
     InvalidBlockHash,
+    InvalidGovernanceVaa,
     InvalidPayloadHash,
     InvalidPayloadValuesLength,
     InvalidProof,
+    InvalidProofKind,
     InvalidPublicKey,
     InvalidSignature,
+    InvalidVaa,
+    NoQuorum,
     ParseArgs,
     UnsupportedChain,
 }
@@ -128,9 +161,11 @@ pub enum ProverError {
 #[strum(serialize_all = "shouty_snake_case", prefix = "ERR_")]
 #[non_exhaustive]
 pub enum TypesError {
+    InvalidChecksum,
     InvalidHex,
     InvalidHexLength,
     StringTooLong,
+    InvalidCharacters,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, AsRefStr, ErrorDisplay)]
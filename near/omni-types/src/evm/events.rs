@@ -1,6 +1,7 @@
 use alloy::{primitives::Log, rlp::Decodable, sol, sol_types::SolEvent};
 
 use crate::{
+    parse_extended_metadata,
     prover_result::{
         DeployTokenMessage, FinTransferMessage, InitTransferMessage, LogMetadataMessage, ProofKind,
         ProverResult,
@@ -44,6 +45,16 @@ sol! {
         string symbol,
         uint8 decimals
     );
+
+    event LogMetadataV2(
+        address indexed tokenAddress,
+        string name,
+        string symbol,
+        uint8 decimals,
+        string icon,
+        string reference,
+        bytes32 referenceHash
+    );
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -81,13 +92,23 @@ pub fn parse_evm_proof(
             chain_kind,
             log_entry_data,
         )?)),
-        ProofKind::LogMetadata => Ok(ProverResult::LogMetadata(parse_evm_event(
-            chain_kind,
-            log_entry_data,
-        )?)),
+        ProofKind::LogMetadata => {
+            let message = if is_evm_event::<LogMetadataV2>(&log_entry_data)? {
+                parse_evm_event::<LogMetadataV2, _>(chain_kind, log_entry_data)?
+            } else {
+                parse_evm_event::<LogMetadata, _>(chain_kind, log_entry_data)?
+            };
+            Ok(ProverResult::LogMetadata(message))
+        }
     }
 }
 
+/// Checks the event signature so `LogMetadataV2` errors aren't hidden by a `LogMetadata` retry.
+fn is_evm_event<T: SolEvent>(log_rlp: &[u8]) -> Result<bool, String> {
+    let rlp_decoded = Log::decode(&mut &log_rlp[..]).map_err(stringify)?;
+    Ok(rlp_decoded.data.topics().first() == Some(&T::SIGNATURE_HASH))
+}
+
 pub trait TryFromLog<T>: Sized {
     type Error;
     fn try_from_log(chain_kind: ChainKind, value: T) -> Result<Self, Self::Error>;
@@ -164,21 +185,55 @@ impl TryFromLog<Log<LogMetadata>> for LogMetadataMessage {
                 chain_kind,
                 H160(event.data.tokenAddress.into()),
             )?,
-            name: event.data.name,
-            symbol: event.data.symbol,
+            name: event.data.name.parse().map_err(stringify)?,
+            symbol: event.data.symbol.parse().map_err(stringify)?,
             decimals: event.data.decimals,
 
             emitter_address: OmniAddress::new_from_evm_address(
                 chain_kind,
                 H160(event.address.into()),
             )?,
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            sequence: None,
+        })
+    }
+}
+
+impl TryFromLog<Log<LogMetadataV2>> for LogMetadataMessage {
+    type Error = String;
+
+    fn try_from_log(chain_kind: ChainKind, event: Log<LogMetadataV2>) -> Result<Self, Self::Error> {
+        let (icon, reference, reference_hash) = parse_extended_metadata(
+            &event.data.icon,
+            &event.data.reference,
+            event.data.referenceHash.as_slice(),
+        )?;
+
+        Ok(Self {
+            token_address: OmniAddress::new_from_evm_address(
+                chain_kind,
+                H160(event.data.tokenAddress.into()),
+            )?,
+            name: event.data.name.parse().map_err(stringify)?,
+            symbol: event.data.symbol.parse().map_err(stringify)?,
+            decimals: event.data.decimals,
+            emitter_address: OmniAddress::new_from_evm_address(
+                chain_kind,
+                H160(event.address.into()),
+            )?,
+            icon,
+            reference,
+            reference_hash,
+            sequence: None,
         })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use alloy::primitives::IntoLogData;
+    use alloy::primitives::{IntoLogData, LogData};
 
     use super::*;
     sol! {
@@ -228,4 +283,74 @@ mod tests {
         assert_eq!(FinTransfer::SIGNATURE_HASH, decoded_log.topics().0);
         assert_eq!(TestFinTransfer::SIGNATURE_HASH, decoded_test_log.topics().0);
     }
+
+    fn log_rlp(data: LogData) -> Vec<u8> {
+        alloy::rlp::encode(&Log {
+            address: [1; 20].into(),
+            data,
+        })
+    }
+
+    fn parse_log_metadata(log_rlp: Vec<u8>) -> LogMetadataMessage {
+        match parse_evm_proof(ProofKind::LogMetadata, ChainKind::Eth, log_rlp).unwrap() {
+            ProverResult::LogMetadata(message) => message,
+            _ => panic!("expected LogMetadata"),
+        }
+    }
+
+    #[test]
+    fn test_parse_log_metadata() {
+        let event = LogMetadata {
+            tokenAddress: [2; 20].into(),
+            name: "Token".to_owned(),
+            symbol: "TKN".to_owned(),
+            decimals: 18,
+        };
+
+        let message = parse_log_metadata(log_rlp(event.to_log_data()));
+
+        assert_eq!(message.symbol.as_str(), "TKN");
+        assert!(message.icon.is_none());
+        assert!(message.reference.is_none());
+        assert!(message.reference_hash.is_none());
+    }
+
+    #[test]
+    fn test_parse_log_metadata_v2() {
+        let event = LogMetadataV2 {
+            tokenAddress: [2; 20].into(),
+            name: "Token".to_owned(),
+            symbol: "TKN".to_owned(),
+            decimals: 18,
+            icon: "data:image/svg+xml,<svg/>".to_owned(),
+            reference: "https://example.com/token.json".to_owned(),
+            referenceHash: [3; 32].into(),
+        };
+
+        let message = parse_log_metadata(log_rlp(event.to_log_data()));
+
+        assert_eq!(message.symbol.as_str(), "TKN");
+        assert_eq!(message.icon.unwrap().as_str(), event.icon);
+        assert_eq!(message.reference.unwrap().as_str(), event.reference);
+        assert_eq!(message.reference_hash, Some(H256([3; 32])));
+    }
+
+    #[test]
+    fn test_parse_log_metadata_v2_without_extended_metadata() {
+        let event = LogMetadataV2 {
+            tokenAddress: [2; 20].into(),
+            name: "Token".to_owned(),
+            symbol: "TKN".to_owned(),
+            decimals: 18,
+            icon: String::new(),
+            reference: String::new(),
+            referenceHash: [0; 32].into(),
+        };
+
+        let message = parse_log_metadata(log_rlp(event.to_log_data()));
+
+        assert!(message.icon.is_none());
+        assert!(message.reference.is_none());
+        assert!(message.reference_hash.is_none());
+    }
 }
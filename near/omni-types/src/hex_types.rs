@@ -4,15 +4,17 @@ use core::str::FromStr;
 use hex::FromHex;
 use near_sdk::near;
 use near_sdk::serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use serde::de::Visitor;
 
 use crate::errors::TypesError;
+use crate::utils;
 
 // Macro to generate common implementations for hash types (H160, H256, etc.)
 macro_rules! impl_h_type {
     ($name:ident, $size:expr, $padded:expr) => {
         #[near(serializers = [borsh])]
-        #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+        #[derive(Debug, Clone, Default, Hash, PartialEq, Eq)]
         pub struct $name(pub [u8; $size]);
 
         impl FromStr for $name {
@@ -96,6 +98,20 @@ macro_rules! impl_h_type {
                 serializer.serialize_str(&self.to_string())
             }
         }
+
+        impl JsonSchema for $name {
+            fn is_referenceable() -> bool {
+                false
+            }
+
+            fn schema_name() -> String {
+                String::schema_name()
+            }
+
+            fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+                String::json_schema(gen)
+            }
+        }
     };
 }
 
@@ -103,6 +119,44 @@ macro_rules! impl_h_type {
 impl_h_type!(H160, 20, false);
 impl_h_type!(H256, 32, true);
 
+impl H160 {
+    /// Formats the address with the EIP-55 mixed-case checksum.
+    pub fn to_checksum_string(&self) -> String {
+        let address = hex::encode(self.0);
+        let hash = utils::keccak256(address.as_bytes());
+        let checksummed: String = address
+            .char_indices()
+            .map(|(i, c)| {
+                let nibble = if i % 2 == 0 {
+                    hash[i / 2] >> 4
+                } else {
+                    hash[i / 2] & 0x0f
+                };
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        format!("0x{checksummed}")
+    }
+
+    /// Parses the address like `from_str`, but rejects mixed-case input whose
+    /// EIP-55 checksum does not match. All-lowercase and all-uppercase input
+    /// carries no checksum and is accepted.
+    pub fn from_checksum_str(s: &str) -> Result<Self, TypesError> {
+        let address = Self::from_str(s)?;
+        let hex_str = s.strip_prefix("0x").unwrap_or(s);
+        let is_mixed_case = hex_str.bytes().any(|c| c.is_ascii_lowercase())
+            && hex_str.bytes().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case && address.to_checksum_string()[2..] != *hex_str {
+            return Err(TypesError::InvalidChecksum);
+        }
+        Ok(address)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,6 +206,36 @@ mod tests {
         assert_eq!(addr, deserialized);
     }
 
+    #[test]
+    fn test_h160_checksum() {
+        // Test vectors from EIP-55
+        for address in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let parsed = H160::from_checksum_str(address).unwrap();
+            assert_eq!(parsed.to_checksum_string(), address);
+            assert_eq!(parsed.to_string(), address.to_lowercase());
+
+            // Single-case input carries no checksum
+            assert_eq!(
+                H160::from_checksum_str(&address.to_lowercase()).unwrap(),
+                parsed
+            );
+            assert_eq!(
+                H160::from_checksum_str(&format!("0x{}", address[2..].to_uppercase())).unwrap(),
+                parsed
+            );
+        }
+
+        // Flipping the case of one letter breaks the checksum
+        let err =
+            H160::from_checksum_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD").unwrap_err();
+        assert_eq!(err, TypesError::InvalidChecksum);
+    }
+
     #[test]
     fn test_h256() {
         // Parse with and without 0x prefix
@@ -23,9 +23,13 @@ pub mod mpc_types;
 pub mod near_events;
 pub mod prover_args;
 pub mod prover_result;
+#[cfg(feature = "abi")]
+pub mod schema;
 pub mod sol_address;
 pub mod starknet;
+pub mod sui;
 pub mod utils;
+pub mod utxo;
 
 #[cfg(test)]
 mod tests;
@@ -83,6 +87,8 @@ pub enum ChainKind {
     Fogo,
     #[serde(alias = "aptos")]
     Aptos,
+    #[serde(alias = "sui")]
+    Sui,
 }
 
 impl ChainKind {
@@ -101,7 +107,8 @@ impl ChainKind {
             | Self::Sol
             | Self::Strk
             | Self::Fogo
-            | Self::Aptos => false,
+            | Self::Aptos
+            | Self::Sui => false,
         }
     }
 
@@ -119,7 +126,8 @@ impl ChainKind {
             | Self::Strk
             | Self::Abs
             | Self::Fogo
-            | Self::Aptos => false,
+            | Self::Aptos
+            | Self::Sui => false,
         }
     }
 
@@ -137,7 +145,50 @@ impl ChainKind {
             | Self::Abs
             | Self::Btc
             | Self::Zcash
-            | Self::Aptos => false,
+            | Self::Aptos
+            | Self::Sui => false,
+        }
+    }
+
+    /// Prefix of the chain in the string form of an `OmniAddress`.
+    pub const fn prefix(self) -> &'static str {
+        match self {
+            Self::Eth => "eth",
+            Self::Near => "near",
+            Self::Sol => "sol",
+            Self::Arb => "arb",
+            Self::Base => "base",
+            Self::Bnb => "bnb",
+            Self::Btc => "btc",
+            Self::Zcash => "zcash",
+            Self::Pol => "pol",
+            Self::HyperEvm => "hlevm",
+            Self::Strk => "strk",
+            Self::Abs => "abs",
+            Self::Fogo => "fogo",
+            Self::Aptos => "aptos",
+            Self::Sui => "sui",
+        }
+    }
+
+    fn from_prefix(prefix: &str) -> Result<Self, String> {
+        match prefix {
+            "eth" => Ok(Self::Eth),
+            "near" => Ok(Self::Near),
+            "sol" => Ok(Self::Sol),
+            "arb" => Ok(Self::Arb),
+            "base" => Ok(Self::Base),
+            "bnb" => Ok(Self::Bnb),
+            "btc" => Ok(Self::Btc),
+            "zcash" => Ok(Self::Zcash),
+            "pol" => Ok(Self::Pol),
+            "hlevm" => Ok(Self::HyperEvm),
+            "strk" => Ok(Self::Strk),
+            "abs" => Ok(Self::Abs),
+            "fogo" => Ok(Self::Fogo),
+            "aptos" => Ok(Self::Aptos),
+            "sui" => Ok(Self::Sui),
+            _ => Err(format!("Chain {prefix} is not supported")),
         }
     }
 }
@@ -174,6 +225,7 @@ impl TryFrom<u8> for ChainKind {
             11 => Ok(Self::Abs),
             12 => Ok(Self::Fogo),
             13 => Ok(Self::Aptos),
+            14 => Ok(Self::Sui),
             _ => Err(format!("{input:?} invalid chain kind")),
         }
     }
@@ -183,10 +235,23 @@ pub type EvmAddress = H160;
 pub type UTXOChainAddress = String;
 pub type StarknetAddress = H256;
 pub type AptosAddress = H256;
+pub type SuiAddress = H256;
 
 pub const ZERO_ACCOUNT_ID: &str =
     "0000000000000000000000000000000000000000000000000000000000000000";
 
+/// Validation applied by [`OmniAddress::parse`]. Both modes reject mixed-case
+/// EVM addresses with an invalid EIP-55 checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AddressParseMode {
+    /// Also accepts a missing chain prefix (Ethereum), hex without `0x` and
+    /// short-form 32-byte addresses such as `aptos:0x1`.
+    #[default]
+    Lenient,
+    /// Requires the chain prefix and full-length `0x`-prefixed hex.
+    Strict,
+}
+
 #[near(serializers=[borsh])]
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum OmniAddress {
@@ -204,6 +269,7 @@ pub enum OmniAddress {
     Abs(EvmAddress),
     Fogo(SolAddress),
     Aptos(AptosAddress),
+    Sui(SuiAddress),
 }
 
 impl OmniAddress {
@@ -224,6 +290,7 @@ impl OmniAddress {
             ChainKind::Abs => Ok(Self::Abs(H160::ZERO)),
             ChainKind::Fogo => Ok(Self::Fogo(SolAddress::ZERO)),
             ChainKind::Aptos => Ok(Self::Aptos(H256::ZERO)),
+            ChainKind::Sui => Ok(Self::Sui(H256::ZERO)),
         }
     }
 
@@ -267,6 +334,7 @@ impl OmniAddress {
             )),
             ChainKind::Strk => Ok(Self::Strk(H256(address.try_into().map_err(stringify)?))),
             ChainKind::Aptos => Ok(Self::Aptos(H256(address.try_into().map_err(stringify)?))),
+            ChainKind::Sui => Ok(Self::Sui(H256(address.try_into().map_err(stringify)?))),
         }
     }
 
@@ -286,25 +354,24 @@ impl OmniAddress {
             Self::Abs(_) => ChainKind::Abs,
             Self::Fogo(_) => ChainKind::Fogo,
             Self::Aptos(_) => ChainKind::Aptos,
+            Self::Sui(_) => ChainKind::Sui,
         }
     }
 
     pub fn encode(&self, separator: char, skip_zero_address: bool) -> String {
-        let (chain_str, address) = match self {
-            Self::Eth(address) => ("eth", address.to_string()),
-            Self::Near(address) => ("near", address.to_string()),
-            Self::Sol(address) => ("sol", address.to_string()),
-            Self::Arb(address) => ("arb", address.to_string()),
-            Self::Base(address) => ("base", address.to_string()),
-            Self::Bnb(address) => ("bnb", address.to_string()),
-            Self::Pol(address) => ("pol", address.to_string()),
-            Self::HyperEvm(address) => ("hlevm", address.to_string()),
-            Self::Btc(address) => ("btc", address.clone()),
-            Self::Zcash(address) => ("zcash", address.clone()),
-            Self::Strk(address) => ("strk", address.to_string()),
-            Self::Abs(address) => ("abs", address.to_string()),
-            Self::Fogo(address) => ("fogo", address.to_string()),
-            Self::Aptos(address) => ("aptos", address.to_string()),
+        let chain_str = self.get_chain().prefix();
+        let address = match self {
+            Self::Near(address) => address.to_string(),
+            Self::Eth(address)
+            | Self::Arb(address)
+            | Self::Base(address)
+            | Self::Bnb(address)
+            | Self::Pol(address)
+            | Self::HyperEvm(address)
+            | Self::Abs(address) => address.to_checksum_string(),
+            Self::Sol(address) | Self::Fogo(address) => address.to_string(),
+            Self::Btc(address) | Self::Zcash(address) => address.clone(),
+            Self::Strk(address) | Self::Aptos(address) | Self::Sui(address) => address.to_string(),
         };
 
         if skip_zero_address && self.is_zero() {
@@ -326,16 +393,18 @@ impl OmniAddress {
             Self::Near(address) => *address == ZERO_ACCOUNT_ID,
             Self::Sol(address) | Self::Fogo(address) => address.is_zero(),
             Self::Btc(address) | Self::Zcash(address) => address.is_empty(),
-            Self::Strk(address) | Self::Aptos(address) => address.is_zero(),
+            Self::Strk(address) | Self::Aptos(address) | Self::Sui(address) => address.is_zero(),
         }
     }
 
     pub fn get_token_prefix(&self) -> String {
         match self {
-            Self::Sol(address) => Self::hashed_token_prefix("sol", &H256(address.0)),
-            Self::Fogo(address) => Self::hashed_token_prefix("fogo", &H256(address.0)),
-            Self::Strk(address) => Self::hashed_token_prefix("strk", address),
-            Self::Aptos(address) => Self::hashed_token_prefix("aptos", address),
+            Self::Sol(address) | Self::Fogo(address) => {
+                Self::hashed_token_prefix(self.get_chain().prefix(), &H256(address.0))
+            }
+            Self::Strk(address) | Self::Aptos(address) | Self::Sui(address) => {
+                Self::hashed_token_prefix(self.get_chain().prefix(), address)
+            }
             Self::Eth(address) => {
                 if self.is_zero() {
                     "eth".to_string()
@@ -343,7 +412,64 @@ impl OmniAddress {
                     address.to_string()[2..].to_string()
                 }
             }
-            _ => self.encode('-', true),
+            // Account IDs are lowercase, so EVM addresses are not checksummed here
+            Self::Arb(address)
+            | Self::Base(address)
+            | Self::Bnb(address)
+            | Self::Pol(address)
+            | Self::HyperEvm(address)
+            | Self::Abs(address) => {
+                let prefix = self.get_chain().prefix();
+                if self.is_zero() {
+                    prefix.to_string()
+                } else {
+                    format!("{prefix}-{address}")
+                }
+            }
+            Self::Near(_) | Self::Btc(_) | Self::Zcash(_) => self.encode('-', true),
+        }
+    }
+
+    /// Account ID of the NEAR token that `deployer` deploys for this address.
+    pub fn get_token_account_id(&self, deployer: &AccountId) -> Result<AccountId, String> {
+        format!("{}.{deployer}", self.get_token_prefix())
+            .parse()
+            .map_err(stringify)
+    }
+
+    pub fn parse(input: &str, mode: AddressParseMode) -> Result<Self, String> {
+        let strict = mode == AddressParseMode::Strict;
+        let (chain, recipient) = match input.split_once(':') {
+            Some(parts) => parts,
+            None if !strict => ("eth", input),
+            None => return Err(format!("Address {input} has no chain prefix")),
+        };
+        let chain_kind = ChainKind::from_prefix(chain)?;
+
+        match chain_kind {
+            ChainKind::Near => Ok(Self::Near(recipient.parse().map_err(stringify)?)),
+            ChainKind::Eth
+            | ChainKind::Arb
+            | ChainKind::Base
+            | ChainKind::Bnb
+            | ChainKind::Pol
+            | ChainKind::HyperEvm
+            | ChainKind::Abs => {
+                if strict {
+                    Self::check_full_length_hex(recipient, 20)?;
+                }
+                Self::new_from_evm_address(
+                    chain_kind,
+                    H160::from_checksum_str(recipient).map_err(stringify)?,
+                )
+            }
+            ChainKind::Sol => Ok(Self::Sol(recipient.parse().map_err(stringify)?)),
+            ChainKind::Fogo => Ok(Self::Fogo(recipient.parse().map_err(stringify)?)),
+            ChainKind::Btc => Ok(Self::Btc(recipient.to_string())),
+            ChainKind::Zcash => Ok(Self::Zcash(recipient.to_string())),
+            ChainKind::Strk => Ok(Self::Strk(Self::parse_h256(recipient, strict)?)),
+            ChainKind::Aptos => Ok(Self::Aptos(Self::parse_h256(recipient, strict)?)),
+            ChainKind::Sui => Ok(Self::Sui(Self::parse_h256(recipient, strict)?)),
         }
     }
 
@@ -379,6 +505,20 @@ impl OmniAddress {
         }
     }
 
+    fn parse_h256(address: &str, strict: bool) -> Result<H256, String> {
+        if strict {
+            Self::check_full_length_hex(address, 32)?;
+        }
+        address.parse().map_err(stringify)
+    }
+
+    fn check_full_length_hex(address: &str, len: usize) -> Result<(), String> {
+        match address.strip_prefix("0x") {
+            Some(hex) if hex.len() == len * 2 => Ok(()),
+            _ => Err(TypesError::InvalidHexLength.to_string()),
+        }
+    }
+
     fn to_evm_address(address: &[u8]) -> Result<EvmAddress, String> {
         let address = if address.len() == 32 {
             &address[address.len() - 20..]
@@ -409,25 +549,7 @@ impl FromStr for OmniAddress {
     type Err = String;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (chain, recipient) = input.split_once(':').unwrap_or(("eth", input));
-
-        match chain {
-            "eth" => Ok(Self::Eth(recipient.parse().map_err(stringify)?)),
-            "near" => Ok(Self::Near(recipient.parse().map_err(stringify)?)),
-            "sol" => Ok(Self::Sol(recipient.parse().map_err(stringify)?)),
-            "arb" => Ok(Self::Arb(recipient.parse().map_err(stringify)?)),
-            "base" => Ok(Self::Base(recipient.parse().map_err(stringify)?)),
-            "bnb" => Ok(Self::Bnb(recipient.parse().map_err(stringify)?)),
-            "pol" => Ok(Self::Pol(recipient.parse().map_err(stringify)?)),
-            "hlevm" => Ok(Self::HyperEvm(recipient.parse().map_err(stringify)?)),
-            "abs" => Ok(Self::Abs(recipient.parse().map_err(stringify)?)),
-            "btc" => Ok(Self::Btc(recipient.to_string())),
-            "zcash" => Ok(Self::Zcash(recipient.to_string())),
-            "strk" => Ok(Self::Strk(recipient.parse().map_err(stringify)?)),
-            "fogo" => Ok(Self::Fogo(recipient.parse().map_err(stringify)?)),
-            "aptos" => Ok(Self::Aptos(recipient.parse().map_err(stringify)?)),
-            _ => Err(format!("Chain {chain} is not supported")),
-        }
+        Self::parse(input, AddressParseMode::Lenient)
     }
 }
 
@@ -487,6 +609,7 @@ impl<'de> Deserialize<'de> for OmniAddress {
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "abi", derive(JsonSchema))]
 pub enum BridgeOnTransferMsg {
     InitTransfer(InitTransferMsg),
     FastFinTransfer(FastFinTransferMsg),
@@ -500,8 +623,60 @@ pub const MAX_INIT_TRANSFER_MSG_LEN: usize = 2048;
 /// Maximum byte length for `InitTransferMsg::external_id` — large enough for UUIDs or
 /// hex-encoded 32-byte hashes, small enough to bound storage-account-hash inputs.
 pub const MAX_EXTERNAL_ID_LEN: usize = 64;
+/// Maximum byte length of a token name in [`BasicMetadata`] and metadata proofs.
+pub const MAX_TOKEN_NAME_LEN: usize = 128;
+/// Maximum byte length of a token symbol in [`BasicMetadata`] and metadata proofs.
+pub const MAX_TOKEN_SYMBOL_LEN: usize = 32;
+/// Maximum byte length of a token icon data URL in [`BasicMetadata`] and metadata payloads.
+pub const MAX_TOKEN_ICON_LEN: usize = 2048;
+/// Maximum byte length of a token metadata reference URL, matching the Metaplex URI limit
+/// so references signed for Solana fit into the token metadata.
+pub const MAX_TOKEN_REFERENCE_LEN: usize = 200;
+/// Maximum byte length of a NEAR account ID.
+pub const MAX_ACCOUNT_ID_LEN: usize = 64;
+/// Byte length of a hex-encoded UTXO transaction hash in [`UtxoId`].
+pub const UTXO_TX_HASH_LEN: usize = 64;
+
+/// Token name without control characters.
+pub type TokenName = BoundedString<MAX_TOKEN_NAME_LEN, true>;
+/// Token symbol without control characters.
+pub type TokenSymbol = BoundedString<MAX_TOKEN_SYMBOL_LEN, true>;
+/// Token icon data URL without control characters.
+pub type TokenIcon = BoundedString<MAX_TOKEN_ICON_LEN, true>;
+/// URL of the token's off-chain metadata without control characters.
+pub type TokenReference = BoundedString<MAX_TOKEN_REFERENCE_LEN, true>;
+
+/// Parses a token icon. Icons are optional, so anything that isn't a data URL within
+/// [`MAX_TOKEN_ICON_LEN`] is dropped instead of failing the whole metadata.
+pub fn parse_token_icon(icon: &str) -> Option<TokenIcon> {
+    if !icon.starts_with("data:") {
+        return None;
+    }
+    icon.parse().ok()
+}
+
+/// Parses the icon, reference and reference hash of a `LogMetadata` event, where empty
+/// values stand for missing fields.
+pub fn parse_extended_metadata(
+    icon: &str,
+    reference: &str,
+    reference_hash: &[u8],
+) -> Result<(Option<TokenIcon>, Option<TokenReference>, Option<H256>), String> {
+    let reference = if reference.is_empty() {
+        None
+    } else {
+        Some(reference.parse().map_err(stringify)?)
+    };
+    let reference_hash = if reference_hash.iter().all(|byte| *byte == 0) {
+        None
+    } else {
+        Some(H256(reference_hash.try_into().map_err(stringify)?))
+    };
+    Ok((parse_token_icon(icon), reference, reference_hash))
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "abi", derive(JsonSchema))]
 pub struct InitTransferMsg {
     pub recipient: OmniAddress,
     pub fee: U128,
@@ -522,6 +697,7 @@ impl InitTransferMsg {
 }
 
 #[derive(Serialize, Deserialize, BorshSerialize, BorshDeserialize, Debug, Clone)]
+#[cfg_attr(feature = "abi", derive(JsonSchema, borsh::BorshSchema))]
 pub struct FastFinTransferMsg {
     pub transfer_id: UnifiedTransferId,
     pub recipient: OmniAddress,
@@ -576,6 +752,12 @@ pub struct TransferId {
     pub origin_nonce: Nonce,
 }
 
+impl TransferId {
+    pub fn global_hash(&self) -> H256 {
+        UnifiedTransferId::from(*self).global_hash()
+    }
+}
+
 #[near(serializers=[borsh, json])]
 #[derive(Debug, Clone)]
 pub struct TransferMessage {
@@ -659,6 +841,7 @@ pub enum PayloadType {
     TransferMessage,
     Metadata,
     ClaimNativeFee,
+    MetadataV2,
 }
 
 #[near(serializers=[borsh, json])]
@@ -673,6 +856,35 @@ pub struct TransferMessagePayloadV1 {
     pub fee_recipient: Option<AccountId>,
 }
 
+/// Signed encoding of a [`TransferMessagePayload`] with a message for the destination chain.
+#[near(serializers=[borsh])]
+#[derive(Debug, Clone)]
+pub struct TransferMessagePayloadV2 {
+    pub prefix: PayloadType,
+    pub destination_nonce: Nonce,
+    pub transfer_id: TransferId,
+    pub token_address: OmniAddress,
+    pub amount: U128,
+    pub recipient: OmniAddress,
+    pub fee_recipient: Option<AccountId>,
+    pub message: Vec<u8>,
+}
+
+impl From<TransferMessagePayload> for TransferMessagePayloadV2 {
+    fn from(payload: TransferMessagePayload) -> Self {
+        Self {
+            prefix: payload.prefix,
+            destination_nonce: payload.destination_nonce,
+            transfer_id: payload.transfer_id,
+            token_address: payload.token_address,
+            amount: payload.amount,
+            recipient: payload.recipient,
+            fee_recipient: payload.fee_recipient,
+            message: payload.message,
+        }
+    }
+}
+
 impl From<TransferMessagePayload> for TransferMessagePayloadV1 {
     fn from(payload: TransferMessagePayload) -> Self {
         Self {
@@ -699,14 +911,74 @@ pub struct TransferMessagePayload {
     pub fee_recipient: Option<AccountId>,
     #[serde(default)]
     pub message: Vec<u8>,
+    /// `transfer_id.global_hash()`, logged for indexers.
+    ///
+    /// Not part of [`Self::encode_hashable`], so the signature doesn't cover it and existing
+    /// destination contracts keep verifying the same bytes. Destination chains must derive
+    /// it from the signed `transfer_id` with [`UnifiedTransferId::global_hash`] instead of
+    /// trusting this field.
+    #[serde(default)]
+    pub global_transfer_hash: H256,
 }
 
 impl TransferMessagePayload {
+    /// Signed encoding of the payload. `global_transfer_hash` is excluded.
     pub fn encode_hashable(&self) -> Result<Vec<u8>, String> {
         if self.message.is_empty() {
             borsh::to_vec(&TransferMessagePayloadV1::from(self.clone())).map_err(stringify)
         } else {
-            borsh::to_vec(self).map_err(stringify)
+            borsh::to_vec(&TransferMessagePayloadV2::from(self.clone())).map_err(stringify)
+        }
+    }
+}
+
+#[near(serializers=[borsh])]
+#[derive(Debug, Clone)]
+pub struct MetadataPayloadV1 {
+    pub prefix: PayloadType,
+    pub token: String,
+    pub name: TokenName,
+    pub symbol: TokenSymbol,
+    pub decimals: u8,
+}
+
+/// Signed encoding of a [`MetadataPayload`] with the icon and reference.
+#[near(serializers=[borsh])]
+#[derive(Debug, Clone)]
+pub struct MetadataPayloadV2 {
+    pub prefix: PayloadType,
+    pub token: String,
+    pub name: TokenName,
+    pub symbol: TokenSymbol,
+    pub decimals: u8,
+    pub icon: Option<TokenIcon>,
+    pub reference: Option<TokenReference>,
+    pub reference_hash: Option<H256>,
+}
+
+impl From<MetadataPayload> for MetadataPayloadV1 {
+    fn from(payload: MetadataPayload) -> Self {
+        Self {
+            prefix: payload.prefix,
+            token: payload.token,
+            name: payload.name,
+            symbol: payload.symbol,
+            decimals: payload.decimals,
+        }
+    }
+}
+
+impl From<MetadataPayload> for MetadataPayloadV2 {
+    fn from(payload: MetadataPayload) -> Self {
+        Self {
+            prefix: PayloadType::MetadataV2,
+            token: payload.token,
+            name: payload.name,
+            symbol: payload.symbol,
+            decimals: payload.decimals,
+            icon: payload.icon,
+            reference: payload.reference,
+            reference_hash: payload.reference_hash,
         }
     }
 }
@@ -716,9 +988,33 @@ impl TransferMessagePayload {
 pub struct MetadataPayload {
     pub prefix: PayloadType,
     pub token: String,
-    pub name: String,
-    pub symbol: String,
+    pub name: TokenName,
+    pub symbol: TokenSymbol,
     pub decimals: u8,
+    #[serde(default)]
+    pub icon: Option<TokenIcon>,
+    #[serde(default)]
+    pub reference: Option<TokenReference>,
+    #[serde(default)]
+    pub reference_hash: Option<H256>,
+}
+
+impl MetadataPayload {
+    #[must_use]
+    pub const fn has_extended_metadata(&self) -> bool {
+        self.icon.is_some() || self.reference.is_some() || self.reference_hash.is_some()
+    }
+
+    /// Payloads without an icon or reference keep the `MetadataPayloadV1` encoding, so
+    /// destination chains that don't support them can still deploy the token. Extended
+    /// payloads are tagged with `PayloadType::MetadataV2`.
+    pub fn encode_hashable(&self) -> Result<Vec<u8>, String> {
+        if self.has_extended_metadata() {
+            borsh::to_vec(&MetadataPayloadV2::from(self.clone())).map_err(stringify)
+        } else {
+            borsh::to_vec(&MetadataPayloadV1::from(self.clone())).map_err(stringify)
+        }
+    }
 }
 
 #[near(serializers=[borsh, json])]
@@ -745,9 +1041,24 @@ pub fn stringify<T: std::fmt::Display>(item: T) -> String {
 #[near(serializers=[json])]
 #[derive(Clone, Debug)]
 pub struct BasicMetadata {
-    pub name: String,
-    pub symbol: String,
+    pub name: TokenName,
+    pub symbol: TokenSymbol,
     pub decimals: u8,
+    #[serde(default)]
+    pub icon: Option<TokenIcon>,
+    #[serde(default)]
+    pub reference: Option<TokenReference>,
+    #[serde(default)]
+    pub reference_hash: Option<H256>,
+}
+
+/// Rolling limit on the amount a token can mint: at most `max_amount` within any
+/// `window_sec` seconds.
+#[near(serializers=[borsh, json])]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MintRateLimit {
+    pub max_amount: U128,
+    pub window_sec: u64,
 }
 
 #[near(serializers=[borsh, json])]
@@ -822,10 +1133,26 @@ impl From<TransferId> for UnifiedTransferId {
     }
 }
 
+/// Domain separator of [`UnifiedTransferId::global_hash`].
+pub const GLOBAL_TRANSFER_HASH_PREFIX: &[u8] = b"omni-bridge:transfer";
+
 impl UnifiedTransferId {
     pub fn is_utxo(&self) -> bool {
         matches!(self.kind, TransferIdKind::Utxo(_))
     }
+
+    /// 32-byte identifier of the transfer that every chain can derive from its ID:
+    /// `keccak256(GLOBAL_TRANSFER_HASH_PREFIX || borsh(self))`. For a nonce based ID the
+    /// borsh encoding is `origin_chain as u8 || 0u8 || origin_nonce as u64 LE`.
+    ///
+    /// Legs created on NEAR to forward a fast or UTXO transfer have their own ID and hash,
+    /// the origin leg is referenced by `TransferMessage::origin_transfer_id`.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn global_hash(&self) -> H256 {
+        let mut preimage = GLOBAL_TRANSFER_HASH_PREFIX.to_vec();
+        borsh::to_writer(&mut preimage, self).unwrap();
+        H256(utils::keccak256(&preimage))
+    }
 }
 
 impl TryInto<TransferId> for &UnifiedTransferId {
@@ -932,6 +1259,25 @@ pub struct FastTransferStatus {
     pub storage_owner: AccountId,
 }
 
+#[near(serializers=[json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalTransferStatus {
+    /// Stored on NEAR, waiting for the signature or the fee claim.
+    Pending,
+    /// Finalised on NEAR.
+    Finalised,
+}
+
+/// Transfer leg resolved from its [`UnifiedTransferId::global_hash`].
+#[near(serializers=[json])]
+#[derive(Debug, Clone)]
+pub struct ResolvedTransfer {
+    pub transfer_id: UnifiedTransferId,
+    pub status: GlobalTransferStatus,
+    /// Set while the transfer is pending.
+    pub transfer_message: Option<TransferMessage>,
+}
+
 #[near(serializers=[json])]
 #[derive(Debug, PartialEq)]
 pub enum DestinationChainMsg {
@@ -982,6 +1328,7 @@ pub fn get_native_token_address(chain_kind: ChainKind) -> Result<OmniAddress, St
         | ChainKind::Pol
         | ChainKind::HyperEvm
         | ChainKind::Abs
-        | ChainKind::Fogo => OmniAddress::new_zero(chain_kind),
+        | ChainKind::Fogo
+        | ChainKind::Sui => OmniAddress::new_zero(chain_kind),
     }
 }
@@ -1,15 +1,92 @@
 use near_sdk::json_types::U128;
-use near_sdk::serde_json::json;
+use near_sdk::serde_json::{self, json, Value};
 use near_sdk::{near, AccountId};
 
 use crate::mpc_types::SignatureResponse;
 use crate::{
-    BasicMetadata, FastTransfer, MetadataPayload, OmniAddress, TransferId, TransferMessage,
-    TransferMessagePayload, UtxoFinTransferMsg,
+    stringify, BasicMetadata, ChainKind, FastTransfer, MetadataPayload, OmniAddress, TransferId,
+    TransferMessage, TransferMessagePayload, UtxoFinTransferMsg, H256,
 };
 
+/// Prefix of NEP-297 event logs.
+pub const EVENT_JSON_PREFIX: &str = "EVENT_JSON:";
+/// NEP-297 `standard` of the bridge events.
+pub const EVENT_STANDARD: &str = "omni-bridge";
+/// NEP-297 `version` of the bridge events. Bumped on any change to the event data.
+pub const EVENT_VERSION: &str = "1.0.0";
+/// NEP-297 `version` of the events that carry [`TransferEventDetails`] and the global
+/// transfer hash.
+pub const EVENT_VERSION_V2: &str = "2.1.0";
+
+/// How events are written to the logs.
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventFormat {
+    /// Bare externally tagged JSON, e.g. `{"InitTransferEvent":{...}}`. Kept for
+    /// consumers that have not moved to NEP-297 yet.
+    Legacy,
+    /// `EVENT_JSON:{"standard":"omni-bridge","version":"1.0.0","event":"init_transfer_event","data":{...}}`
+    Nep297,
+    /// Same envelope with version `2.x`: transfer events also carry [`TransferEventDetails`]
+    /// and `global_transfer_hash`.
+    #[default]
+    Nep297V2,
+}
+
+/// Change of the locked token balance made while processing a transfer.
+#[near(serializers=[json, borsh])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockAction {
+    Locked {
+        chain_kind: ChainKind,
+        token_id: AccountId,
+        amount: U128,
+    },
+    Unlocked {
+        chain_kind: ChainKind,
+        token_id: AccountId,
+        amount: U128,
+    },
+    Unchanged,
+}
+
+/// Token the transfer is paid out in on a non-NEAR destination chain.
+#[near(serializers=[json, borsh])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DestinationToken {
+    pub token_address: OmniAddress,
+    pub decimals: u8,
+    pub origin_decimals: u8,
+    /// Transferred amount without the fee, in `decimals`.
+    pub normalized_amount: U128,
+    /// Fee in `decimals`.
+    pub normalized_fee: U128,
+}
+
+/// Context of a transfer event that is only logged from version `2.0.0` on.
+#[near(serializers=[json, borsh])]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferEventDetails {
+    /// Account that triggered the event: the relayer or the fee recipient.
+    pub relayer: AccountId,
+    /// Account whose storage deposit pays for the transfer, if any.
+    pub storage_owner: Option<AccountId>,
+    /// `None` when the tokens are released on NEAR.
+    pub destination_token: Option<DestinationToken>,
+    pub lock_actions: Vec<LockAction>,
+}
+
+#[near(serializers=[json])]
+struct Nep297Event {
+    standard: String,
+    version: String,
+    event: String,
+    data: Value,
+}
+
 #[near(serializers=[json])]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, strum_macros::AsRefStr)]
+#[strum(serialize_all = "snake_case")]
 pub enum OmniBridgeEvent {
     InitTransferEvent {
         transfer_message: TransferMessage,
@@ -17,13 +94,26 @@ pub enum OmniBridgeEvent {
     SignTransferEvent {
         signature: SignatureResponse,
         message_payload: TransferMessagePayload,
+        #[serde(flatten)]
+        details: Option<TransferEventDetails>,
     },
     FinTransferEvent {
         transfer_message: TransferMessage,
+        #[serde(flatten)]
+        details: Option<TransferEventDetails>,
     },
     FailedFinTransferEvent {
         transfer_message: TransferMessage,
     },
+    /// Withdrawal from a token that couldn't be initiated, so the burned tokens are minted
+    /// back to `sender_id`.
+    FailedWithdrawEvent {
+        sender_id: AccountId,
+        token_id: AccountId,
+        amount: U128,
+        recipient: OmniAddress,
+        reason: String,
+    },
     UpdateFeeEvent {
         transfer_message: TransferMessage,
     },
@@ -33,6 +123,8 @@ pub enum OmniBridgeEvent {
     },
     ClaimFeeEvent {
         transfer_message: TransferMessage,
+        #[serde(flatten)]
+        details: Option<TransferEventDetails>,
     },
     DeployTokenEvent {
         token_id: AccountId,
@@ -59,10 +151,131 @@ pub enum OmniBridgeEvent {
         old_token_id: AccountId,
         new_token_id: AccountId,
     },
+    TokenReviewRequestedEvent {
+        token_address: OmniAddress,
+        requester: AccountId,
+    },
+    TokenReviewApprovedEvent {
+        token_address: OmniAddress,
+    },
+    TokenReviewRejectedEvent {
+        token_address: OmniAddress,
+    },
 }
 
 impl OmniBridgeEvent {
-    pub fn to_log_string(&self) -> String {
-        json!(self).to_string()
+    /// NEP-297 event name, e.g. `init_transfer_event`.
+    pub fn event_name(&self) -> &str {
+        self.as_ref()
+    }
+
+    /// [`UnifiedTransferId::global_hash`] of the transfer leg the event belongs to. `None` for
+    /// token events and for UTXO transfer events, which do not carry the origin chain.
+    ///
+    /// [`UnifiedTransferId::global_hash`]: crate::UnifiedTransferId::global_hash
+    pub fn global_transfer_hash(&self) -> Option<H256> {
+        match self {
+            Self::InitTransferEvent { transfer_message }
+            | Self::FinTransferEvent {
+                transfer_message, ..
+            }
+            | Self::FailedFinTransferEvent { transfer_message }
+            | Self::UpdateFeeEvent { transfer_message }
+            | Self::ClaimFeeEvent {
+                transfer_message, ..
+            } => Some(transfer_message.get_transfer_id().global_hash()),
+            Self::SignTransferEvent {
+                message_payload, ..
+            } => Some(message_payload.transfer_id.global_hash()),
+            Self::FastTransferEvent { fast_transfer, .. } => {
+                Some(fast_transfer.transfer_id.global_hash())
+            }
+            Self::LogMetadataEvent { .. }
+            | Self::DeployTokenEvent { .. }
+            | Self::BindTokenEvent { .. }
+            | Self::UtxoTransferEvent { .. }
+            | Self::MigrateTokenEvent { .. }
+            | Self::FailedWithdrawEvent { .. }
+            | Self::TokenReviewRequestedEvent { .. }
+            | Self::TokenReviewApprovedEvent { .. }
+            | Self::TokenReviewRejectedEvent { .. } => None,
+        }
+    }
+
+    pub fn to_log_string(&self, format: EventFormat) -> String {
+        match format {
+            EventFormat::Legacy => json!(self.without_details()).to_string(),
+            EventFormat::Nep297 => {
+                self.to_nep297_string(EVENT_VERSION, Self::nep297_data(&self.without_details()))
+            }
+            EventFormat::Nep297V2 => {
+                let mut data = Self::nep297_data(self);
+                if let (Some(hash), Value::Object(fields)) =
+                    (self.global_transfer_hash(), &mut data)
+                {
+                    fields.insert("global_transfer_hash".to_string(), json!(hash));
+                }
+                self.to_nep297_string(EVENT_VERSION_V2, data)
+            }
+        }
+    }
+
+    fn nep297_data(event: &Self) -> Value {
+        match json!(event) {
+            Value::Object(tagged) => tagged.into_iter().next().map(|(_, data)| data),
+            _ => None,
+        }
+        .unwrap_or_default()
+    }
+
+    fn to_nep297_string(&self, version: &str, data: Value) -> String {
+        let event = json!({
+            "standard": EVENT_STANDARD,
+            "version": version,
+            "event": self.event_name(),
+            "data": data,
+        });
+        format!("{EVENT_JSON_PREFIX}{event}")
+    }
+
+    /// Copy of the event as logged before version `2.0.0`.
+    fn without_details(&self) -> Self {
+        let mut event = self.clone();
+        if let Self::SignTransferEvent { details, .. }
+        | Self::FinTransferEvent { details, .. }
+        | Self::ClaimFeeEvent { details, .. } = &mut event
+        {
+            *details = None;
+        }
+        event
+    }
+
+    /// Decodes a log line written in either format.
+    pub fn from_log_string(log: &str) -> Result<Self, String> {
+        let Some(event) = log.strip_prefix(EVENT_JSON_PREFIX) else {
+            return serde_json::from_str(log).map_err(stringify);
+        };
+
+        let event: Nep297Event = serde_json::from_str(event).map_err(stringify)?;
+        if event.standard != EVENT_STANDARD {
+            return Err(format!("Unexpected event standard: {}", event.standard));
+        }
+        let major = event.version.split('.').next();
+        if major != EVENT_VERSION.split('.').next() && major != EVENT_VERSION_V2.split('.').next() {
+            return Err(format!("Unsupported event version: {}", event.version));
+        }
+
+        let variant = event
+            .event
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect::<String>();
+        serde_json::from_value(json!({ variant: event.data })).map_err(stringify)
     }
 }
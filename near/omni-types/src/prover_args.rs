@@ -21,6 +21,34 @@ pub struct WormholeVerifyProofArgs {
 pub struct MpcVerifyProofArgs {
     pub proof_kind: ProofKind,
     pub sign_payload: Vec<u8>,
+    /// Index of the extracted value in the signed payload to build the result from.
+    pub value_index: u32,
+}
+
+/// `MpcVerifyProofArgs` layout without `value_index`, still accepted by the prover.
+#[near(serializers=[borsh])]
+#[derive(Debug, Clone)]
+pub struct MpcVerifyProofArgsV0 {
+    pub proof_kind: ProofKind,
+    pub sign_payload: Vec<u8>,
+}
+
+impl From<MpcVerifyProofArgsV0> for MpcVerifyProofArgs {
+    fn from(args: MpcVerifyProofArgsV0) -> Self {
+        Self {
+            proof_kind: args.proof_kind,
+            sign_payload: args.sign_payload,
+            value_index: 0,
+        }
+    }
+}
+
+impl MpcVerifyProofArgs {
+    /// Parses the current layout and falls back to [`MpcVerifyProofArgsV0`].
+    pub fn parse(input: &[u8]) -> Result<Self, std::io::Error> {
+        borsh::from_slice::<Self>(input)
+            .or_else(|_| borsh::from_slice::<MpcVerifyProofArgsV0>(input).map(Into::into))
+    }
 }
 
 #[near(serializers=[borsh, json])]
@@ -2,7 +2,10 @@ use near_sdk::json_types::U128;
 use near_sdk::{near, AccountId};
 use num_enum::IntoPrimitive;
 
-use crate::{Fee, Nonce, OmniAddress, TransferId};
+use crate::{
+    BasicMetadata, Fee, Nonce, OmniAddress, TokenIcon, TokenName, TokenReference, TokenSymbol,
+    TransferId, H256,
+};
 
 #[near(serializers=[borsh, json])]
 #[derive(Debug, Clone)]
@@ -40,10 +43,33 @@ pub struct DeployTokenMessage {
 #[derive(Debug, Clone)]
 pub struct LogMetadataMessage {
     pub token_address: OmniAddress,
-    pub name: String,
-    pub symbol: String,
+    pub name: TokenName,
+    pub symbol: TokenSymbol,
     pub decimals: u8,
     pub emitter_address: OmniAddress,
+    /// The EVM, Starknet, Aptos, Sui and wormhole `LogMetadata` events don't include an icon
+    /// or reference yet, so their parsers always leave these fields empty. They are filled
+    /// once the origin chain factories emit them.
+    pub icon: Option<TokenIcon>,
+    pub reference: Option<TokenReference>,
+    pub reference_hash: Option<H256>,
+    /// Position of the event on the origin chain, used to order metadata updates: the
+    /// block number for light client and MPC proofs, the emitter sequence for VAAs. Set by
+    /// the prover, `None` if the proof carries no ordering.
+    pub sequence: Option<u64>,
+}
+
+impl From<LogMetadataMessage> for BasicMetadata {
+    fn from(metadata: LogMetadataMessage) -> Self {
+        Self {
+            name: metadata.name,
+            symbol: metadata.symbol,
+            decimals: metadata.decimals,
+            icon: metadata.icon,
+            reference: metadata.reference,
+            reference_hash: metadata.reference_hash,
+        }
+    }
 }
 
 #[near(serializers=[borsh, json])]
@@ -55,6 +81,17 @@ pub enum ProverResult {
     LogMetadata(LogMetadataMessage),
 }
 
+impl ProverResult {
+    /// Sets the origin sequence of a `LogMetadata` result, other results are unchanged.
+    #[must_use]
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        if let Self::LogMetadata(metadata) = &mut self {
+            metadata.sequence = Some(sequence);
+        }
+        self
+    }
+}
+
 #[near(serializers=[borsh, json])]
 #[derive(Debug, Clone, Copy, Eq, PartialEq, IntoPrimitive)]
 #[repr(u8)]
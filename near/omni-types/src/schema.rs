@@ -0,0 +1,59 @@
+//! JSON schema of the omni-bridge wire types.
+//!
+//! The contract ABI produced by `cargo near abi` describes method arguments only.
+//! Messages passed inside `ft_transfer_call` `msg` strings and the events logged by
+//! the bridge never appear in method signatures, so they are exported here instead.
+
+use schemars::gen::SchemaSettings;
+use schemars::schema::{Metadata, RootSchema, SchemaObject, SubschemaValidation};
+
+use crate::btc::{TokenReceiverMessage, UTXOChainConfig};
+use crate::locker_args::{
+    AddDeployedTokenArgs, BindTokenArgs, ClaimFeeArgs, DeployTokenArgs, FinTransferArgs,
+};
+use crate::near_events::OmniBridgeEvent;
+use crate::prover_result::ProverResult;
+use crate::{
+    BridgeOnTransferMsg, FastTransfer, FastTransferStatus, MetadataPayload, TransferMessage,
+    TransferMessagePayload, UnifiedTransferId,
+};
+
+/// Returns a schema whose root accepts any of the exported types, with every type
+/// available under `definitions`.
+pub fn json_schema() -> RootSchema {
+    let mut gen = SchemaSettings::draft07().into_generator();
+    let roots = vec![
+        gen.subschema_for::<BridgeOnTransferMsg>(),
+        gen.subschema_for::<TokenReceiverMessage>(),
+        gen.subschema_for::<OmniBridgeEvent>(),
+        gen.subschema_for::<FinTransferArgs>(),
+        gen.subschema_for::<ClaimFeeArgs>(),
+        gen.subschema_for::<BindTokenArgs>(),
+        gen.subschema_for::<DeployTokenArgs>(),
+        gen.subschema_for::<AddDeployedTokenArgs>(),
+        gen.subschema_for::<TransferMessage>(),
+        gen.subschema_for::<TransferMessagePayload>(),
+        gen.subschema_for::<MetadataPayload>(),
+        gen.subschema_for::<FastTransfer>(),
+        gen.subschema_for::<FastTransferStatus>(),
+        gen.subschema_for::<UnifiedTransferId>(),
+        gen.subschema_for::<ProverResult>(),
+        gen.subschema_for::<UTXOChainConfig>(),
+    ];
+
+    RootSchema {
+        meta_schema: gen.settings().meta_schema.clone(),
+        schema: SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                title: Some("omni-types".to_string()),
+                ..Default::default()
+            })),
+            subschemas: Some(Box::new(SubschemaValidation {
+                one_of: Some(roots),
+                ..Default::default()
+            })),
+            ..Default::default()
+        },
+        definitions: gen.take_definitions(),
+    }
+}
@@ -3,6 +3,7 @@ use core::str::FromStr;
 
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{bs58, near};
+use schemars::JsonSchema;
 use serde::de::Visitor;
 
 #[near(serializers=[borsh])]
@@ -74,3 +75,17 @@ impl Serialize for SolAddress {
         serializer.serialize_str(&self.to_string())
     }
 }
+
+impl JsonSchema for SolAddress {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        String::schema_name()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    parse_extended_metadata,
     prover_result::{
         DeployTokenMessage, FinTransferMessage, InitTransferMessage, LogMetadataMessage,
     },
@@ -181,7 +182,12 @@ pub fn parse_deploy_token(
 /// data[0..] = name                      (ByteArray)
 /// data[..] = symbol                     (ByteArray)
 /// data[..] = decimals                   (u8 as felt)
+/// data[..] = icon                       (ByteArray, optional)
+/// data[..] = reference                  (ByteArray, optional)
+/// data[..] = reference_hash             (u256 as low and high felts, optional)
 /// ```
+///
+/// Events without the trailing icon, reference and reference hash are parsed without them.
 pub fn parse_log_metadata(
     from_address: &[u8; 32],
     keys: &[[u8; 32]],
@@ -203,15 +209,27 @@ pub fn parse_log_metadata(
     let name = cursor.read_byte_array()?;
     let symbol = cursor.read_byte_array()?;
     let decimals: u8 = cursor.read_u64()?.try_into().map_err(stringify)?;
+    let (icon, reference, reference_hash) = if cursor.is_empty() {
+        (None, None, None)
+    } else {
+        let icon = cursor.read_byte_array()?;
+        let reference = cursor.read_byte_array()?;
+        let reference_hash = cursor.read_u256()?;
+        parse_extended_metadata(&icon, &reference, &reference_hash)?
+    };
 
     let emitter_address = OmniAddress::Strk(H256(*from_address));
 
     Ok(LogMetadataMessage {
         token_address,
-        name,
-        symbol,
+        name: name.parse().map_err(stringify)?,
+        symbol: symbol.parse().map_err(stringify)?,
         decimals,
         emitter_address,
+        icon,
+        reference,
+        reference_hash,
+        sequence: None,
     })
 }
 
@@ -310,6 +328,20 @@ impl<'a> FeltCursor<'a> {
         felt_to_u128(&felt)
     }
 
+    /// Reads a Cairo `u256`, serialized as its low and high `u128` halves, as big-endian bytes.
+    fn read_u256(&mut self) -> Result<[u8; 32], String> {
+        let low = self.read_u128()?;
+        let high = self.read_u128()?;
+        let mut bytes = [0u8; 32];
+        bytes[..16].copy_from_slice(&high.to_be_bytes());
+        bytes[16..].copy_from_slice(&low.to_be_bytes());
+        Ok(bytes)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
     /// Reads a Cairo `ByteArray` serialized as felts.
     ///
     /// Cairo `ByteArray` Serde layout:
@@ -727,6 +759,36 @@ mod tests {
         assert_eq!(msg.decimals, 18);
         assert_eq!(msg.token_address, OmniAddress::Strk(H256(token_addr)));
         assert_eq!(msg.emitter_address, OmniAddress::Strk(H256(emitter)));
+        assert!(msg.icon.is_none());
+        assert!(msg.reference.is_none());
+        assert!(msg.reference_hash.is_none());
+    }
+
+    #[test]
+    fn test_parse_log_metadata_with_extended_metadata() {
+        let token_addr =
+            hex_felt("0000000000000000000000000000000000000000000000000000000000dd0001");
+        let emitter = hex_felt("0000000000000000000000000000000000000000000000000000000000ee0002");
+
+        let keys = vec![LOG_METADATA_SELECTOR, token_addr];
+
+        let mut data = Vec::new();
+        data.extend(encode_byte_array("Wrapped ETH")); // name
+        data.extend(encode_byte_array("WETH")); // symbol
+        data.push(u64_felt(18)); // decimals
+        data.extend(encode_byte_array("data:image/svg+xml,<svg/>")); // icon
+        data.extend(encode_byte_array("https://example.com/weth.json")); // reference
+        data.push(u128_felt(2)); // reference_hash low
+        data.push(u128_felt(1)); // reference_hash high
+
+        let msg = parse_log_metadata(&emitter, &keys, &data).unwrap();
+        let mut reference_hash = [0u8; 32];
+        reference_hash[15] = 1;
+        reference_hash[31] = 2;
+        assert_eq!(msg.name, "Wrapped ETH");
+        assert_eq!(msg.icon.unwrap(), "data:image/svg+xml,<svg/>");
+        assert_eq!(msg.reference.unwrap(), "https://example.com/weth.json");
+        assert_eq!(msg.reference_hash, Some(H256(reference_hash)));
     }
 
     #[test]
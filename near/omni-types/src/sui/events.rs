@@ -0,0 +1,400 @@
+//! Sui Move event parsing for the MPC omni-prover.
+//!
+//! A Sui event is delivered as its struct tag (`SuiEvent.type`) and the JSON
+//! rendering of the Move struct (`SuiEvent.parsedJson`), which follows the Sui
+//! JSON-RPC conventions:
+//!
+//! | Move type              | JSON encoding                               |
+//! |------------------------|---------------------------------------------|
+//! | `u8` / `u16` / `u32`   | JSON number (e.g. `18`)                     |
+//! | `u64` / `u128`         | JSON string (e.g. `"1000"`)                 |
+//! | `address` / `ID`       | `0x`-prefixed hex string                    |
+//! | `String`               | JSON string                                 |
+//! | `vector<u8>`           | JSON array of numbers (e.g. `[110, 101]`)   |
+//! | `Option<T>`            | `null` (None) / the encoded value (Some)    |
+//!
+//! The struct tag is `"0x<package>::omni_bridge::Event"`, where `<package>` is
+//! the package that originally defined the event type, so it stays the same
+//! across package upgrades. This module mirrors `crate::aptos::events`.
+
+use near_sdk::json_types::U128;
+use near_sdk::serde_json::{self, Value};
+
+use crate::{
+    prover_result::{
+        DeployTokenMessage, FinTransferMessage, InitTransferMessage, LogMetadataMessage, ProofKind,
+        ProverResult,
+    },
+    stringify, ChainKind, Fee, OmniAddress, TransferId, H256,
+};
+
+const INIT_TRANSFER_TAG: &str = "::omni_bridge::InitTransfer";
+const FIN_TRANSFER_TAG: &str = "::omni_bridge::FinTransfer";
+const DEPLOY_TOKEN_TAG: &str = "::omni_bridge::DeployToken";
+const LOG_METADATA_TAG: &str = "::omni_bridge::LogMetadata";
+
+/// Parsed omni-bridge Sui event variants.
+pub enum SuiBridgeEvent {
+    InitTransfer(InitTransferMessage),
+    FinTransfer(FinTransferMessage),
+    DeployToken(DeployTokenMessage),
+    LogMetadata(LogMetadataMessage),
+}
+
+fn event_json(data: &str) -> Result<Value, String> {
+    serde_json::from_str(data).map_err(|e| format!("Sui event: invalid JSON: {e}"))
+}
+
+fn field<'a>(v: &'a Value, key: &str) -> Result<&'a Value, String> {
+    v.get(key)
+        .ok_or_else(|| format!("Sui event: missing field '{key}'"))
+}
+
+fn field_str<'a>(v: &'a Value, key: &str) -> Result<&'a str, String> {
+    field(v, key)?
+        .as_str()
+        .ok_or_else(|| format!("Sui event: field '{key}' is not a string"))
+}
+
+fn field_u8(v: &Value, key: &str) -> Result<u8, String> {
+    let n = field(v, key)?
+        .as_u64()
+        .ok_or_else(|| format!("Sui event: field '{key}' is not an integer"))?;
+    u8::try_from(n).map_err(|_| format!("Sui event: field '{key}' exceeds u8 range"))
+}
+
+fn field_u64(v: &Value, key: &str) -> Result<u64, String> {
+    field_str(v, key)?
+        .parse()
+        .map_err(|_| format!("Sui event: field '{key}' is not a u64 string"))
+}
+
+fn field_u128(v: &Value, key: &str) -> Result<u128, String> {
+    field_str(v, key)?
+        .parse()
+        .map_err(|_| format!("Sui event: field '{key}' is not a u128 string"))
+}
+
+/// Parses a Sui `address`, left-padding the short form (`0x2`) to 32 bytes.
+fn parse_address(s: &str) -> Result<H256, String> {
+    let stripped = s.strip_prefix("0x").unwrap_or(s);
+    if stripped.is_empty() || stripped.len() > 64 {
+        return Err(format!("Sui event: invalid address '{s}'"));
+    }
+    let bytes = hex::decode(format!("{stripped:0>64}"))
+        .map_err(|e| format!("Sui event: invalid address hex: {e}"))?;
+    bytes
+        .try_into()
+        .map(H256)
+        .map_err(|_| "Sui event: address is not 32 bytes".to_string())
+}
+
+fn field_address(v: &Value, key: &str) -> Result<OmniAddress, String> {
+    parse_address(field_str(v, key)?).map(OmniAddress::Sui)
+}
+
+/// The emitting package is the address in front of the first `::` of the struct tag.
+fn type_tag_address(type_tag: &str) -> Result<OmniAddress, String> {
+    let addr = type_tag
+        .split("::")
+        .next()
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("Sui event: type tag missing package address: '{type_tag}'"))?;
+    parse_address(addr).map(OmniAddress::Sui)
+}
+
+fn field_bytes(v: &Value, key: &str) -> Result<Vec<u8>, String> {
+    field(v, key)?
+        .as_array()
+        .ok_or_else(|| format!("Sui event: field '{key}' is not a byte array"))?
+        .iter()
+        .map(|byte| {
+            byte.as_u64()
+                .and_then(|byte| u8::try_from(byte).ok())
+                .ok_or_else(|| format!("Sui event: field '{key}' is not a byte array"))
+        })
+        .collect()
+}
+
+fn field_option_str(v: &Value, key: &str) -> Result<Option<String>, String> {
+    match field(v, key)? {
+        Value::Null => Ok(None),
+        Value::String(s) => Ok(Some(s.clone())),
+        _ => Err(format!(
+            "Sui event: field '{key}' is not an optional string"
+        )),
+    }
+}
+
+/// Parses a Sui `InitTransfer` event.
+///
+/// # Move event layout
+/// ```text
+/// sender: address, token_address: address, origin_nonce: u64,
+/// amount: u128, fee: u128, native_fee: u128,
+/// recipient: String, message: vector<u8>
+/// ```
+pub fn parse_init_transfer(type_tag: &str, data: &str) -> Result<InitTransferMessage, String> {
+    if !type_tag.ends_with(INIT_TRANSFER_TAG) {
+        return Err(format!("InitTransfer: unexpected type tag '{type_tag}'"));
+    }
+    let emitter_address = type_tag_address(type_tag)?;
+    let v = event_json(data)?;
+
+    let msg = String::from_utf8(field_bytes(&v, "message")?)
+        .map_err(|e| format!("InitTransfer: message is not valid UTF-8: {e}"))?;
+
+    Ok(InitTransferMessage {
+        origin_nonce: field_u64(&v, "origin_nonce")?,
+        token: field_address(&v, "token_address")?,
+        amount: U128(field_u128(&v, "amount")?),
+        recipient: field_str(&v, "recipient")?.parse().map_err(stringify)?,
+        fee: Fee {
+            fee: U128(field_u128(&v, "fee")?),
+            native_fee: U128(field_u128(&v, "native_fee")?),
+        },
+        sender: field_address(&v, "sender")?,
+        msg,
+        emitter_address,
+    })
+}
+
+/// Parses a Sui `FinTransfer` event.
+///
+/// # Move event layout
+/// ```text
+/// origin_chain: u8, origin_nonce: u64, token_address: address,
+/// amount: u128, recipient: address,
+/// fee_recipient: Option<String>, message: Option<vector<u8>>
+/// ```
+pub fn parse_fin_transfer(type_tag: &str, data: &str) -> Result<FinTransferMessage, String> {
+    if !type_tag.ends_with(FIN_TRANSFER_TAG) {
+        return Err(format!("FinTransfer: unexpected type tag '{type_tag}'"));
+    }
+    let emitter_address = type_tag_address(type_tag)?;
+    let v = event_json(data)?;
+
+    Ok(FinTransferMessage {
+        transfer_id: TransferId {
+            origin_chain: field_u8(&v, "origin_chain")?.try_into()?,
+            origin_nonce: field_u64(&v, "origin_nonce")?,
+        },
+        amount: U128(field_u128(&v, "amount")?),
+        fee_recipient: field_option_str(&v, "fee_recipient")?.and_then(|s| s.parse().ok()),
+        emitter_address,
+    })
+}
+
+/// Parses a Sui `DeployToken` event.
+///
+/// # Move event layout
+/// ```text
+/// token_address: address, near_token_id: String, name: String,
+/// symbol: String, decimals: u8, origin_decimals: u8
+/// ```
+pub fn parse_deploy_token(type_tag: &str, data: &str) -> Result<DeployTokenMessage, String> {
+    if !type_tag.ends_with(DEPLOY_TOKEN_TAG) {
+        return Err(format!("DeployToken: unexpected type tag '{type_tag}'"));
+    }
+    let emitter_address = type_tag_address(type_tag)?;
+    let v = event_json(data)?;
+
+    Ok(DeployTokenMessage {
+        token: field_str(&v, "near_token_id")?.parse().map_err(stringify)?,
+        token_address: field_address(&v, "token_address")?,
+        decimals: field_u8(&v, "decimals")?,
+        origin_decimals: field_u8(&v, "origin_decimals")?,
+        emitter_address,
+    })
+}
+
+/// Parses a Sui `LogMetadata` event.
+///
+/// # Move event layout
+/// ```text
+/// token_address: address, name: String, symbol: String, decimals: u8
+/// ```
+pub fn parse_log_metadata(type_tag: &str, data: &str) -> Result<LogMetadataMessage, String> {
+    if !type_tag.ends_with(LOG_METADATA_TAG) {
+        return Err(format!("LogMetadata: unexpected type tag '{type_tag}'"));
+    }
+    let emitter_address = type_tag_address(type_tag)?;
+    let v = event_json(data)?;
+
+    Ok(LogMetadataMessage {
+        token_address: field_address(&v, "token_address")?,
+        name: field_str(&v, "name")?.parse().map_err(stringify)?,
+        symbol: field_str(&v, "symbol")?.parse().map_err(stringify)?,
+        decimals: field_u8(&v, "decimals")?,
+        emitter_address,
+        icon: None,
+        reference: None,
+        reference_hash: None,
+        sequence: None,
+    })
+}
+
+/// Dispatches to the correct parser based on the event `type_tag`.
+pub fn parse_sui_event(type_tag: &str, data: &str) -> Result<SuiBridgeEvent, String> {
+    if type_tag.ends_with(INIT_TRANSFER_TAG) {
+        parse_init_transfer(type_tag, data).map(SuiBridgeEvent::InitTransfer)
+    } else if type_tag.ends_with(FIN_TRANSFER_TAG) {
+        parse_fin_transfer(type_tag, data).map(SuiBridgeEvent::FinTransfer)
+    } else if type_tag.ends_with(DEPLOY_TOKEN_TAG) {
+        parse_deploy_token(type_tag, data).map(SuiBridgeEvent::DeployToken)
+    } else if type_tag.ends_with(LOG_METADATA_TAG) {
+        parse_log_metadata(type_tag, data).map(SuiBridgeEvent::LogMetadata)
+    } else {
+        Err(format!("Unknown Sui event type tag: '{type_tag}'"))
+    }
+}
+
+/// Dispatches to the correct parser based on `ProofKind`, validating that the
+/// event `type_tag` matches the expected kind.
+pub fn parse_sui_proof(
+    kind: ProofKind,
+    _chain_kind: ChainKind,
+    type_tag: &str,
+    data: &str,
+) -> Result<ProverResult, String> {
+    match kind {
+        ProofKind::InitTransfer => {
+            parse_init_transfer(type_tag, data).map(ProverResult::InitTransfer)
+        }
+        ProofKind::FinTransfer => parse_fin_transfer(type_tag, data).map(ProverResult::FinTransfer),
+        ProofKind::DeployToken => parse_deploy_token(type_tag, data).map(ProverResult::DeployToken),
+        ProofKind::LogMetadata => parse_log_metadata(type_tag, data).map(ProverResult::LogMetadata),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKAGE: &str = "0x5e3b7ac4f9a0e6c4c1d1b3f1e5f7b8cd2e4a6b8d0f1e3c5a7b9d1f3e5a7c9b1d";
+
+    /// Splits a `SuiEvent` JSON-RPC fixture into its struct tag and `parsedJson`.
+    fn fixture(event: &str) -> (String, String) {
+        let event: Value = serde_json::from_str(event).unwrap();
+        (
+            event["type"].as_str().unwrap().to_string(),
+            event["parsedJson"].to_string(),
+        )
+    }
+
+    fn sui_address(s: &str) -> OmniAddress {
+        OmniAddress::Sui(parse_address(s).unwrap())
+    }
+
+    #[test]
+    fn test_parse_init_transfer_fixture() {
+        let (tag, data) = fixture(include_str!("fixtures/init_transfer.json"));
+        let msg = parse_init_transfer(&tag, &data).unwrap();
+        assert_eq!(msg.origin_nonce, 7);
+        assert_eq!(msg.amount.0, 1000);
+        assert_eq!(msg.fee.fee.0, 10);
+        assert_eq!(msg.fee.native_fee.0, 5);
+        assert_eq!(msg.msg, "");
+        assert_eq!(msg.recipient.to_string(), "near:frolik.testnet");
+        assert_eq!(msg.token, sui_address("0x2"));
+        assert_eq!(
+            msg.sender,
+            sui_address("0x8b1e4d2f0a3c5e7b9d1f3a5c7e9b1d3f5a7c9e1b3d5f7a9c1e3b5d7f9a1c3e5b")
+        );
+        assert_eq!(msg.emitter_address, sui_address(PACKAGE));
+        assert_eq!(msg.emitter_address.get_chain(), ChainKind::Sui);
+    }
+
+    #[test]
+    fn test_parse_init_transfer_message_bytes() {
+        let (tag, data) = fixture(include_str!("fixtures/init_transfer.json"));
+        let data = data.replace(r#""message":[]"#, r#""message":[110,101,97,114]"#);
+        assert_eq!(parse_init_transfer(&tag, &data).unwrap().msg, "near");
+
+        let data = data.replace("[110,101,97,114]", "[256]");
+        assert!(parse_init_transfer(&tag, &data).is_err());
+    }
+
+    #[test]
+    fn test_parse_fin_transfer_fixture() {
+        let (tag, data) = fixture(include_str!("fixtures/fin_transfer.json"));
+        let msg = parse_fin_transfer(&tag, &data).unwrap();
+        assert_eq!(msg.transfer_id.origin_chain, ChainKind::Near);
+        assert_eq!(msg.transfer_id.origin_nonce, 42);
+        assert_eq!(msg.amount.0, 500);
+        assert_eq!(msg.fee_recipient.unwrap().to_string(), "fee.near");
+        assert_eq!(msg.emitter_address, sui_address(PACKAGE));
+
+        let data = data.replace(r#""fee.near""#, "null");
+        assert!(parse_fin_transfer(&tag, &data)
+            .unwrap()
+            .fee_recipient
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_deploy_token_fixture() {
+        let (tag, data) = fixture(include_str!("fixtures/deploy_token.json"));
+        let msg = parse_deploy_token(&tag, &data).unwrap();
+        assert_eq!(msg.token.to_string(), "wrap.testnet");
+        assert_eq!(msg.decimals, 9);
+        assert_eq!(msg.origin_decimals, 24);
+        assert_eq!(
+            msg.token_address,
+            sui_address("0x3f1a5c7e9b1d3f5a7c9e1b3d5f7a9c1e3b5d7f9a1c3e5b7d9f1a3c5e7b9d1f3a")
+        );
+        assert_eq!(msg.emitter_address, sui_address(PACKAGE));
+    }
+
+    #[test]
+    fn test_parse_log_metadata_fixture() {
+        let (tag, data) = fixture(include_str!("fixtures/log_metadata.json"));
+        let msg = parse_log_metadata(&tag, &data).unwrap();
+        assert_eq!(msg.name, "Sui");
+        assert_eq!(msg.symbol, "SUI");
+        assert_eq!(msg.decimals, 9);
+        assert_eq!(msg.token_address, sui_address("0x2"));
+    }
+
+    #[test]
+    fn test_parse_sui_event_dispatches_by_type_tag() {
+        for (fixture_json, expected) in [
+            (include_str!("fixtures/init_transfer.json"), "InitTransfer"),
+            (include_str!("fixtures/fin_transfer.json"), "FinTransfer"),
+            (include_str!("fixtures/deploy_token.json"), "DeployToken"),
+            (include_str!("fixtures/log_metadata.json"), "LogMetadata"),
+        ] {
+            let (tag, data) = fixture(fixture_json);
+            let parsed = match parse_sui_event(&tag, &data).unwrap() {
+                SuiBridgeEvent::InitTransfer(_) => "InitTransfer",
+                SuiBridgeEvent::FinTransfer(_) => "FinTransfer",
+                SuiBridgeEvent::DeployToken(_) => "DeployToken",
+                SuiBridgeEvent::LogMetadata(_) => "LogMetadata",
+            };
+            assert_eq!(parsed, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_sui_proof_rejects_kind_mismatch() {
+        let (tag, data) = fixture(include_str!("fixtures/log_metadata.json"));
+        assert!(matches!(
+            parse_sui_proof(ProofKind::LogMetadata, ChainKind::Sui, &tag, &data),
+            Ok(ProverResult::LogMetadata(_))
+        ));
+        assert!(parse_sui_proof(ProofKind::InitTransfer, ChainKind::Sui, &tag, &data).is_err());
+    }
+
+    #[test]
+    fn test_unknown_type_tag_rejected() {
+        assert!(parse_sui_event("0x2::coin::CoinMetadata", "{}").is_err());
+        assert!(parse_init_transfer("::omni_bridge::InitTransfer", "{}").is_err());
+    }
+
+    #[test]
+    fn test_numeric_string_required_for_u128() {
+        let (tag, data) = fixture(include_str!("fixtures/init_transfer.json"));
+        let data = data.replace(r#""amount":"1000""#, r#""amount":1000"#);
+        assert!(parse_init_transfer(&tag, &data).is_err());
+    }
+}
@@ -2,14 +2,19 @@ use near_sdk::json_types::U128;
 use near_sdk::serde_json;
 use near_sdk::{borsh, AccountId, NearToken};
 
+use crate::near_events::{
+    DestinationToken, EventFormat, LockAction, OmniBridgeEvent, TransferEventDetails,
+};
 use crate::{
-    get_native_token_address, stringify, BridgeError, ChainKind, DestinationChainMsg, Fee,
-    OmniAddress, OmniError, PayloadType, SolAddress, StorageBalanceError, TransferId,
-    TransferMessage, H160, H256,
+    get_native_token_address, parse_token_icon, stringify, AddressParseMode, BasicMetadata,
+    BridgeError, ChainKind, DestinationChainMsg, Fee, MetadataPayload, OmniAddress, OmniError,
+    PayloadType, SolAddress, StorageBalanceError, TokenSymbol, TransferId, TransferIdKind,
+    TransferMessage, TransferMessagePayload, TypesError, UnifiedTransferId, H160, H256,
+    MAX_TOKEN_ICON_LEN, MAX_TOKEN_SYMBOL_LEN,
 };
 use std::str::FromStr;
 
-fn chain_kinds_for_borsh() -> [ChainKind; 14] {
+fn chain_kinds_for_borsh() -> [ChainKind; 15] {
     [
         ChainKind::Eth,
         ChainKind::Near,
@@ -25,6 +30,7 @@ fn chain_kinds_for_borsh() -> [ChainKind; 14] {
         ChainKind::Abs,
         ChainKind::Fogo,
         ChainKind::Aptos,
+        ChainKind::Sui,
     ]
 }
 
@@ -56,6 +62,10 @@ fn omni_addresses_for_borsh() -> Vec<OmniAddress> {
             H256::from_str("0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf")
                 .unwrap(),
         ),
+        OmniAddress::Sui(
+            H256::from_str("0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf")
+                .unwrap(),
+        ),
     ]
 }
 
@@ -77,7 +87,10 @@ fn test_omni_address_serialization() {
     let serialized = serde_json::to_string(&address).unwrap();
     let deserialized = serde_json::from_str(&serialized).unwrap();
 
-    assert_eq!(serialized, format!("\"eth:{address_str}\""));
+    assert_eq!(
+        serialized,
+        "\"eth:0x5A08FeED678C056650b3eb4a5cb1b9BB6F0fE265\""
+    );
     assert_eq!(address, deserialized);
 }
 
@@ -89,6 +102,8 @@ fn test_payload_prefix() {
     assert_eq!(hex::encode(res), "01");
     let res = borsh::to_vec(&PayloadType::ClaimNativeFee).unwrap();
     assert_eq!(hex::encode(res), "02");
+    let res = borsh::to_vec(&PayloadType::MetadataV2).unwrap();
+    assert_eq!(hex::encode(res), "03");
 }
 
 #[test]
@@ -204,6 +219,14 @@ fn test_chain_kind_from_omni_address() {
         ChainKind::Aptos,
         "Aptos",
     );
+    test_chain_kind(
+        OmniAddress::Sui(
+            H256::from_str("0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf")
+                .unwrap(),
+        ),
+        ChainKind::Sui,
+        "Sui",
+    );
 }
 
 #[test]
@@ -292,6 +315,16 @@ fn test_omni_address_from_str() {
             )),
             "Should parse Aptos address",
         ),
+        (
+            "sui:0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf".to_string(),
+            Ok(OmniAddress::Sui(
+                H256::from_str(
+                    "0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf",
+                )
+                .unwrap(),
+            )),
+            "Should parse Sui address",
+        ),
         (
             "invalid_format".to_string(),
             Err("ERR_INVALID_HEX".to_string()),
@@ -317,7 +350,7 @@ fn test_omni_address_display() {
     let test_cases = vec![
         (
             OmniAddress::Eth(evm_addr.clone()),
-            format!("eth:{evm_addr}"),
+            format!("eth:{}", evm_addr.to_checksum_string()),
             "ETH address should format as eth:0x...",
         ),
         (
@@ -337,17 +370,17 @@ fn test_omni_address_display() {
         ),
         (
             OmniAddress::Arb(evm_addr.clone()),
-            format!("arb:{evm_addr}"),
+            format!("arb:{}", evm_addr.to_checksum_string()),
             "ARB address should format as arb:0x...",
         ),
         (
             OmniAddress::Base(evm_addr.clone()),
-            format!("base:{evm_addr}"),
+            format!("base:{}", evm_addr.to_checksum_string()),
             "BASE address should format as base:0x...",
         ),
         (
             OmniAddress::Abs(evm_addr.clone()),
-            format!("abs:{evm_addr}"),
+            format!("abs:{}", evm_addr.to_checksum_string()),
             "ABS address should format as abs:0x...",
         ),
         (
@@ -370,6 +403,16 @@ fn test_omni_address_display() {
             "aptos:0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf".to_string(),
             "Aptos address should format as aptos:0x...",
         ),
+        (
+            OmniAddress::Sui(
+                H256::from_str(
+                    "0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf",
+                )
+                .unwrap(),
+            ),
+            "sui:0x05558831a603eca8cd69a42d4251f08de3573039b69f23972265cac76639f1cf".to_string(),
+            "Sui address should format as sui:0x...",
+        ),
     ];
 
     for (address, expected, message) in test_cases {
@@ -377,6 +420,174 @@ fn test_omni_address_display() {
     }
 }
 
+#[test]
+fn test_omni_address_strict_parsing() {
+    let evm_addr = "0x5A08FeED678C056650b3eb4a5cb1b9BB6F0fE265";
+    let expected = OmniAddress::Eth(H160::from_str(evm_addr).unwrap());
+    for input in [
+        format!("eth:{evm_addr}"),
+        format!("eth:{}", evm_addr.to_lowercase()),
+    ] {
+        assert_eq!(
+            OmniAddress::parse(&input, AddressParseMode::Strict),
+            Ok(expected.clone())
+        );
+    }
+
+    let test_cases = [
+        (
+            evm_addr.to_string(),
+            "Address 0x5A08FeED678C056650b3eb4a5cb1b9BB6F0fE265 has no chain prefix".to_string(),
+        ),
+        (
+            format!("eth:{}", &evm_addr[2..]),
+            TypesError::InvalidHexLength.to_string(),
+        ),
+        (
+            "aptos:0xa".to_string(),
+            TypesError::InvalidHexLength.to_string(),
+        ),
+        (
+            "strk:4718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d".to_string(),
+            TypesError::InvalidHexLength.to_string(),
+        ),
+    ];
+    for (input, error) in test_cases {
+        assert_eq!(
+            OmniAddress::parse(&input, AddressParseMode::Strict),
+            Err(error),
+            "{input}"
+        );
+        assert!(OmniAddress::from_str(&input).is_ok(), "{input}");
+    }
+}
+
+#[test]
+fn test_omni_address_rejects_bad_checksum() {
+    // The checksummed form ends with `6F0fE265`
+    let input = "base:0x5A08FeED678C056650b3eb4a5cb1b9BB6F0FE265";
+    for mode in [AddressParseMode::Lenient, AddressParseMode::Strict] {
+        assert_eq!(
+            OmniAddress::parse(input, mode),
+            Err(TypesError::InvalidChecksum.to_string())
+        );
+    }
+    assert!(serde_json::from_str::<OmniAddress>(&format!("\"{input}\"")).is_err());
+}
+
+// Deterministic xorshift generator, so failures are reproducible without extra dependencies
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn bytes<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = [0; N];
+        for byte in &mut bytes {
+            *byte = self.next_u64().to_le_bytes()[0];
+        }
+        // Exercise leading zeros, which short-form and base58 encodings drop
+        let leading_zeros = usize::try_from(self.next_u64() % 4).unwrap() * N / 8;
+        bytes[..leading_zeros].fill(0);
+        bytes
+    }
+
+    fn string(&mut self, charset: &[u8], len: usize) -> String {
+        (0..len)
+            .map(|_| {
+                let index = usize::try_from(self.next_u64()).unwrap() % charset.len();
+                char::from(charset[index])
+            })
+            .collect()
+    }
+}
+
+fn random_omni_address(rng: &mut Rng, chain_kind: ChainKind) -> OmniAddress {
+    match chain_kind {
+        ChainKind::Near => {
+            let name = rng.string(b"abcdefghijklmnopqrstuvwxyz0123456789", 12);
+            OmniAddress::Near(format!("{name}.near").parse().unwrap())
+        }
+        _ if chain_kind.is_evm_chain() => {
+            OmniAddress::new_from_slice(chain_kind, &rng.bytes::<20>()).unwrap()
+        }
+        _ if chain_kind.is_utxo_chain() => {
+            let address = rng.string(
+                b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+                34,
+            );
+            OmniAddress::new_from_slice(chain_kind, address.as_bytes()).unwrap()
+        }
+        _ => OmniAddress::new_from_slice(chain_kind, &rng.bytes::<32>()).unwrap(),
+    }
+}
+
+#[test]
+fn test_fuzz_omni_address_round_trip() {
+    let mut rng = Rng(0x5eed_0123_4567_89ab);
+    for _ in 0..200 {
+        for chain_kind in chain_kinds_for_borsh() {
+            let address = random_omni_address(&mut rng, chain_kind);
+            let canonical = address.to_string();
+
+            for mode in [AddressParseMode::Lenient, AddressParseMode::Strict] {
+                let parsed = OmniAddress::parse(&canonical, mode).unwrap();
+                assert_eq!(parsed, address, "{canonical}");
+                assert_eq!(parsed.to_string(), canonical);
+            }
+
+            let json = serde_json::to_string(&address).unwrap();
+            assert_eq!(json, format!("\"{canonical}\""));
+            assert_eq!(serde_json::from_str::<OmniAddress>(&json).unwrap(), address);
+            assert_eq!(
+                borsh::from_slice::<OmniAddress>(&borsh::to_vec(&address).unwrap()).unwrap(),
+                address
+            );
+
+            let (prefix, raw) = canonical.split_once(':').unwrap();
+            match chain_kind {
+                _ if chain_kind.is_evm_chain() => {
+                    // Single-case forms carry no checksum and parse to the same address
+                    for raw in [raw.to_lowercase(), format!("0x{}", raw[2..].to_uppercase())] {
+                        let input = format!("{prefix}:{raw}");
+                        assert_eq!(OmniAddress::from_str(&input).unwrap(), address);
+                    }
+
+                    // Flipping the case of any checksummed letter must be rejected
+                    if let Some(position) = raw.find(|c: char| c.is_ascii_alphabetic() && c != 'x')
+                    {
+                        let mut tampered = raw.to_string().into_bytes();
+                        tampered[position] ^= 0x20;
+                        let tampered = String::from_utf8(tampered).unwrap();
+                        if tampered.bytes().skip(2).any(|c| c.is_ascii_lowercase())
+                            && tampered.bytes().any(|c| c.is_ascii_uppercase())
+                        {
+                            assert_eq!(
+                                OmniAddress::from_str(&format!("{prefix}:{tampered}")),
+                                Err(TypesError::InvalidChecksum.to_string())
+                            );
+                        }
+                    }
+                }
+                ChainKind::Strk | ChainKind::Aptos | ChainKind::Sui => {
+                    // Short form is only accepted by the lenient parser
+                    let short = format!("{prefix}:0x{}", raw[2..].trim_start_matches('0'));
+                    assert_eq!(OmniAddress::from_str(&short).unwrap(), address);
+                    if short != canonical {
+                        assert!(OmniAddress::parse(&short, AddressParseMode::Strict).is_err());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
 #[test]
 fn test_omni_address_visitor_expecting() {
     let invalid_value = 123;
@@ -571,13 +782,20 @@ fn test_get_evm_token_prefix() {
 #[test]
 fn test_token_id_validity() {
     // Testnet token deployer has the longest account id
-    let token_deployer = "omnidep.testnet";
+    let token_deployer: AccountId = "omnidep.testnet".parse().unwrap();
 
     for omni_address in omni_addresses_for_borsh() {
         let token_prefix: String = omni_address.get_token_prefix();
         let token_id = format!("{token_prefix}.{token_deployer}");
 
         assert!(AccountId::from_str(&token_id).is_ok());
+        assert_eq!(
+            omni_address
+                .get_token_account_id(&token_deployer)
+                .unwrap()
+                .as_str(),
+            token_id
+        );
     }
 }
 
@@ -684,3 +902,274 @@ fn test_errors_serialization() {
         "ERR_NOT_ENOUGH_STORAGE: required=100.00 NEAR, available=50.00 NEAR"
     );
 }
+
+#[test]
+fn test_event_log_formats() {
+    let event = OmniBridgeEvent::MigrateTokenEvent {
+        old_token_id: "old.near".parse().unwrap(),
+        new_token_id: "new.near".parse().unwrap(),
+    };
+    assert_eq!(event.event_name(), "migrate_token_event");
+
+    let legacy = event.to_log_string(EventFormat::Legacy);
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&legacy).unwrap(),
+        serde_json::json!({
+            "MigrateTokenEvent": { "old_token_id": "old.near", "new_token_id": "new.near" }
+        })
+    );
+
+    let nep297 = event.to_log_string(EventFormat::Nep297);
+    let nep297_json = nep297.strip_prefix("EVENT_JSON:").unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(nep297_json).unwrap(),
+        serde_json::json!({
+            "standard": "omni-bridge",
+            "version": "1.0.0",
+            "event": "migrate_token_event",
+            "data": { "old_token_id": "old.near", "new_token_id": "new.near" }
+        })
+    );
+
+    for log in [legacy, nep297] {
+        let OmniBridgeEvent::MigrateTokenEvent {
+            old_token_id,
+            new_token_id,
+        } = OmniBridgeEvent::from_log_string(&log).unwrap()
+        else {
+            panic!("Unexpected event decoded from {log}");
+        };
+        assert_eq!(old_token_id, "old.near");
+        assert_eq!(new_token_id, "new.near");
+    }
+}
+
+#[test]
+fn test_transfer_event_details_by_format() {
+    let details = TransferEventDetails {
+        relayer: "relayer.near".parse().unwrap(),
+        storage_owner: Some("relayer.near".parse().unwrap()),
+        destination_token: Some(DestinationToken {
+            token_address: OmniAddress::Eth(
+                "0x3ba4c387f786bfee076a58914f5bd38d668b42c3"
+                    .parse()
+                    .unwrap(),
+            ),
+            decimals: 6,
+            origin_decimals: 18,
+            normalized_amount: U128(900),
+            normalized_fee: U128(100),
+        }),
+        lock_actions: vec![LockAction::Locked {
+            chain_kind: ChainKind::Eth,
+            token_id: "token.near".parse().unwrap(),
+            amount: U128(1000),
+        }],
+    };
+    let event = OmniBridgeEvent::FinTransferEvent {
+        transfer_message: TransferMessage {
+            destination_nonce: 1,
+            origin_nonce: 123,
+            token: OmniAddress::Near("token.near".parse().unwrap()),
+            amount: U128(1000),
+            recipient: OmniAddress::Eth(
+                "0x3ba4c387f786bfee076a58914f5bd38d668b42c3"
+                    .parse()
+                    .unwrap(),
+            ),
+            fee: Fee::default(),
+            sender: OmniAddress::Near("alice.near".parse().unwrap()),
+            msg: String::new(),
+            origin_transfer_id: None,
+        },
+        details: Some(details.clone()),
+    };
+
+    let nep297_v2 = event.to_log_string(EventFormat::Nep297V2);
+    let nep297_v2_json: serde_json::Value =
+        serde_json::from_str(nep297_v2.strip_prefix("EVENT_JSON:").unwrap()).unwrap();
+    assert_eq!(nep297_v2_json["version"], "2.1.0");
+    assert_eq!(nep297_v2_json["data"]["relayer"], "relayer.near");
+    assert_eq!(
+        nep297_v2_json["data"]["global_transfer_hash"],
+        TransferId {
+            origin_chain: ChainKind::Near,
+            origin_nonce: 123,
+        }
+        .global_hash()
+        .to_string()
+    );
+    assert_eq!(
+        nep297_v2_json["data"]["destination_token"]["normalized_amount"],
+        "900"
+    );
+
+    let OmniBridgeEvent::FinTransferEvent {
+        details: decoded, ..
+    } = OmniBridgeEvent::from_log_string(&nep297_v2).unwrap()
+    else {
+        panic!("Unexpected event decoded from {nep297_v2}");
+    };
+    assert_eq!(decoded, Some(details));
+
+    // Existing consumers keep receiving the 1.0.0 shape
+    for log in [
+        event.to_log_string(EventFormat::Legacy),
+        event.to_log_string(EventFormat::Nep297),
+    ] {
+        assert!(!log.contains("relayer"), "{log}");
+        let OmniBridgeEvent::FinTransferEvent { details, .. } =
+            OmniBridgeEvent::from_log_string(&log).unwrap()
+        else {
+            panic!("Unexpected event decoded from {log}");
+        };
+        assert_eq!(details, None);
+    }
+}
+
+#[test]
+fn test_global_transfer_hash() {
+    let transfer_id = TransferId {
+        origin_chain: ChainKind::Eth,
+        origin_nonce: 1,
+    };
+    // keccak256("omni-bridge:transfer" || 0x00 || 0x00 || 1u64 LE), also checked by the Solana program
+    assert_eq!(
+        transfer_id.global_hash().to_string(),
+        "0x3e43011ac9e4d81228ad5d8e384d087c5602ac0fa8c96ced7a606b10c4f6148c"
+    );
+    assert_eq!(
+        UnifiedTransferId::from(transfer_id).global_hash(),
+        transfer_id.global_hash()
+    );
+
+    let utxo_transfer_id = UnifiedTransferId {
+        origin_chain: ChainKind::Btc,
+        kind: TransferIdKind::Utxo("ab@0".parse().unwrap()),
+    };
+    assert_ne!(
+        utxo_transfer_id.global_hash(),
+        UnifiedTransferId {
+            origin_chain: ChainKind::Zcash,
+            ..utxo_transfer_id.clone()
+        }
+        .global_hash()
+    );
+
+    // The hash is carried next to the signed payload, not inside it
+    let mut payload = TransferMessagePayload {
+        prefix: PayloadType::TransferMessage,
+        destination_nonce: 1,
+        transfer_id,
+        token_address: OmniAddress::Eth(H160::ZERO),
+        amount: U128(100),
+        recipient: OmniAddress::Eth(H160::ZERO),
+        fee_recipient: None,
+        message: vec![1, 2, 3],
+        global_transfer_hash: H256::ZERO,
+    };
+    let hashable = payload.encode_hashable().unwrap();
+    payload.global_transfer_hash = transfer_id.global_hash();
+    assert_eq!(payload.encode_hashable().unwrap(), hashable);
+}
+
+#[test]
+fn test_metadata_payload_encoding() {
+    let mut payload = MetadataPayload {
+        prefix: PayloadType::Metadata,
+        token: "token.near".to_string(),
+        name: "Token".parse().unwrap(),
+        symbol: "TKN".parse().unwrap(),
+        decimals: 18,
+        icon: None,
+        reference: None,
+        reference_hash: None,
+    };
+    let v1 = payload.encode_hashable().unwrap();
+    assert_eq!(
+        v1,
+        borsh::to_vec(&(PayloadType::Metadata, "token.near", "Token", "TKN", 18u8)).unwrap()
+    );
+
+    payload.icon = parse_token_icon("data:image/svg+xml,<svg/>");
+    let v2 = payload.encode_hashable().unwrap();
+    assert_eq!(
+        v2,
+        borsh::to_vec(&(
+            PayloadType::MetadataV2,
+            "token.near",
+            "Token",
+            "TKN",
+            18u8,
+            Some("data:image/svg+xml,<svg/>"),
+            None::<String>,
+            None::<H256>
+        ))
+        .unwrap()
+    );
+
+    let metadata: BasicMetadata =
+        serde_json::from_str(r#"{"name":"Token","symbol":"TKN","decimals":18}"#).unwrap();
+    assert!(metadata.icon.is_none() && metadata.reference.is_none());
+}
+
+#[test]
+fn test_parse_token_icon() {
+    assert!(parse_token_icon("data:image/png;base64,AAAA").is_some());
+    assert!(parse_token_icon("https://example.com/icon.png").is_none());
+    assert!(parse_token_icon(&format!("data:{}", "A".repeat(MAX_TOKEN_ICON_LEN))).is_none());
+    assert!(parse_token_icon("data:image/svg+xml,<svg>\n</svg>").is_none());
+}
+
+#[test]
+fn test_bounded_string_new_truncated() {
+    assert_eq!(TokenSymbol::new_truncated("T\nK\u{7f}N"), "TKN");
+    assert_eq!(
+        TokenSymbol::new_truncated(&"A".repeat(MAX_TOKEN_SYMBOL_LEN + 1)),
+        "A".repeat(MAX_TOKEN_SYMBOL_LEN).as_str()
+    );
+    // Multi-byte characters are never split.
+    let symbol = TokenSymbol::new_truncated(&"é".repeat(MAX_TOKEN_SYMBOL_LEN));
+    assert_eq!(symbol, "é".repeat(MAX_TOKEN_SYMBOL_LEN / 2).as_str());
+}
+
+#[test]
+fn test_event_decoder_rejects_foreign_logs() {
+    for log in [
+        r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[]}"#,
+        r#"EVENT_JSON:{"standard":"omni-bridge","version":"9.0.0","event":"migrate_token_event","data":{"old_token_id":"a.near","new_token_id":"b.near"}}"#,
+        r#"EVENT_JSON:{"standard":"omni-bridge","version":"1.0.0","event":"unknown_event","data":{}}"#,
+        "Yield init transfer until storage is available",
+    ] {
+        assert!(OmniBridgeEvent::from_log_string(log).is_err(), "{log}");
+    }
+}
+
+#[cfg(feature = "abi")]
+#[test]
+fn test_json_schema_exports_wire_types() {
+    let schema = serde_json::to_value(crate::schema::json_schema()).unwrap();
+    let definitions = schema["definitions"].as_object().unwrap();
+
+    for name in [
+        "BridgeOnTransferMsg",
+        "InitTransferMsg",
+        "FastFinTransferMsg",
+        "UtxoFinTransferMsg",
+        "OmniBridgeEvent",
+        "FinTransferArgs",
+        "ClaimFeeArgs",
+        "TransferMessage",
+        "TokenReceiverMessage",
+    ] {
+        assert!(definitions.contains_key(name), "missing {name}");
+    }
+
+    // Addresses and bounded strings are plain strings on the wire
+    let init_transfer = &definitions["InitTransferMsg"]["properties"];
+    assert_eq!(init_transfer["recipient"]["type"], "string");
+    assert_eq!(
+        init_transfer["external_id"]["maxLength"],
+        crate::MAX_EXTERNAL_ID_LEN
+    );
+}
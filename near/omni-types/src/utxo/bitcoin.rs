@@ -0,0 +1,207 @@
+use super::encoding::{self, Bech32Variant};
+use super::UtxoNetwork;
+
+/// BIP-173 limit on the length of a bech32 string.
+const MAX_SEGWIT_ADDRESS_LEN: usize = 90;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitcoinAddress {
+    P2pkh([u8; 20]),
+    P2sh([u8; 20]),
+    Segwit { version: u8, program: Vec<u8> },
+}
+
+impl UtxoNetwork {
+    const fn bitcoin_hrp(self) -> &'static str {
+        match self {
+            Self::Mainnet => "bc",
+            Self::Testnet => "tb",
+        }
+    }
+
+    const fn bitcoin_base58_versions(self) -> (u8, u8) {
+        match self {
+            Self::Mainnet => (0x00, 0x05),
+            Self::Testnet => (0x6f, 0xc4),
+        }
+    }
+}
+
+impl BitcoinAddress {
+    pub fn parse(address: &str, network: UtxoNetwork) -> Result<Self, String> {
+        let lowercase = address.to_ascii_lowercase();
+        if lowercase.starts_with("bc1") || lowercase.starts_with("tb1") {
+            Self::parse_segwit(address, network)
+        } else {
+            Self::parse_base58(address, network)
+        }
+    }
+
+    fn parse_segwit(address: &str, network: UtxoNetwork) -> Result<Self, String> {
+        if address.len() > MAX_SEGWIT_ADDRESS_LEN {
+            return Err("Invalid BTC address: too long".to_string());
+        }
+
+        let (hrp, data, variant) = encoding::bech32_decode(address)
+            .map_err(|err| format!("Invalid BTC address: {err}"))?;
+        if hrp != network.bitcoin_hrp() {
+            return Err(format!("BTC address is not a {network:?} address"));
+        }
+
+        let (&version, program) = data
+            .split_first()
+            .ok_or_else(|| "Invalid BTC address: empty witness program".to_string())?;
+        let program = encoding::convert_bits(program, 5, 8, false)
+            .map_err(|err| format!("Invalid BTC address: {err}"))?;
+
+        let expected_variant = if version == 0 {
+            Bech32Variant::Bech32
+        } else {
+            Bech32Variant::Bech32m
+        };
+        let valid_program_len = match version {
+            0 => program.len() == 20 || program.len() == 32,
+            1..=16 => (2..=40).contains(&program.len()),
+            _ => false,
+        };
+        if variant != expected_variant || !valid_program_len {
+            return Err(format!(
+                "Invalid BTC address: bad segwit v{version} program"
+            ));
+        }
+
+        Ok(Self::Segwit { version, program })
+    }
+
+    fn parse_base58(address: &str, network: UtxoNetwork) -> Result<Self, String> {
+        let payload = encoding::base58check_decode(address)
+            .map_err(|err| format!("Invalid BTC address: {err}"))?;
+        let (&version, hash) = payload
+            .split_first()
+            .ok_or_else(|| "Invalid BTC address length".to_string())?;
+        let hash: [u8; 20] = hash
+            .try_into()
+            .map_err(|_| "Invalid BTC address length".to_string())?;
+
+        let (p2pkh, p2sh) = network.bitcoin_base58_versions();
+        if version == p2pkh {
+            Ok(Self::P2pkh(hash))
+        } else if version == p2sh {
+            Ok(Self::P2sh(hash))
+        } else {
+            Err(format!("BTC address is not a {network:?} address"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segwit_address(hrp: &str, version: u8, program: &[u8], variant: Bech32Variant) -> String {
+        let mut data = vec![version];
+        data.extend(encoding::convert_bits(program, 8, 5, true).unwrap());
+        encoding::bech32_encode(hrp, &data, variant)
+    }
+
+    #[test]
+    fn test_parse_mainnet_addresses() {
+        assert!(matches!(
+            BitcoinAddress::parse("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", UtxoNetwork::Mainnet),
+            Ok(BitcoinAddress::P2pkh(_))
+        ));
+        assert!(matches!(
+            BitcoinAddress::parse("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy", UtxoNetwork::Mainnet),
+            Ok(BitcoinAddress::P2sh(_))
+        ));
+
+        for address in [
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+        ] {
+            let Ok(BitcoinAddress::Segwit { version, program }) =
+                BitcoinAddress::parse(address, UtxoNetwork::Mainnet)
+            else {
+                panic!("expected a segwit address: {address}");
+            };
+            assert_eq!(version, 0);
+            assert_eq!(
+                hex::encode(program),
+                "751e76e8199196d454941c45d1b3a323f1433bd6"
+            );
+        }
+
+        assert!(matches!(
+            BitcoinAddress::parse(
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                UtxoNetwork::Mainnet
+            ),
+            Ok(BitcoinAddress::Segwit { version: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_testnet_addresses() {
+        assert!(matches!(
+            BitcoinAddress::parse("mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn", UtxoNetwork::Testnet),
+            Ok(BitcoinAddress::P2pkh(_))
+        ));
+        assert!(matches!(
+            BitcoinAddress::parse(
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                UtxoNetwork::Testnet
+            ),
+            Ok(BitcoinAddress::Segwit { version: 0, .. })
+        ));
+
+        // Addresses are bound to a network
+        for address in [
+            "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        ] {
+            assert!(BitcoinAddress::parse(address, UtxoNetwork::Testnet).is_err());
+        }
+        assert!(BitcoinAddress::parse(
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+            UtxoNetwork::Mainnet
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_reject_invalid_addresses() {
+        for address in [
+            // Bad checksums
+            "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb",
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+            // Mixed case
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kV8f3t4",
+            // Longer than BIP-173 allows
+            &format!("bc1p{}", "q".repeat(85)),
+            "",
+            "not an address",
+        ] {
+            assert!(
+                BitcoinAddress::parse(address, UtxoNetwork::Mainnet).is_err(),
+                "{address}"
+            );
+        }
+
+        // Segwit v0 must use bech32 and v1+ must use bech32m
+        let v0_bech32m = segwit_address("bc", 0, &[7; 20], Bech32Variant::Bech32m);
+        let v1_bech32 = segwit_address("bc", 1, &[7; 32], Bech32Variant::Bech32);
+        // v0 programs are either 20 or 32 bytes
+        let v0_bad_len = segwit_address("bc", 0, &[7; 24], Bech32Variant::Bech32);
+        for address in [v0_bech32m, v1_bech32, v0_bad_len] {
+            assert!(
+                BitcoinAddress::parse(&address, UtxoNetwork::Mainnet).is_err(),
+                "{address}"
+            );
+        }
+        assert!(BitcoinAddress::parse(
+            &segwit_address("bc", 1, &[7; 32], Bech32Variant::Bech32m),
+            UtxoNetwork::Mainnet
+        )
+        .is_ok());
+    }
+}
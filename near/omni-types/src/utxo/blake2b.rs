@@ -0,0 +1,117 @@
+//! Minimal BLAKE2b (RFC 7693) with personalization, as needed by F4Jumble.
+//! NEAR has no BLAKE2b host function, and unified addresses are short, so a
+//! plain implementation is sufficient.
+
+const IV: [u64; 8] = [
+    0x6a09_e667_f3bc_c908,
+    0xbb67_ae85_84ca_a73b,
+    0x3c6e_f372_fe94_f82b,
+    0xa54f_f53a_5f1d_36f1,
+    0x510e_527f_ade6_82d1,
+    0x9b05_688c_2b3e_6c1f,
+    0x1f83_d9ab_fb41_bd6b,
+    0x5be0_cd19_137e_2179,
+];
+
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+const BLOCK_LEN: usize = 128;
+pub const MAX_OUTPUT_LEN: usize = 64;
+
+fn mix(v: &mut [u64; 16], [a, b, c, d]: [usize; 4], x: u64, y: u64) {
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+    v[d] = (v[d] ^ v[a]).rotate_right(32);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(24);
+    v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+    v[d] = (v[d] ^ v[a]).rotate_right(16);
+    v[c] = v[c].wrapping_add(v[d]);
+    v[b] = (v[b] ^ v[c]).rotate_right(63);
+}
+
+fn compress(h: &mut [u64; 8], block: &[u8; BLOCK_LEN], counter: u64, last: bool) {
+    let mut m = [0_u64; 16];
+    for (word, bytes) in m.iter_mut().zip(block.chunks_exact(8)) {
+        *word = u64::from_le_bytes(bytes.try_into().unwrap_or_default());
+    }
+
+    let mut v = [0_u64; 16];
+    v[..8].copy_from_slice(h);
+    v[8..].copy_from_slice(&IV);
+    v[12] ^= counter;
+    if last {
+        v[14] = !v[14];
+    }
+
+    for round in 0..12 {
+        let s = &SIGMA[round % 10];
+        mix(&mut v, [0, 4, 8, 12], m[s[0]], m[s[1]]);
+        mix(&mut v, [1, 5, 9, 13], m[s[2]], m[s[3]]);
+        mix(&mut v, [2, 6, 10, 14], m[s[4]], m[s[5]]);
+        mix(&mut v, [3, 7, 11, 15], m[s[6]], m[s[7]]);
+        mix(&mut v, [0, 5, 10, 15], m[s[8]], m[s[9]]);
+        mix(&mut v, [1, 6, 11, 12], m[s[10]], m[s[11]]);
+        mix(&mut v, [2, 7, 8, 13], m[s[12]], m[s[13]]);
+        mix(&mut v, [3, 4, 9, 14], m[s[14]], m[s[15]]);
+    }
+
+    for (i, word) in h.iter_mut().enumerate() {
+        *word ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Unkeyed BLAKE2b with an `output_len`-byte digest (at most 64).
+pub fn blake2b(output_len: usize, personal: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let output_len = output_len.min(MAX_OUTPUT_LEN);
+    let mut h = IV;
+    h[0] ^= 0x0101_0000 ^ output_len as u64;
+    h[6] ^= u64::from_le_bytes(personal[..8].try_into().unwrap_or_default());
+    h[7] ^= u64::from_le_bytes(personal[8..].try_into().unwrap_or_default());
+
+    let block_count = data.len().div_ceil(BLOCK_LEN).max(1);
+    let mut counter = 0_u64;
+    for index in 0..block_count {
+        let chunk = &data[index * BLOCK_LEN..data.len().min((index + 1) * BLOCK_LEN)];
+        let mut block = [0; BLOCK_LEN];
+        block[..chunk.len()].copy_from_slice(chunk);
+        counter += chunk.len() as u64;
+        compress(&mut h, &block, counter, index + 1 == block_count);
+    }
+
+    h.iter()
+        .flat_map(|word| word.to_le_bytes())
+        .take(output_len)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blake2b() {
+        // RFC 7693 Appendix A
+        assert_eq!(
+            hex::encode(blake2b(64, &[0; 16], b"abc")),
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+             7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+        );
+        assert_eq!(
+            hex::encode(blake2b(64, &[0; 16], b"")),
+            "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419\
+             d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce"
+        );
+        assert_eq!(blake2b(20, &[0; 16], b"abc").len(), 20);
+    }
+}
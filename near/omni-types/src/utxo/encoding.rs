@@ -0,0 +1,176 @@
+use near_sdk::bs58;
+
+use crate::utils;
+
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_CHECKSUM_LEN: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bech32Variant {
+    /// BIP-173 checksum, used by segwit v0.
+    Bech32,
+    /// BIP-350 checksum, used by segwit v1+ and Zcash unified addresses.
+    Bech32m,
+}
+
+impl Bech32Variant {
+    const fn constant(self) -> u32 {
+        match self {
+            Self::Bech32 => 1,
+            Self::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    const GENERATOR: [u32; 5] = [
+        0x3b6a_57b2,
+        0x2650_8e6d,
+        0x1ea1_19fa,
+        0x3d42_33dd,
+        0x2a14_62b3,
+    ];
+
+    let mut checksum = 1_u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ u32::from(value);
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    hrp.bytes()
+        .map(|c| c >> 5)
+        .chain(core::iter::once(0))
+        .chain(hrp.bytes().map(|c| c & 0x1f))
+}
+
+/// Decodes a bech32 or bech32m string into its lowercase human-readable part
+/// and 5-bit data without the checksum. Length limits are left to the caller.
+pub fn bech32_decode(input: &str) -> Result<(String, Vec<u8>, Bech32Variant), String> {
+    if input.bytes().any(|c| c.is_ascii_lowercase())
+        && input.bytes().any(|c| c.is_ascii_uppercase())
+    {
+        return Err("Mixed-case bech32 string".to_string());
+    }
+
+    let input = input.to_ascii_lowercase();
+    let (hrp, data) = input
+        .rsplit_once('1')
+        .ok_or_else(|| "Missing bech32 separator".to_string())?;
+    if hrp.is_empty()
+        || hrp.bytes().any(|c| !(33..=126).contains(&c))
+        || data.len() < BECH32_CHECKSUM_LEN
+    {
+        return Err("Invalid bech32 string".to_string());
+    }
+
+    let data = data
+        .bytes()
+        .map(|c| {
+            BECH32_CHARSET
+                .iter()
+                .position(|&symbol| symbol == c)
+                .and_then(|value| u8::try_from(value).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| "Invalid bech32 character".to_string())?;
+
+    let variant = match polymod(hrp_expand(hrp).chain(data.iter().copied())) {
+        c if c == Bech32Variant::Bech32.constant() => Bech32Variant::Bech32,
+        c if c == Bech32Variant::Bech32m.constant() => Bech32Variant::Bech32m,
+        _ => return Err("Invalid bech32 checksum".to_string()),
+    };
+
+    Ok((
+        hrp.to_string(),
+        data[..data.len() - BECH32_CHECKSUM_LEN].to_vec(),
+        variant,
+    ))
+}
+
+/// Encodes 5-bit `data` as a lowercase bech32 or bech32m string.
+pub fn bech32_encode(hrp: &str, data: &[u8], variant: Bech32Variant) -> String {
+    let checksum = polymod(
+        hrp_expand(hrp)
+            .chain(data.iter().copied())
+            .chain([0; BECH32_CHECKSUM_LEN]),
+    ) ^ variant.constant();
+
+    let checksum = (0..BECH32_CHECKSUM_LEN)
+        .rev()
+        .map(|i| (checksum >> (5 * i)) & 0x1f);
+    let symbols = data
+        .iter()
+        .map(|&value| usize::from(value))
+        .chain(checksum.map(|value| value as usize))
+        .map(|value| char::from(BECH32_CHARSET[value]));
+
+    format!("{hrp}1{}", symbols.collect::<String>())
+}
+
+/// Regroups bits, e.g. 8-bit bytes into 5-bit bech32 values and back. Without
+/// `pad`, leftover bits must be zero and shorter than `from` bits.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, String> {
+    let max_value = (1_u32 << to) - 1;
+    let mut acc = 0_u32;
+    let mut bits = 0_u32;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for &value in data {
+        let value = u32::from(value);
+        if value >> from != 0 {
+            return Err("Invalid data for bit conversion".to_string());
+        }
+        acc = (acc << from) | value;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & max_value).to_le_bytes()[0]);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & max_value).to_le_bytes()[0]);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max_value != 0 {
+        return Err("Invalid padding in bit conversion".to_string());
+    }
+
+    Ok(result)
+}
+
+fn base58check_checksum(payload: &[u8]) -> [u8; 4] {
+    let hash = utils::sha256(&utils::sha256(payload));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Decodes a base58check string and returns the payload without the checksum.
+pub fn base58check_decode(input: &str) -> Result<Vec<u8>, String> {
+    let mut data = bs58::decode(input)
+        .into_vec()
+        .map_err(|err| err.to_string())?;
+    if data.len() < 4 {
+        return Err("Base58check string is too short".to_string());
+    }
+
+    let checksum = data.split_off(data.len() - 4);
+    if checksum != base58check_checksum(&data) {
+        return Err("Invalid base58check checksum".to_string());
+    }
+
+    Ok(data)
+}
+
+pub fn base58check_encode(payload: &[u8]) -> String {
+    let mut data = payload.to_vec();
+    data.extend_from_slice(&base58check_checksum(payload));
+    bs58::encode(data).into_string()
+}
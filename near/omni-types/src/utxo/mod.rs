@@ -0,0 +1,29 @@
+use near_sdk::near;
+
+use crate::ChainKind;
+
+mod blake2b;
+mod encoding;
+
+pub mod bitcoin;
+pub mod zcash;
+
+#[near(serializers = [borsh, json])]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UtxoNetwork {
+    Mainnet,
+    Testnet,
+}
+
+/// Checks that `address` decodes to a valid `chain_kind` address on `network`.
+pub fn validate_address(
+    chain_kind: ChainKind,
+    address: &str,
+    network: UtxoNetwork,
+) -> Result<(), String> {
+    match chain_kind {
+        ChainKind::Btc => bitcoin::BitcoinAddress::parse(address, network).map(drop),
+        ChainKind::Zcash => zcash::ZcashAddress::parse(address, network).map(drop),
+        _ => Err(format!("{chain_kind:?} is not a UTXO chain")),
+    }
+}
@@ -0,0 +1,443 @@
+use super::blake2b::{blake2b, MAX_OUTPUT_LEN};
+use super::encoding::{self, Bech32Variant};
+use super::UtxoNetwork;
+
+const P2PKH_TYPECODE: u32 = 0x00;
+const P2SH_TYPECODE: u32 = 0x01;
+const SAPLING_TYPECODE: u32 = 0x02;
+const ORCHARD_TYPECODE: u32 = 0x03;
+
+const SHIELDED_RECEIVER_LEN: usize = 43;
+const UNIFIED_PADDING_LEN: usize = 16;
+const F4JUMBLE_MIN_LEN: usize = 48;
+const F4JUMBLE_MAX_LEN: usize = 4_194_368;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZcashAddress {
+    TransparentP2pkh([u8; 20]),
+    TransparentP2sh([u8; 20]),
+    Unified(UnifiedAddress),
+}
+
+/// ZIP-316 unified address: a set of receivers, at least one of them shielded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnifiedAddress {
+    pub receivers: Vec<Receiver>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Receiver {
+    P2pkh([u8; 20]),
+    P2sh([u8; 20]),
+    Sapling([u8; SHIELDED_RECEIVER_LEN]),
+    Orchard([u8; SHIELDED_RECEIVER_LEN]),
+    Unknown { typecode: u32, data: Vec<u8> },
+}
+
+impl UtxoNetwork {
+    const fn zcash_transparent_prefixes(self) -> ([u8; 2], [u8; 2]) {
+        match self {
+            Self::Mainnet => ([0x1c, 0xb8], [0x1c, 0xbd]),
+            Self::Testnet => ([0x1d, 0x25], [0x1c, 0xba]),
+        }
+    }
+
+    const fn zcash_unified_hrp(self) -> &'static str {
+        match self {
+            Self::Mainnet => "u",
+            Self::Testnet => "utest",
+        }
+    }
+}
+
+impl ZcashAddress {
+    pub fn parse(address: &str, network: UtxoNetwork) -> Result<Self, String> {
+        if address.starts_with('t') {
+            Self::parse_transparent(address, network)
+        } else {
+            UnifiedAddress::parse(address, network).map(Self::Unified)
+        }
+    }
+
+    fn parse_transparent(address: &str, network: UtxoNetwork) -> Result<Self, String> {
+        let payload = encoding::base58check_decode(address)
+            .map_err(|err| format!("Invalid Zcash address: {err}"))?;
+        let (prefix, hash) = payload
+            .split_at_checked(2)
+            .ok_or_else(|| "Invalid Zcash address length".to_string())?;
+        let hash: [u8; 20] = hash
+            .try_into()
+            .map_err(|_| "Invalid Zcash address length".to_string())?;
+
+        let (p2pkh, p2sh) = network.zcash_transparent_prefixes();
+        if prefix == p2pkh {
+            Ok(Self::TransparentP2pkh(hash))
+        } else if prefix == p2sh {
+            Ok(Self::TransparentP2sh(hash))
+        } else {
+            Err(format!("Zcash address is not a {network:?} t-address"))
+        }
+    }
+}
+
+impl Receiver {
+    fn new(typecode: u32, data: &[u8]) -> Result<Self, String> {
+        let invalid_length = || format!("Invalid length of Zcash receiver {typecode}");
+        match typecode {
+            P2PKH_TYPECODE => Ok(Self::P2pkh(data.try_into().map_err(|_| invalid_length())?)),
+            P2SH_TYPECODE => Ok(Self::P2sh(data.try_into().map_err(|_| invalid_length())?)),
+            SAPLING_TYPECODE => Ok(Self::Sapling(
+                data.try_into().map_err(|_| invalid_length())?,
+            )),
+            ORCHARD_TYPECODE => Ok(Self::Orchard(
+                data.try_into().map_err(|_| invalid_length())?,
+            )),
+            _ => Ok(Self::Unknown {
+                typecode,
+                data: data.to_vec(),
+            }),
+        }
+    }
+
+    pub const fn typecode(&self) -> u32 {
+        match self {
+            Self::P2pkh(_) => P2PKH_TYPECODE,
+            Self::P2sh(_) => P2SH_TYPECODE,
+            Self::Sapling(_) => SAPLING_TYPECODE,
+            Self::Orchard(_) => ORCHARD_TYPECODE,
+            Self::Unknown { typecode, .. } => *typecode,
+        }
+    }
+
+    fn data(&self) -> &[u8] {
+        match self {
+            Self::P2pkh(data) | Self::P2sh(data) => data,
+            Self::Sapling(data) | Self::Orchard(data) => data,
+            Self::Unknown { data, .. } => data,
+        }
+    }
+
+    const fn is_transparent(&self) -> bool {
+        matches!(self, Self::P2pkh(_) | Self::P2sh(_))
+    }
+}
+
+impl UnifiedAddress {
+    pub fn parse(address: &str, network: UtxoNetwork) -> Result<Self, String> {
+        let (hrp, data, variant) = encoding::bech32_decode(address)
+            .map_err(|err| format!("Invalid Zcash address: {err}"))?;
+        if variant != Bech32Variant::Bech32m {
+            return Err("Unified address must use bech32m".to_string());
+        }
+        if hrp != network.zcash_unified_hrp() {
+            return Err(format!(
+                "Zcash address is not a {network:?} unified address"
+            ));
+        }
+
+        let raw = f4jumble_inv(&encoding::convert_bits(&data, 5, 8, false)?)?;
+        let (mut items, padding) = raw.split_at(raw.len() - UNIFIED_PADDING_LEN);
+        if padding != unified_padding(&hrp) {
+            return Err("Invalid unified address padding".to_string());
+        }
+
+        let mut receivers = Vec::new();
+        while !items.is_empty() {
+            let typecode = read_compact_size(&mut items)?;
+            let len = usize::try_from(read_compact_size(&mut items)?)
+                .map_err(|_| "Invalid unified address item length".to_string())?;
+            let (data, rest) = items
+                .split_at_checked(len)
+                .ok_or_else(|| "Truncated unified address item".to_string())?;
+            let typecode = u32::try_from(typecode)
+                .map_err(|_| "Invalid unified address typecode".to_string())?;
+            receivers.push(Receiver::new(typecode, data)?);
+            items = rest;
+        }
+
+        let address = Self { receivers };
+        address.validate()?;
+        Ok(address)
+    }
+
+    pub fn encode(&self, network: UtxoNetwork) -> Result<String, String> {
+        self.validate()?;
+        let hrp = network.zcash_unified_hrp();
+
+        let mut raw = Vec::new();
+        for receiver in &self.receivers {
+            write_compact_size(&mut raw, u64::from(receiver.typecode()));
+            write_compact_size(&mut raw, receiver.data().len() as u64);
+            raw.extend_from_slice(receiver.data());
+        }
+        raw.extend_from_slice(&unified_padding(hrp));
+
+        let data = encoding::convert_bits(&f4jumble(&raw)?, 8, 5, true)?;
+        Ok(encoding::bech32_encode(hrp, &data, Bech32Variant::Bech32m))
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if !self
+            .receivers
+            .windows(2)
+            .all(|pair| pair[0].typecode() < pair[1].typecode())
+        {
+            return Err(
+                "Unified address receivers must be in ascending typecode order".to_string(),
+            );
+        }
+        if self.receivers.iter().all(Receiver::is_transparent) {
+            return Err("Unified address must contain a shielded receiver".to_string());
+        }
+        if self.receivers.iter().filter(|r| r.is_transparent()).count() > 1 {
+            return Err("Unified address contains both P2PKH and P2SH receivers".to_string());
+        }
+        Ok(())
+    }
+}
+
+fn unified_padding(hrp: &str) -> [u8; UNIFIED_PADDING_LEN] {
+    let mut padding = [0; UNIFIED_PADDING_LEN];
+    padding[..hrp.len()].copy_from_slice(hrp.as_bytes());
+    padding
+}
+
+fn read_compact_size(data: &mut &[u8]) -> Result<u64, String> {
+    let truncated = || "Truncated compact size".to_string();
+    let (&tag, rest) = data.split_first().ok_or_else(truncated)?;
+    let (len, min) = match tag {
+        0xfd => (2, 0xfd),
+        0xfe => (4, 0x1_0000),
+        0xff => (8, 0x1_0000_0000),
+        _ => {
+            *data = rest;
+            return Ok(u64::from(tag));
+        }
+    };
+
+    let (bytes, rest) = rest.split_at_checked(len).ok_or_else(truncated)?;
+    let mut value = [0; 8];
+    value[..len].copy_from_slice(bytes);
+    let value = u64::from_le_bytes(value);
+    if value < min {
+        return Err("Non-canonical compact size".to_string());
+    }
+
+    *data = rest;
+    Ok(value)
+}
+
+fn write_compact_size(data: &mut Vec<u8>, value: u64) {
+    let bytes = value.to_le_bytes();
+    match value {
+        0..=0xfc => data.push(bytes[0]),
+        0xfd..=0xffff => {
+            data.push(0xfd);
+            data.extend_from_slice(&bytes[..2]);
+        }
+        0x1_0000..=0xffff_ffff => {
+            data.push(0xfe);
+            data.extend_from_slice(&bytes[..4]);
+        }
+        _ => {
+            data.push(0xff);
+            data.extend_from_slice(&bytes);
+        }
+    }
+}
+
+fn f4jumble_personal(prefix: &[u8; 13], suffix: [u8; 3]) -> [u8; 16] {
+    let mut personal = [0; 16];
+    personal[..13].copy_from_slice(prefix);
+    personal[13..].copy_from_slice(&suffix);
+    personal
+}
+
+fn f4jumble_h(round: u8, left_len: usize, input: &[u8]) -> Vec<u8> {
+    blake2b(
+        left_len,
+        &f4jumble_personal(b"UA_F4Jumble_H", [round, 0, 0]),
+        input,
+    )
+}
+
+fn f4jumble_g(round: u8, right_len: usize, input: &[u8]) -> Result<Vec<u8>, String> {
+    let mut output = Vec::with_capacity(right_len);
+    for counter in 0..right_len.div_ceil(MAX_OUTPUT_LEN) {
+        let [low, high] = u16::try_from(counter)
+            .map_err(|_| "F4Jumble input is too long".to_string())?
+            .to_le_bytes();
+        output.extend(blake2b(
+            MAX_OUTPUT_LEN,
+            &f4jumble_personal(b"UA_F4Jumble_G", [round, low, high]),
+            input,
+        ));
+    }
+    output.truncate(right_len);
+    Ok(output)
+}
+
+fn xor(left: &[u8], right: &[u8]) -> Vec<u8> {
+    left.iter().zip(right).map(|(a, b)| a ^ b).collect()
+}
+
+fn f4jumble_split(len: usize) -> Result<usize, String> {
+    if (F4JUMBLE_MIN_LEN..=F4JUMBLE_MAX_LEN).contains(&len) {
+        Ok((len / 2).min(MAX_OUTPUT_LEN))
+    } else {
+        Err("Invalid unified address length".to_string())
+    }
+}
+
+fn f4jumble(message: &[u8]) -> Result<Vec<u8>, String> {
+    let (a, b) = message.split_at(f4jumble_split(message.len())?);
+    let x = xor(b, &f4jumble_g(0, b.len(), a)?);
+    let y = xor(a, &f4jumble_h(0, a.len(), &x));
+    let d = xor(&x, &f4jumble_g(1, x.len(), &y)?);
+    let c = xor(&y, &f4jumble_h(1, y.len(), &d));
+    Ok([c, d].concat())
+}
+
+fn f4jumble_inv(message: &[u8]) -> Result<Vec<u8>, String> {
+    let (c, d) = message.split_at(f4jumble_split(message.len())?);
+    let y = xor(c, &f4jumble_h(1, c.len(), d));
+    let x = xor(d, &f4jumble_g(1, d.len(), &y)?);
+    let a = xor(&y, &f4jumble_h(0, y.len(), &x));
+    let b = xor(&x, &f4jumble_g(0, x.len(), &a)?);
+    Ok([a, b].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unified_address() -> UnifiedAddress {
+        UnifiedAddress {
+            receivers: vec![
+                Receiver::P2pkh([1; 20]),
+                Receiver::Sapling([2; SHIELDED_RECEIVER_LEN]),
+                Receiver::Orchard([3; SHIELDED_RECEIVER_LEN]),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_transparent_addresses() {
+        let p2pkh = encoding::base58check_encode(&[&[0x1c, 0xb8][..], &[7; 20]].concat());
+        assert!(p2pkh.starts_with("t1"));
+        assert_eq!(
+            ZcashAddress::parse(&p2pkh, UtxoNetwork::Mainnet),
+            Ok(ZcashAddress::TransparentP2pkh([7; 20]))
+        );
+        assert!(ZcashAddress::parse(&p2pkh, UtxoNetwork::Testnet).is_err());
+
+        let p2sh = encoding::base58check_encode(&[&[0x1c, 0xba][..], &[7; 20]].concat());
+        assert!(p2sh.starts_with("t2"));
+        assert_eq!(
+            ZcashAddress::parse(&p2sh, UtxoNetwork::Testnet),
+            Ok(ZcashAddress::TransparentP2sh([7; 20]))
+        );
+
+        let mut tampered = p2pkh.into_bytes();
+        tampered[10] = if tampered[10] == b'a' { b'b' } else { b'a' };
+        let tampered = String::from_utf8(tampered).unwrap();
+        assert!(ZcashAddress::parse(&tampered, UtxoNetwork::Mainnet).is_err());
+    }
+
+    #[test]
+    fn test_unified_address_round_trip() {
+        let address = unified_address();
+
+        let mainnet = address.encode(UtxoNetwork::Mainnet).unwrap();
+        assert!(mainnet.starts_with("u1"));
+        assert_eq!(
+            ZcashAddress::parse(&mainnet, UtxoNetwork::Mainnet),
+            Ok(ZcashAddress::Unified(address.clone()))
+        );
+        assert!(ZcashAddress::parse(&mainnet, UtxoNetwork::Testnet).is_err());
+
+        let testnet = address.encode(UtxoNetwork::Testnet).unwrap();
+        assert!(testnet.starts_with("utest1"));
+        assert_eq!(
+            UnifiedAddress::parse(&testnet, UtxoNetwork::Testnet),
+            Ok(address)
+        );
+
+        // Unknown receivers are preserved, and the length is not capped at 90 characters
+        let long = UnifiedAddress {
+            receivers: vec![
+                Receiver::Orchard([3; SHIELDED_RECEIVER_LEN]),
+                Receiver::Unknown {
+                    typecode: 0x1000,
+                    data: vec![4; 300],
+                },
+            ],
+        };
+        let encoded = long.encode(UtxoNetwork::Mainnet).unwrap();
+        assert!(encoded.len() > 500);
+        assert_eq!(
+            UnifiedAddress::parse(&encoded, UtxoNetwork::Mainnet),
+            Ok(long)
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_unified_addresses() {
+        let mut tampered = unified_address()
+            .encode(UtxoNetwork::Mainnet)
+            .unwrap()
+            .into_bytes();
+        tampered[20] = if tampered[20] == b'q' { b'p' } else { b'q' };
+        let tampered = String::from_utf8(tampered).unwrap();
+        assert!(UnifiedAddress::parse(&tampered, UtxoNetwork::Mainnet).is_err());
+
+        let invalid = [
+            vec![Receiver::P2pkh([1; 20])],
+            vec![
+                Receiver::Orchard([3; SHIELDED_RECEIVER_LEN]),
+                Receiver::Sapling([2; SHIELDED_RECEIVER_LEN]),
+            ],
+            vec![
+                Receiver::P2pkh([1; 20]),
+                Receiver::P2sh([1; 20]),
+                Receiver::Orchard([3; SHIELDED_RECEIVER_LEN]),
+            ],
+        ];
+        for receivers in invalid {
+            assert!(UnifiedAddress { receivers }
+                .encode(UtxoNetwork::Mainnet)
+                .is_err());
+        }
+
+        assert!(
+            UnifiedAddress::parse(&format!("u{}", "1".repeat(499)), UtxoNetwork::Mainnet).is_err()
+        );
+    }
+
+    #[test]
+    fn test_compact_size() {
+        for value in [0, 0xfc, 0xfd, 0xffff, 0x1_0000, 0xffff_ffff, 0x1_0000_0000] {
+            let mut data = Vec::new();
+            write_compact_size(&mut data, value);
+            let mut slice = data.as_slice();
+            assert_eq!(read_compact_size(&mut slice), Ok(value));
+            assert!(slice.is_empty());
+        }
+
+        let mut non_canonical: &[u8] = &[0xfd, 0x10, 0x00];
+        assert!(read_compact_size(&mut non_canonical).is_err());
+        let mut truncated: &[u8] = &[0xfe, 0x10];
+        assert!(read_compact_size(&mut truncated).is_err());
+    }
+
+    #[test]
+    fn test_f4jumble_is_invertible() {
+        for len in [F4JUMBLE_MIN_LEN, 127, 128, 300] {
+            let message: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let jumbled = f4jumble(&message).unwrap();
+            assert_ne!(jumbled, message);
+            assert_eq!(f4jumble_inv(&jumbled).unwrap(), message);
+        }
+        assert!(f4jumble(&[0; F4JUMBLE_MIN_LEN - 1]).is_err());
+    }
+}
@@ -1,16 +1,33 @@
 use near_plugins::{
     access_control, access_control_any, AccessControlRole, AccessControllable, Pausable, Upgradable,
 };
-use near_sdk::borsh::BorshDeserialize;
-use near_sdk::json_types::Base58CryptoHash;
+use near_sdk::borsh::{BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, UnorderedSet, Vector};
+use near_sdk::json_types::{Base58CryptoHash, U128};
 use near_sdk::serde_json::json;
-use near_sdk::{env, near, AccountId, CryptoHash, Gas, NearToken, PanicOnDefault, Promise};
-use omni_types::BasicMetadata;
+use near_sdk::{
+    env, near, require, AccountId, BorshStorageKey, CryptoHash, Gas, NearToken, PanicOnDefault,
+    Promise,
+};
+use omni_types::errors::TokenError;
+use omni_types::{BasicMetadata, MintRateLimit};
 
 mod migrate;
+pub mod upgrade;
+
+use upgrade::CodeVersion;
 
 const NO_DEPOSIT: NearToken = NearToken::from_near(0);
 const OMNI_TOKEN_INIT_GAS: Gas = Gas::from_tgas(10);
+const SET_MINT_LIMITS_GAS: Gas = Gas::from_tgas(10);
+
+#[derive(BorshSerialize, BorshStorageKey)]
+enum StorageKey {
+    CodeVersions,
+    DeployedTokens,
+    TokenCodeVersions,
+    CanaryTokens,
+}
 
 #[near(serializers = [json])]
 #[derive(AccessControlRole, Copy, Clone)]
@@ -42,15 +59,18 @@ pub enum Role {
 // https://github.com/Near-One/rainbow-token-connector/blob/7d8ee3b086cee4d478e1e104c3d6ef5e5625aadd/bridge-token-factory/src/lib.rs#L214C27-L214C28
 pub struct TokenDeployer {
     global_code_hash: CryptoHash,
+    current_code_version: u32,
+    code_versions: Vector<CodeVersion>,
+    deployed_tokens: UnorderedSet<AccountId>,
+    token_code_versions: LookupMap<AccountId, u32>,
+    canary_tokens: UnorderedSet<AccountId>,
 }
 
 #[near]
 impl TokenDeployer {
     #[init]
     pub fn new(controller: AccountId, dao: AccountId, global_code_hash: Base58CryptoHash) -> Self {
-        let mut contract = Self {
-            global_code_hash: global_code_hash.into(),
-        };
+        let mut contract = Self::with_global_code_hash(global_code_hash);
 
         contract.acl_init_super_admin(near_sdk::env::predecessor_account_id());
         contract.acl_grant_role(Role::DAO.into(), dao.clone());
@@ -59,12 +79,26 @@ impl TokenDeployer {
         contract
     }
 
+    /// The storage of the deployer's records of the token is paid from the attached deposit,
+    /// the rest is transferred to the token account.
     #[payable]
     #[access_control_any(roles(Role::Controller, Role::LegacyController))]
     pub fn deploy_token(&mut self, account_id: AccountId, metadata: &BasicMetadata) -> Promise {
+        let storage_usage = env::storage_usage();
+        self.deployed_tokens.insert(&account_id);
+        self.token_code_versions
+            .insert(&account_id, &self.current_code_version);
+
+        let required_deposit = env::storage_byte_cost()
+            .saturating_mul(env::storage_usage().saturating_sub(storage_usage).into());
+        require!(
+            env::attached_deposit() >= required_deposit,
+            TokenError::InsufficientStorageDeposit.as_ref()
+        );
+
         Promise::new(account_id)
             .create_account()
-            .transfer(env::attached_deposit())
+            .transfer(env::attached_deposit().saturating_sub(required_deposit))
             .use_global_contract(self.global_code_hash)
             .function_call(
                 "new".to_string(),
@@ -80,8 +114,49 @@ impl TokenDeployer {
         self.global_code_hash.into()
     }
 
+    #[access_control_any(roles(Role::DAO))]
+    pub fn set_token_mint_limits(
+        &mut self,
+        token: AccountId,
+        max_total_supply: Option<U128>,
+        mint_rate_limit: Option<MintRateLimit>,
+    ) -> Promise {
+        Promise::new(token).function_call(
+            "set_mint_limits".to_string(),
+            json!({
+                "max_total_supply": max_total_supply,
+                "mint_rate_limit": mint_rate_limit,
+            })
+            .to_string()
+            .into_bytes(),
+            NO_DEPOSIT,
+            SET_MINT_LIMITS_GAS,
+        )
+    }
+
+    /// Records `global_code_hash` as a new code version without release notes and uses it for
+    /// future deployments.
     #[access_control_any(roles(Role::DAO))]
     pub fn set_global_code_hash(&mut self, global_code_hash: Base58CryptoHash) {
-        self.global_code_hash = global_code_hash.into();
+        let version =
+            self.internal_add_code_version(global_code_hash, String::new(), String::new());
+        self.internal_promote_code_version(version);
+    }
+}
+
+impl TokenDeployer {
+    fn with_global_code_hash(global_code_hash: Base58CryptoHash) -> Self {
+        let mut contract = Self {
+            global_code_hash: global_code_hash.into(),
+            current_code_version: 0,
+            code_versions: Vector::new(StorageKey::CodeVersions),
+            deployed_tokens: UnorderedSet::new(StorageKey::DeployedTokens),
+            token_code_versions: LookupMap::new(StorageKey::TokenCodeVersions),
+            canary_tokens: UnorderedSet::new(StorageKey::CanaryTokens),
+        };
+        let version =
+            contract.internal_add_code_version(global_code_hash, String::new(), String::new());
+        contract.internal_promote_code_version(version);
+        contract
     }
 }
@@ -1,7 +1,7 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use near_sdk::{
-    collections::UnorderedSet, env, json_types::Base58CryptoHash, near, AccountId, PanicOnDefault,
-    PublicKey,
+    collections::UnorderedSet, env, json_types::Base58CryptoHash, near, AccountId, CryptoHash,
+    PanicOnDefault, PublicKey,
 };
 
 use crate::{TokenDeployer, TokenDeployerExt};
@@ -11,6 +11,12 @@ const STATE_KEY: &[u8] = b"STATE";
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct OldState {}
 
+/// State before the deployer tracked code versions and deployed tokens.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldStateV1 {
+    pub global_code_hash: CryptoHash,
+}
+
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct OldLegacyState {
     pub prover_account: AccountId,
@@ -24,6 +30,8 @@ pub struct OldLegacyState {
 
 #[near]
 impl TokenDeployer {
+    /// Old states don't list the deployed tokens, so tokens deployed before the migration
+    /// are unknown to `upgrade_tokens` until the DAO adds them with `register_tokens`.
     #[private]
     #[init(ignore_state)]
     pub fn migrate(global_code_hash: Base58CryptoHash) -> Self {
@@ -35,11 +43,10 @@ impl TokenDeployer {
             .unwrap_or_else(|| env::panic_str("Failed to read state key."));
 
         if OldState::try_from_slice(&state).is_ok()
+            || OldStateV1::try_from_slice(&state).is_ok()
             || OldLegacyState::try_from_slice(&state).is_ok()
         {
-            Self {
-                global_code_hash: global_code_hash.into(),
-            }
+            Self::with_global_code_hash(global_code_hash)
         } else {
             env::panic_str("Old state not found. Migration is not needed.")
         }
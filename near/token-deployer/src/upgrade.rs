@@ -0,0 +1,242 @@
+use near_plugins::{access_control_any, AccessControllable};
+use near_sdk::json_types::{Base58CryptoHash, U64};
+use near_sdk::{env, near, require, AccountId, CryptoHash, Gas, Promise};
+use omni_types::errors::TokenError;
+
+use crate::{Role, TokenDeployer, TokenDeployerExt, NO_DEPOSIT};
+
+const UPGRADE_TOKEN_GAS: Gas = Gas::from_tgas(20);
+const UPGRADE_TOKEN_CALLBACK_GAS: Gas = Gas::from_tgas(5);
+/// Maximum number of tokens upgraded by a single `upgrade_tokens` call.
+pub const MAX_UPGRADE_BATCH_SIZE: usize = 8;
+
+#[near(serializers=[borsh, json])]
+#[derive(Debug, Clone)]
+pub struct CodeVersion {
+    pub version: u32,
+    pub code_hash: Base58CryptoHash,
+    pub release_notes: String,
+    /// Checksum of the released artifact, e.g. the sha256 of the reproducible build.
+    pub checksum: String,
+    pub created_at: U64,
+    /// Versions that aren't promoted yet can only be rolled out to the canary tokens.
+    pub promoted: bool,
+}
+
+#[near(event_json(standard = "omni-token-deployer"))]
+pub enum TokenDeployerEvent {
+    #[event_version("1.0.0")]
+    CodeVersionAdded {
+        version: u32,
+        code_hash: Base58CryptoHash,
+    },
+    #[event_version("1.0.0")]
+    CodeVersionPromoted {
+        version: u32,
+        code_hash: Base58CryptoHash,
+    },
+    #[event_version("1.0.0")]
+    TokenUpgraded { token: AccountId, version: u32 },
+    #[event_version("1.0.0")]
+    TokenUpgradeFailed { token: AccountId, version: u32 },
+}
+
+#[near]
+impl TokenDeployer {
+    /// Registers a global code hash as a new version. Tokens aren't affected until the
+    /// version is rolled out with `upgrade_tokens`.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_code_version(
+        &mut self,
+        code_hash: Base58CryptoHash,
+        release_notes: String,
+        checksum: String,
+    ) -> u32 {
+        self.internal_add_code_version(code_hash, release_notes, checksum)
+    }
+
+    /// Uses the version for future deployments and allows rolling it out to all tokens.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn promote_code_version(&mut self, version: u32) {
+        self.internal_promote_code_version(version);
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn add_canary_tokens(&mut self, tokens: Vec<AccountId>) {
+        for token in tokens {
+            require!(
+                self.deployed_tokens.contains(&token),
+                TokenError::TokenNotRegistered.as_ref()
+            );
+            self.canary_tokens.insert(&token);
+        }
+    }
+
+    #[access_control_any(roles(Role::DAO))]
+    pub fn remove_canary_tokens(&mut self, tokens: Vec<AccountId>) {
+        for token in tokens {
+            self.canary_tokens.remove(&token);
+        }
+    }
+
+    /// Registers tokens deployed before the deployer tracked its tokens, so they can be
+    /// upgraded with `upgrade_tokens`. Their code version stays unknown until then.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn register_tokens(&mut self, tokens: Vec<AccountId>) {
+        for token in tokens {
+            require!(
+                token.is_sub_account_of(&env::current_account_id()),
+                TokenError::InvalidParentAccount.as_ref()
+            );
+            self.deployed_tokens.insert(&token);
+        }
+    }
+
+    /// Moves a batch of tokens onto the global code of `version`. Promoted versions go
+    /// through all deployed tokens, other versions only through the canary tokens.
+    /// `from_index` pages through the respective set; tokens already on the version are
+    /// skipped. Each result is reported with a `token_upgraded` or `token_upgrade_failed`
+    /// event. Returns the tokens being upgraded.
+    #[access_control_any(roles(Role::DAO))]
+    pub fn upgrade_tokens(
+        &self,
+        version: u32,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        let code_version = self
+            .get_code_version(version)
+            .unwrap_or_else(|| env::panic_str(TokenError::UnknownCodeVersion.as_ref()));
+        let code_hash = CryptoHash::from(code_version.code_hash);
+        let tokens = if code_version.promoted {
+            &self.deployed_tokens
+        } else {
+            &self.canary_tokens
+        };
+
+        let batch: Vec<AccountId> = tokens
+            .iter()
+            .skip(usize::try_from(from_index.unwrap_or_default()).unwrap_or(usize::MAX))
+            .take(
+                usize::try_from(limit.unwrap_or(u64::MAX))
+                    .unwrap_or(usize::MAX)
+                    .min(MAX_UPGRADE_BATCH_SIZE),
+            )
+            .filter(|token| self.token_code_versions.get(token) != Some(version))
+            .collect();
+
+        for token in &batch {
+            // `upgrade_and_migrate` reads a raw 32-byte input as a global code hash
+            Promise::new(token.clone())
+                .function_call(
+                    "upgrade_and_migrate".to_string(),
+                    code_hash.to_vec(),
+                    NO_DEPOSIT,
+                    UPGRADE_TOKEN_GAS,
+                )
+                .then(
+                    Self::ext(env::current_account_id())
+                        .with_static_gas(UPGRADE_TOKEN_CALLBACK_GAS)
+                        .upgrade_token_callback(token.clone(), version),
+                )
+                .detach();
+        }
+
+        batch
+    }
+
+    #[private]
+    pub fn upgrade_token_callback(&mut self, token: AccountId, version: u32) {
+        if env::promise_result_checked(0, usize::MAX).is_ok() {
+            self.token_code_versions.insert(&token, &version);
+            TokenDeployerEvent::TokenUpgraded { token, version }.emit();
+        } else {
+            TokenDeployerEvent::TokenUpgradeFailed { token, version }.emit();
+        }
+    }
+
+    pub fn get_code_version(&self, version: u32) -> Option<CodeVersion> {
+        self.code_versions.get(u64::from(version))
+    }
+
+    pub fn get_code_versions(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<CodeVersion> {
+        let from_index = from_index.unwrap_or_default();
+        let to_index = limit.map_or(self.code_versions.len(), |limit| {
+            from_index
+                .saturating_add(limit)
+                .min(self.code_versions.len())
+        });
+        (from_index..to_index)
+            .filter_map(|index| self.code_versions.get(index))
+            .collect()
+    }
+
+    pub fn get_current_code_version(&self) -> u32 {
+        self.current_code_version
+    }
+
+    pub fn get_deployed_tokens(
+        &self,
+        from_index: Option<u64>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        self.deployed_tokens
+            .iter()
+            .skip(usize::try_from(from_index.unwrap_or_default()).unwrap_or(usize::MAX))
+            .take(usize::try_from(limit.unwrap_or(u64::MAX)).unwrap_or(usize::MAX))
+            .collect()
+    }
+
+    pub fn get_canary_tokens(&self) -> Vec<AccountId> {
+        self.canary_tokens.to_vec()
+    }
+
+    /// Returns `None` for tokens registered with `register_tokens` that weren't upgraded yet.
+    pub fn get_token_code_version(&self, token: &AccountId) -> Option<u32> {
+        self.token_code_versions.get(token)
+    }
+}
+
+impl TokenDeployer {
+    pub(crate) fn internal_add_code_version(
+        &mut self,
+        code_hash: Base58CryptoHash,
+        release_notes: String,
+        checksum: String,
+    ) -> u32 {
+        let version = u32::try_from(self.code_versions.len())
+            .unwrap_or_else(|_| env::panic_str(TokenError::UnknownCodeVersion.as_ref()));
+        self.code_versions.push(&CodeVersion {
+            version,
+            code_hash,
+            release_notes,
+            checksum,
+            created_at: U64(env::block_timestamp()),
+            promoted: false,
+        });
+
+        TokenDeployerEvent::CodeVersionAdded { version, code_hash }.emit();
+        version
+    }
+
+    pub(crate) fn internal_promote_code_version(&mut self, version: u32) {
+        let mut code_version = self
+            .get_code_version(version)
+            .unwrap_or_else(|| env::panic_str(TokenError::UnknownCodeVersion.as_ref()));
+        code_version.promoted = true;
+        self.code_versions
+            .replace(u64::from(version), &code_version);
+        self.global_code_hash = code_version.code_hash.into();
+        self.current_code_version = version;
+
+        TokenDeployerEvent::CodeVersionPromoted {
+            version,
+            code_hash: code_version.code_hash,
+        }
+        .emit();
+    }
+}
@@ -32,6 +32,14 @@ include!(concat!(env!("OUT_DIR"), "/chain_id.rs"));
 #[constant]
 pub const MAX_ALLOWED_DECIMALS: u8 = 9;
 
+/// Byte length bound of `InitTransferPayload::recipient`, an omni address such as `near:<account>`.
+#[constant]
+pub const MAX_RECIPIENT_LEN: u32 = 128;
+
+/// Byte length bound of `InitTransferPayload::message`, same as `MAX_INIT_TRANSFER_MSG_LEN` on NEAR.
+#[constant]
+pub const MAX_MESSAGE_LEN: u32 = 2048;
+
 #[constant]
 pub const INIT_TRANSFER_PAUSED: u8 = 1 << 0;
 
@@ -24,4 +24,8 @@ pub enum ErrorCode {
     Unauthorized,
     #[msg("Amount overflow (unexpected state)")]
     AmountOverflow,
+    #[msg("String too long")]
+    StringTooLong,
+    #[msg("Invalid characters")]
+    InvalidCharacters,
 }
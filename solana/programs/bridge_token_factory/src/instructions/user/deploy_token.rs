@@ -10,7 +10,7 @@ use crate::state::message::{
 };
 use anchor_lang::prelude::*;
 use solana_program::hash::hash;
-use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::mpl_token_metadata::{types::DataV2, MAX_URI_LENGTH};
 use anchor_spl::metadata::{
     create_metadata_accounts_v3, CreateMetadataAccountsV3, Metadata as Metaplex, ID as MetaplexID,
 };
@@ -71,9 +71,20 @@ pub struct DeployToken<'info> {
 }
 
 impl DeployToken<'_> {
-    pub fn initialize_token_metadata(&self, mut metadata: DeployTokenPayload) -> Result<()> {
+    /// Metaplex metadata has no icon field, wallets take the image from the JSON at `uri`,
+    /// so only the `reference` of a signed `DeployTokenExtension` is stored.
+    pub fn initialize_token_metadata(
+        &self,
+        mut metadata: DeployTokenPayload,
+        reference: Option<String>,
+    ) -> Result<()> {
         let bump = &[self.common.config.bumps.authority];
         let signer_seeds = &[&[AUTHORITY_SEED, bump][..]];
+        let uri = reference.unwrap_or_default();
+        require!(
+            uri.len() <= MAX_URI_LENGTH,
+            crate::error::ErrorCode::StringTooLong
+        );
         let origin_decimals = metadata.decimals;
         metadata.decimals = std::cmp::min(MAX_ALLOWED_DECIMALS, metadata.decimals);
 
@@ -96,7 +107,7 @@ impl DeployToken<'_> {
             DataV2 {
                 name: metadata.name,
                 symbol: metadata.symbol,
-                uri: String::new(),
+                uri,
                 seller_fee_basis_points: 0,
                 creators: None,
                 collection: None,
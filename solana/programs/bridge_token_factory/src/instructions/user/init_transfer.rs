@@ -70,6 +70,7 @@ pub struct InitTransfer<'info> {
 
 impl InitTransfer<'_> {
     pub fn process(&self, payload: &InitTransferPayload) -> Result<()> {
+        payload.validate()?;
         require!(payload.amount > payload.fee, ErrorCode::InvalidFee);
 
         if payload.native_fee > 0 {
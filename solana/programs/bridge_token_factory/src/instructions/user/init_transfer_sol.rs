@@ -33,6 +33,7 @@ pub struct InitTransferSol<'info> {
 
 impl InitTransferSol<'_> {
     pub fn process(&self, payload: &InitTransferPayload) -> Result<()> {
+        payload.validate()?;
         require!(payload.fee == 0, ErrorCode::InvalidFee);
         require!(payload.amount > 0, ErrorCode::InvalidArgs);
 
@@ -9,8 +9,10 @@ use instructions::{
     __client_accounts_log_metadata, __client_accounts_pause, __client_accounts_update_metadata,
 };
 use state::message::{
-    deploy_token::DeployTokenPayload, finalize_transfer::FinalizeTransferPayload,
-    init_transfer::InitTransferPayload, SignedPayload,
+    deploy_token::{DeployTokenExtension, DeployTokenPayload},
+    finalize_transfer::FinalizeTransferPayload,
+    init_transfer::InitTransferPayload,
+    SignedPayload,
 };
 
 pub mod constants;
@@ -28,8 +30,8 @@ pub mod bridge_token_factory {
 
     use super::constants::{FINALIZE_TRANSFER_PAUSED, INIT_TRANSFER_PAUSED};
     use super::{
-        msg, ChangeConfig, Context, DeployToken, DeployTokenPayload, FinalizeTransfer,
-        FinalizeTransferPayload, FinalizeTransferSol, GetVersion, InitTransfer,
+        msg, ChangeConfig, Context, DeployToken, DeployTokenExtension, DeployTokenPayload,
+        FinalizeTransfer, FinalizeTransferPayload, FinalizeTransferSol, GetVersion, InitTransfer,
         InitTransferPayload, InitTransferSol, Initialize, Key, LogMetadata, Pause, Pubkey, Result,
         SignedPayload, UpdateMetadata,
     };
@@ -69,8 +71,28 @@ pub mod bridge_token_factory {
     ) -> Result<()> {
         msg!("Deploying token");
 
-        data.verify_signature((), &ctx.accounts.common.config.derived_near_bridge_address)?;
-        ctx.accounts.initialize_token_metadata(data.payload)?;
+        data.verify_signature(
+            None,
+            &ctx.accounts.common.config.derived_near_bridge_address,
+        )?;
+        ctx.accounts.initialize_token_metadata(data.payload, None)?;
+
+        Ok(())
+    }
+
+    pub fn deploy_token_v2(
+        ctx: Context<DeployToken>,
+        data: SignedPayload<DeployTokenPayload>,
+        extension: DeployTokenExtension,
+    ) -> Result<()> {
+        msg!("Deploying token");
+
+        data.verify_signature(
+            Some(extension.clone()),
+            &ctx.accounts.common.config.derived_near_bridge_address,
+        )?;
+        ctx.accounts
+            .initialize_token_metadata(data.payload, extension.reference)?;
 
         Ok(())
     }
@@ -13,13 +13,37 @@ pub struct DeployTokenPayload {
     pub decimals: u8,
 }
 
+/// Icon and reference signed together with a `DeployTokenPayload` by `deploy_token_v2`.
+/// Kept out of `DeployTokenPayload` so `deploy_token` keeps its instruction layout.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DeployTokenExtension {
+    pub icon: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<[u8; 32]>,
+}
+
+impl DeployTokenExtension {
+    fn is_empty(&self) -> bool {
+        self.icon.is_none() && self.reference.is_none() && self.reference_hash.is_none()
+    }
+}
+
 impl Payload for DeployTokenPayload {
-    type AdditionalParams = ();
+    type AdditionalParams = Option<DeployTokenExtension>;
 
-    fn serialize_for_near(&self, _params: Self::AdditionalParams) -> Result<Vec<u8>> {
+    fn serialize_for_near(&self, extension: Self::AdditionalParams) -> Result<Vec<u8>> {
         let mut writer = BufWriter::new(Vec::with_capacity(DEFAULT_SERIALIZER_CAPACITY));
-        IncomingMessageType::Metadata.serialize(&mut writer)?;
-        self.serialize(&mut writer)?; // borsh encoding
+        match extension.filter(|extension| !extension.is_empty()) {
+            Some(extension) => {
+                IncomingMessageType::MetadataV2.serialize(&mut writer)?;
+                self.serialize(&mut writer)?; // borsh encoding
+                extension.serialize(&mut writer)?;
+            }
+            None => {
+                IncomingMessageType::Metadata.serialize(&mut writer)?;
+                self.serialize(&mut writer)?; // borsh encoding
+            }
+        }
         writer
             .into_inner()
             .map_err(|_| error!(ErrorCode::InvalidArgs))
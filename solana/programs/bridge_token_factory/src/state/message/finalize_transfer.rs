@@ -67,6 +67,8 @@ impl Payload for FinalizeTransferResponse {
         self.amount.serialize(&mut writer)?;
         // 4. fee_recipient
         self.fee_recipient.serialize(&mut writer)?;
+        // 5. global_transfer_hash
+        writer.write_all(&self.transfer_id.global_hash())?;
 
         writer
             .into_inner()
@@ -1,7 +1,10 @@
 use std::io::{BufWriter, Write};
 
 use super::{OutgoingMessageType, Payload, DEFAULT_SERIALIZER_CAPACITY};
-use crate::{constants::SOLANA_OMNI_BRIDGE_CHAIN_ID, error::ErrorCode};
+use crate::{
+    constants::{MAX_MESSAGE_LEN, MAX_RECIPIENT_LEN, SOLANA_OMNI_BRIDGE_CHAIN_ID},
+    error::ErrorCode,
+};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -13,6 +16,22 @@ pub struct InitTransferPayload {
     pub message: String,
 }
 
+impl InitTransferPayload {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.recipient.len() <= MAX_RECIPIENT_LEN as usize
+                && self.message.len() <= MAX_MESSAGE_LEN as usize,
+            ErrorCode::StringTooLong
+        );
+        require!(
+            !self.recipient.chars().any(char::is_control),
+            ErrorCode::InvalidCharacters
+        );
+
+        Ok(())
+    }
+}
+
 impl Payload for InitTransferPayload {
     type AdditionalParams = (u64, Pubkey, Pubkey); // nonce, sender, token_address
 
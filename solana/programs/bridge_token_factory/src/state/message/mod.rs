@@ -51,6 +51,8 @@ impl<P: Payload> SignedPayload<P> {
 pub enum IncomingMessageType {
     InitTransfer,
     Metadata,
+    ClaimNativeFee,
+    MetadataV2,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -67,4 +69,19 @@ pub struct TransferId {
     pub origin_nonce: u64,
 }
 
+impl TransferId {
+    /// Hash identifying the transfer on every chain, matching `UnifiedTransferId::global_hash`
+    /// on NEAR: `keccak256("omni-bridge:transfer" || borsh(UnifiedTransferId))`.
+    pub fn global_hash(&self) -> [u8; 32] {
+        keccak::hashv(&[
+            b"omni-bridge:transfer",
+            &[self.origin_chain],
+            // `TransferIdKind::Nonce`
+            &[0],
+            &self.origin_nonce.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
 const DEFAULT_SERIALIZER_CAPACITY: usize = 1024;